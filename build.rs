@@ -0,0 +1,55 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    grpc::compile();
+}
+
+/// 生成 gRPC 服务端代码
+///
+/// 消息类型在 `src/grpc.rs` 中手写，这里只生成服务骨架，不依赖 protoc。
+/// 服务定义需与 `proto/bedu_claim.proto` 保持一致。
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, MethodBuilder, Service};
+
+    fn method(name: &str, route: &str, input: &str, output: &str) -> MethodBuilder {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("super::{}", input))
+            .output_type(format!("super::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+    }
+
+    pub fn compile() {
+        println!("cargo:rerun-if-changed=build.rs");
+
+        let service = Service::builder()
+            .name("ClaimerControl")
+            .package("bedu_claim.v1")
+            .method(method("start", "Start", "StartRequest", "ClaimerStatus").build())
+            .method(method("stop", "Stop", "StopRequest", "ClaimerStatus").build())
+            .method(
+                method(
+                    "configure",
+                    "Configure",
+                    "ConfigureRequest",
+                    "ClaimerStatus",
+                )
+                .build(),
+            )
+            .method(method("get_status", "GetStatus", "StatusRequest", "ClaimerStatus").build())
+            .method(
+                method(
+                    "watch_events",
+                    "WatchEvents",
+                    "WatchEventsRequest",
+                    "ClaimEvent",
+                )
+                .server_streaming()
+                .build(),
+            )
+            .build();
+
+        Builder::new().build_client(false).compile(&[service]);
+    }
+}
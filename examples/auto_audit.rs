@@ -0,0 +1,69 @@
+use anyhow::Result;
+use bedu_claim::api::ClueDetailData;
+use bedu_claim::client::{
+    AutoAuditor, AutoAuditorConfig, AutoClaimConfig, AutoClaimer, ClaimedTask, Judge, Verdict,
+};
+
+/// 简单的关键词判定器：题干包含敏感词时驳回，缺少答案时跳过，其余通过
+struct KeywordJudge {
+    blocked: Vec<String>,
+}
+
+impl Judge for KeywordJudge {
+    async fn judge(&self, _task: &ClaimedTask, detail: &ClueDetailData) -> Result<Verdict> {
+        let content = &detail.content;
+        if let Some(word) = self.blocked.iter().find(|w| content.stem.contains(*w)) {
+            return Ok(Verdict::Reject {
+                reason: format!("题干包含敏感词: {}", word),
+            });
+        }
+        if content.answer.is_empty() {
+            return Ok(Verdict::Skip);
+        }
+
+        Ok(Verdict::Approve)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let claimer = AutoClaimer::new(AutoClaimConfig {
+        cookie: "your_cookie_here".to_string(),
+        claim_limit: 5,
+        ..Default::default()
+    })?;
+
+    let auditor = AutoAuditor::new(
+        claimer.client(),
+        KeywordJudge {
+            blocked: vec!["广告".to_string()],
+        },
+        AutoAuditorConfig {
+            dry_run: true,
+            ..Default::default()
+        },
+    );
+
+    // 认领与审核并行：认领成功的任务通过事件流交给审核器
+    let receiver = claimer.subscribe();
+    let (summary, decisions) = tokio::join!(
+        async {
+            let summary = claimer.start().await;
+            drop(claimer);
+            summary
+        },
+        auditor.run(receiver)
+    );
+
+    println!("认领 {} 个任务", summary?.total_claims);
+    for decision in &decisions {
+        println!(
+            "任务 {}: {:?} (已提交: {})",
+            decision.task_id, decision.verdict, decision.submitted
+        );
+    }
+
+    Ok(())
+}
@@ -15,10 +15,11 @@ async fn example_auto_claimer() -> Result<()> {
         step_id: 1,
         subject_id: 2,
         clue_type_id: 1,
+        ..Default::default()
     };
 
     // 创建自动认领器
-    let claimer = AutoClaimer::new(config);
+    let claimer = AutoClaimer::new(config)?;
 
     // 验证用户
     let user_name = claimer.validate_user().await?;
@@ -59,18 +60,28 @@ async fn example_auto_claimer() -> Result<()> {
 
 /// 示例2：直接使用HTTP客户端
 async fn example_http_client() -> Result<()> {
-    let client = HttpClient::new(
+    let client = HttpClient::try_new(
         "https://easylearn.baidu.com".to_string(),
         "your_cookie_here".to_string(),
-    );
+    )?;
 
     // 获取用户信息
-    let user_info = client.get_user_info().await?;
-    if user_info.errno == 0 {
-        println!("用户名: {}", user_info.data.user_name);
-        println!("角色: {:?}", user_info.data.role_names);
-    } else {
-        println!("获取用户信息失败: {}", user_info.errmsg);
+    match client.get_user_info().await?.into_result() {
+        Ok(user_info) => {
+            println!("用户名: {}", user_info.user_name);
+            println!("角色: {:?}", user_info.role_names);
+        }
+        Err(e) => println!("获取用户信息失败: {}", e),
+    }
+
+    // 获取筛选标签
+    if let Ok(labels) = client.get_labels("audittask").await?.into_result() {
+        for filter in &labels.filter {
+            println!("{} ({}):", filter.name, filter.id);
+            for option in &filter.list {
+                println!("  {} - {}", option.id, option.name);
+            }
+        }
     }
 
     // 获取任务列表
@@ -80,28 +91,28 @@ async fn example_http_client() -> Result<()> {
     options.insert("step".to_string(), json!(1));
     options.insert("clueType".to_string(), json!(1));
 
-    let tasks = client.get_audit_task_list(&options).await?;
-    if tasks.errno == 0 {
-        println!("任务总数: {}", tasks.data.total);
-        println!("当前页任务数: {}", tasks.data.list.len());
+    match client.get_audit_task_list(&options).await?.into_result() {
+        Ok(tasks) => {
+            println!("任务总数: {}", tasks.total);
+            println!("当前页任务数: {}", tasks.list.len());
 
-        for task in &tasks.data.list {
-            println!("任务 {}: {}", task.task_id, task.brief);
-        }
+            for task in &tasks.list {
+                println!("任务 {}: {}", task.task_id, task.brief);
+            }
 
-        // 如果有任务，尝试认领第一个
-        if !tasks.data.list.is_empty() {
-            let task_ids = vec![tasks.data.list[0].task_id.to_string()];
-            let claim_result = client.claim_audit_task(task_ids, "audittask").await?;
+            // 如果有任务，尝试认领第一个
+            if let Some(task) = tasks.list.first() {
+                let task_ids = vec![task.task_id.to_string()];
+                let claim_result = client.claim_audit_task(task_ids, "audittask").await?;
 
-            if claim_result.errno == 0 {
-                println!("认领成功!");
-            } else {
-                println!("认领失败: {}", claim_result.errmsg);
+                if claim_result.is_ok() {
+                    println!("认领成功!");
+                } else {
+                    println!("认领失败: {}", claim_result.errmsg);
+                }
             }
         }
-    } else {
-        println!("获取任务列表失败: {}", tasks.errmsg);
+        Err(e) => println!("获取任务列表失败: {}", e),
     }
 
     Ok(())
@@ -118,9 +129,10 @@ async fn example_status_monitor() -> Result<()> {
         step_id: 1,
         subject_id: 2,
         clue_type_id: 1,
+        ..Default::default()
     };
 
-    let claimer = AutoClaimer::new(config);
+    let claimer = AutoClaimer::new(config)?;
 
     // 模拟状态监控
     println!("=== 认领器状态监控 ===");
@@ -187,7 +199,7 @@ async fn example_status_monitor() -> Result<()> {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    env_logger::init();
+    tracing_subscriber::fmt::init();
 
     println!("=== 百度教育API库使用示例 ===\n");
 
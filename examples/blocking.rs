@@ -0,0 +1,28 @@
+//! 不使用 async 的同步调用：`cargo run --example blocking --features blocking`
+
+use anyhow::Result;
+use bedu_claim::client::{AutoClaimConfig, AutoClaimerBlocking, HttpClientBlocking};
+
+fn main() -> Result<()> {
+    let client = HttpClientBlocking::try_new(
+        "https://easylearn.baidu.com".to_string(),
+        "your_cookie_here".to_string(),
+    )?;
+    let user_info = client.get_user_info()?.into_result()?;
+    println!("用户名: {}", user_info.user_name);
+
+    let config = AutoClaimConfig {
+        server_base_url: "https://easylearn.baidu.com".to_string(),
+        cookie: "your_cookie_here".to_string(),
+        claim_limit: 5,
+        ..Default::default()
+    };
+    let claimer = AutoClaimerBlocking::new(config)?;
+    let summary = claimer.start()?;
+    println!(
+        "认领 {} 个任务，尝试 {} 次",
+        summary.total_claims, summary.attempts
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use bedu_claim::client::{HttpClient, Middleware, Next};
+use futures::future::BoxFuture;
+use reqwest::header::HeaderValue;
+use reqwest::{Request, Response};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// 为每个请求添加自定义请求头
+struct TraceHeader;
+
+impl Middleware for TraceHeader {
+    fn handle<'a>(
+        &'a self,
+        mut request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response>> {
+        request
+            .headers_mut()
+            .insert("X-Client", HeaderValue::from_static("bedu-claim"));
+        next.run(request)
+    }
+}
+
+/// 统计请求次数并打印耗时
+#[derive(Default)]
+struct Metrics {
+    requests: AtomicUsize,
+}
+
+impl Middleware for Metrics {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            let count = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+            let path = request.url().path().to_string();
+            let started = Instant::now();
+            let response = next.run(request).await?;
+            println!(
+                "#{} {} -> {} ({:?})",
+                count,
+                path,
+                response.status(),
+                started.elapsed()
+            );
+            Ok(response)
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = HttpClient::builder("https://easylearn.baidu.com", "your_cookie_here")
+        .middleware(Metrics::default())
+        .middleware(TraceHeader)
+        .build()?;
+
+    let user_info = client.get_user_info().await?.into_result()?;
+    println!("用户名: {}", user_info.user_name);
+
+    Ok(())
+}
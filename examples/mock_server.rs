@@ -0,0 +1,48 @@
+//! 使用假服务器离线运行认领器：`cargo run --example mock_server --features testing`
+
+use anyhow::Result;
+use bedu_claim::client::{AutoClaimConfig, AutoClaimer};
+use bedu_claim::testing::MockBeduServer;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let server = MockBeduServer::start().await;
+    let config = AutoClaimConfig {
+        claim_limit: 3,
+        ..server.claim_config()
+    };
+
+    // 正常认领：任务池中的 3 个任务全部认领成功
+    let summary = AutoClaimer::new(config.clone())?.start().await?;
+    println!(
+        "正常认领: {} 个任务 {:?}",
+        summary.total_claims, summary.claimed_task_ids
+    );
+    println!("认领请求: {:?}", server.received_claims().await);
+
+    // 第一次认领返回 10003，之后恢复
+    server.reset().await;
+    server.pending_limit(Some(1)).await;
+    let summary = AutoClaimer::new(config.clone())?.start().await?;
+    println!(
+        "10003 后恢复: {} 个任务，失败 {} 次",
+        summary.total_claims,
+        summary.failures.len()
+    );
+
+    // Cookie 过期：启动时的用户验证失败
+    server.reset().await;
+    server.expire_cookie().await;
+    if let Err(e) = AutoClaimer::new(config.clone())?.start().await {
+        println!("Cookie 过期: {}", e);
+    }
+
+    // 网关错误：响应无法解析
+    server.reset().await;
+    server.server_error(502).await;
+    if let Err(e) = AutoClaimer::new(config)?.start().await {
+        println!("5xx: {}", e);
+    }
+
+    Ok(())
+}
@@ -1,39 +1,254 @@
-use serde::{Deserialize, Serialize};
+use crate::tr;
+use anyhow::anyhow;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
+use crate::error::BeduError;
+
+/// 接口统一的响应结构，`errno` 为 0 表示成功
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiResponse<T> {
+    pub errno: i32,
+    #[serde(default)]
+    pub errmsg: String,
+    #[serde(default = "Option::default")]
+    pub data: Option<T>,
+    /// 未识别的顶层字段
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+    /// data 解析失败时保留的原始响应，见 [`ApiResponse::parse`]
+    #[serde(skip)]
+    pub raw: Option<RawBody>,
+}
+
+/// data 解析失败时保留的原始响应内容
+#[derive(Debug, Clone)]
+pub struct RawBody {
+    /// 解析失败的原因
+    pub error: String,
+    /// 原始响应体
+    pub body: String,
+}
+
+impl<T: DeserializeOwned> ApiResponse<T> {
+    /// 宽松解析响应体
+    ///
+    /// data 结构与预期不符时不会直接报错，而是保留 `errno`/`errmsg`，
+    /// 将 data 置空并把原始响应体和失败原因记录在 `raw` 中；
+    /// 只有连外层结构都无法解析时才返回 `Decode` 错误。
+    pub fn parse(body: &str) -> Result<Self, BeduError> {
+        let error = match serde_json::from_str(body) {
+            Ok(parsed) => return Ok(parsed),
+            Err(e) => e.to_string(),
+        };
+
+        let envelope: ApiResponse<Value> =
+            serde_json::from_str(body).map_err(|e| BeduError::Decode {
+                message: e.to_string(),
+                body: body.to_string(),
+            })?;
+
+        Ok(Self {
+            errno: envelope.errno,
+            errmsg: envelope.errmsg,
+            data: None,
+            extra: envelope.extra,
+            raw: Some(RawBody {
+                error,
+                body: body.to_string(),
+            }),
+        })
+    }
+}
+
+/// 服务器可能返回的请求标识字段
+const LOG_ID_KEYS: [&str; 6] = ["logid", "logId", "log_id", "traceid", "traceId", "trace_id"];
+
+impl<T> ApiResponse<T> {
+    /// 接口是否返回成功
+    pub fn is_ok(&self) -> bool {
+        self.errno == 0
+    }
+
+    /// 服务器返回的 logid/traceid，用于和服务端日志对应
+    pub fn log_id(&self) -> Option<String> {
+        LOG_ID_KEYS
+            .iter()
+            .filter_map(|key| self.extra.get(*key))
+            .find_map(|value| match value {
+                Value::String(id) if !id.is_empty() => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            })
+    }
+
+    /// 转换为 `Result`：`errno` 非 0 时返回接口错误，成功但缺少 data 时返回 `MissingData`
+    pub fn into_result(self) -> Result<T, BeduError> {
+        self.into_optional()?.ok_or(BeduError::MissingData)
+    }
+
+    /// 转换为 `Result`，允许成功时 data 为空；data 解析失败时返回 `Decode`
+    pub fn into_optional(self) -> Result<Option<T>, BeduError> {
+        if self.errno != 0 {
+            let log_id = self.log_id();
+            return Err(BeduError::Api {
+                errno: self.errno,
+                errmsg: self.errmsg,
+                log_id,
+            });
+        }
+        if let Some(raw) = self.raw {
+            return Err(BeduError::Decode {
+                message: raw.error,
+                body: raw.body,
+            });
+        }
+        Ok(self.data)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct Subject {
     pub id: i32,
     pub name: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct Filter {
     pub id: String,
     pub name: String,
     #[serde(rename = "type")]
     pub filter_type: String,
     pub list: Vec<Subject>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct LabelResponse {
-    pub errno: i32,
-    pub errmsg: String,
-    pub data: LabelData,
-}
+/// 筛选标签响应
+pub type LabelResponse = ApiResponse<LabelData>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct LabelData {
     pub filter: Vec<Filter>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl LabelData {
+    /// 按ID查找筛选项，如 `subject`、`step`、`clueType`
+    pub fn find_filter(&self, id: &str) -> Option<&Filter> {
+        self.filter.iter().find(|f| f.id == id)
+    }
+}
+
+impl Filter {
+    /// 按ID查找可选项
+    pub fn find_option(&self, id: i32) -> Option<&Subject> {
+        self.list.iter().find(|s| s.id == id)
+    }
+
+    /// 可选项列表，形如 `2=数学, 3=语文`
+    pub fn describe_options(&self) -> String {
+        self.list
+            .iter()
+            .map(|s| format!("{}={}", s.id, s.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// 将ID或名称解析为可选项ID，名称不存在时给出相近的候选
+    pub fn resolve(&self, value: &str) -> anyhow::Result<i32> {
+        let value = value.trim();
+
+        if let Ok(id) = value.parse::<i32>() {
+            return match self.find_option(id) {
+                Some(option) => Ok(option.id),
+                None => Err(anyhow!(tr!(
+                    filter_id_not_found,
+                    self.name,
+                    id,
+                    self.describe_options()
+                ))),
+            };
+        }
+
+        if let Some(option) = self.list.iter().find(|s| s.name == value) {
+            return Ok(option.id);
+        }
+
+        // 名称互相包含或编辑距离较小的视为候选
+        let mut candidates: Vec<(usize, &Subject)> = self
+            .list
+            .iter()
+            .filter_map(|s| {
+                let distance = edit_distance(&s.name, value);
+                let threshold = (value.chars().count() / 2).max(1);
+                let similar = s.name.contains(value) || value.contains(&s.name);
+                (similar || distance <= threshold).then_some((distance, s))
+            })
+            .collect();
+        candidates.sort_by_key(|(distance, _)| *distance);
+
+        if candidates.is_empty() {
+            Err(anyhow!(tr!(
+                filter_name_not_found,
+                self.name,
+                value,
+                self.describe_options()
+            )))
+        } else {
+            let suggestions = candidates
+                .iter()
+                .map(|(_, s)| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join("、");
+            Err(anyhow!(tr!(
+                filter_name_suggest,
+                self.name,
+                value,
+                suggestions,
+                self.describe_options()
+            )))
+        }
+    }
+}
+
+/// 按字符计算的编辑距离
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct TaskItem {
     #[serde(rename = "taskID")]
-    pub task_id: i32,
+    pub task_id: i64,
     #[serde(rename = "clueID")]
-    pub clue_id: i32,
+    pub clue_id: i64,
     pub brief: String,
     pub step: i32,
     pub subject: i32,
@@ -50,32 +265,278 @@ pub struct TaskItem {
     pub state_name: String,
     #[serde(rename = "createTime")]
     pub create_time: String,
-    #[serde(rename = "dispatchTime", default)]
+    #[serde(rename = "dispatchTime")]
     pub dispatch_time: Option<String>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct TaskListData {
     pub total: i32,
     pub list: Vec<TaskItem>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TaskListResponse {
-    pub errno: i32,
-    pub errmsg: String,
-    pub data: TaskListData,
+/// 任务列表响应
+pub type TaskListResponse = ApiResponse<TaskListData>;
+
+/// 我的任务中的一条记录
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MyTaskItem {
+    #[serde(rename = "taskID")]
+    pub task_id: i64,
+    #[serde(rename = "clueID")]
+    pub clue_id: i64,
+    pub brief: String,
+    pub step: i32,
+    pub subject: i32,
+    pub state: i32,
+    #[serde(rename = "stepName")]
+    pub step_name: String,
+    #[serde(rename = "subjectName")]
+    pub subject_name: String,
+    #[serde(rename = "clueType")]
+    pub clue_type: i32,
+    #[serde(rename = "clueTypeName")]
+    pub clue_type_name: String,
+    #[serde(rename = "stateName")]
+    pub state_name: String,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    #[serde(rename = "claimTime")]
+    pub claim_time: Option<String>,
+    #[serde(rename = "commitTime")]
+    pub commit_time: Option<String>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MyTaskListData {
+    pub total: i32,
+    pub list: Vec<MyTaskItem>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 我的任务列表响应
+pub type MyTaskListResponse = ApiResponse<MyTaskListData>;
+
+/// 认领、释放及审核结论提交的响应
+pub type ClaimResponse = ApiResponse<ClaimResultData>;
+
+/// 认领失败的单个任务
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClaimFailedTask {
+    #[serde(deserialize_with = "id_string")]
+    pub id: String,
+    pub reason: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 认领结果明细，接口未提供的字段保持为空；无法识别的结构按空结果处理
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, from = "Value")]
+pub struct ClaimResultData {
+    /// 认领成功的数量
+    pub success: Option<i32>,
+    /// 认领成功的任务ID
+    #[serde(rename = "successIDs", deserialize_with = "id_strings")]
+    pub success_ids: Vec<String>,
+    /// 认领失败的任务及原因
+    #[serde(rename = "failList")]
+    pub fail_list: Vec<ClaimFailedTask>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 与 `ClaimResultData` 字段相同，用于在 `from` 中按默认方式解析
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawClaimResultData {
+    success: Option<i32>,
+    #[serde(rename = "successIDs", deserialize_with = "id_strings")]
+    success_ids: Vec<String>,
+    #[serde(rename = "failList")]
+    fail_list: Vec<ClaimFailedTask>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl From<Value> for ClaimResultData {
+    fn from(value: Value) -> Self {
+        let raw: RawClaimResultData = serde_json::from_value(value).unwrap_or_default();
+        Self {
+            success: raw.success,
+            success_ids: raw.success_ids,
+            fail_list: raw.fail_list,
+            extra: raw.extra,
+        }
+    }
+}
+
+/// ID 可能是数字或字符串，统一转为字符串
+fn value_to_id(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
 }
 
+fn id_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(value_to_id(Value::deserialize(deserializer)?))
+}
+
+fn id_strings<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(Vec::<Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(value_to_id)
+        .collect())
+}
+
+/// 选择题选项
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct QuestionOption {
+    /// 选项标号，如 `A`
+    pub label: String,
+    pub content: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 题目内容，文本字段可能包含 HTML
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct QuestionContent {
+    /// 题干
+    pub stem: String,
+    pub options: Vec<QuestionOption>,
+    pub answer: String,
+    /// 解析
+    pub analysis: String,
+    /// 题目中的图片地址
+    pub images: Vec<String>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl QuestionContent {
+    /// 题干去除 HTML 标签后没有文字、只有图片
+    pub fn is_image_only(&self) -> bool {
+        !self.images.is_empty() && strip_html(&self.stem).trim().is_empty()
+    }
+}
+
+/// 去除 HTML 标签，保留文本内容
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ")
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClueDetailData {
+    #[serde(rename = "clueID")]
+    pub clue_id: i64,
+    #[serde(rename = "taskID")]
+    pub task_id: Option<i64>,
+    pub step: i32,
+    pub subject: i32,
+    #[serde(rename = "stepName")]
+    pub step_name: String,
+    #[serde(rename = "subjectName")]
+    pub subject_name: String,
+    #[serde(rename = "clueType")]
+    pub clue_type: i32,
+    #[serde(rename = "clueTypeName")]
+    pub clue_type_name: String,
+    pub content: QuestionContent,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 线索详情响应
+pub type ClueDetailResponse = ApiResponse<ClueDetailData>;
+
+/// 生产任务答案提交请求
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ClaimResponse {
-    pub errno: i32,
-    pub errmsg: String,
-    #[serde(default)]
-    pub data: Option<Value>,
+pub struct ProduceAnswerRequest {
+    #[serde(rename = "clueID")]
+    pub clue_id: i64,
+    pub content: QuestionContent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ProduceAnswerData {
+    /// 提交后生成的任务ID
+    #[serde(rename = "taskID")]
+    pub task_id: Option<i64>,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 答案提交响应
+pub type ProduceAnswerResponse = ApiResponse<ProduceAnswerData>;
+
+/// 上传凭证
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct UploadTokenData {
+    pub token: String,
+    /// 实际接收文件的上传地址
+    #[serde(rename = "uploadUrl")]
+    pub upload_url: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 上传凭证响应
+pub type UploadTokenResponse = ApiResponse<UploadTokenData>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct UploadData {
+    /// 上传后的访问地址
+    pub url: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// 上传响应
+pub type UploadResponse = ApiResponse<UploadData>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct UserInfoData {
     #[serde(rename = "roleLinks")]
     pub role_links: Vec<String>,
@@ -84,11 +545,10 @@ pub struct UserInfoData {
     #[serde(rename = "userName")]
     pub user_name: String,
     pub avatar: String,
+    /// 未识别的字段，接口新增字段时不会导致解析失败
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserInfoResponse {
-    pub errno: i32,
-    pub errmsg: String,
-    pub data: UserInfoData,
-}
+/// 用户信息响应
+pub type UserInfoResponse = ApiResponse<UserInfoData>;
@@ -0,0 +1,124 @@
+//! 系统凭据存储（需 `keyring` 特性）：macOS 钥匙串、Windows 凭据管理器、Linux Secret Service
+//!
+//! `auth set` 将 Cookie 保存到凭据存储，之后配置文件和命令行都未指定 `--cookie` 时从中读取，
+//! Cookie 不会出现在 shell 历史、配置文件和进程列表中。
+
+use anyhow::{Result, anyhow};
+use bedu_claim::client::{Cookie, HttpClient};
+use bedu_claim::tr;
+use dialoguer::Password;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::cli::config::Config;
+use crate::cli::style::{self, Status};
+
+/// 凭据存储中的服务名
+const SERVICE: &str = "bedu-claim";
+/// 凭据存储中的账号名
+const USER: &str = "cookie";
+
+#[derive(clap::Args, Debug)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub action: AuthAction,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum AuthAction {
+    /// 输入 Cookie 并保存到系统凭据存储；标准输入不是终端时从标准输入读取，可以是“复制为 cURL”得到的命令
+    Set {
+        #[arg(
+            long,
+            default_value = "https://easylearn.baidu.com",
+            help = "验证 Cookie 使用的服务器基础URL"
+        )]
+        server: String,
+
+        #[arg(long, help = "不验证 Cookie 是否有效，直接保存")]
+        no_verify: bool,
+    },
+    /// 查看凭据存储中是否保存了 Cookie
+    Status,
+    /// 从系统凭据存储中删除 Cookie
+    Delete,
+}
+
+fn entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, USER).map_err(|e| anyhow!(tr!(keyring_failed, e)))
+}
+
+/// 读取凭据存储中保存的 Cookie，未保存或凭据存储不可用时返回 None
+pub fn load() -> Option<String> {
+    entry().ok()?.get_password().ok()
+}
+
+/// 将 Cookie 保存到凭据存储，覆盖之前保存的
+pub fn save(cookie: &str) -> Result<()> {
+    entry()?
+        .set_password(cookie)
+        .map_err(|e| anyhow!(tr!(keyring_failed, e)))
+}
+
+pub async fn execute(args: AuthArgs, config_path: &Path) -> Result<()> {
+    match args.action {
+        AuthAction::Set { server, no_verify } => set(&server, no_verify, config_path).await,
+        AuthAction::Status => match entry()?.get_password() {
+            Ok(cookie) => {
+                println!("{}", tr!(auth_stored, cookie.chars().count()));
+                Ok(())
+            }
+            Err(keyring::Error::NoEntry) => {
+                println!("{}", tr!(auth_not_stored));
+                Ok(())
+            }
+            Err(e) => Err(anyhow!(tr!(keyring_failed, e))),
+        },
+        AuthAction::Delete => match entry()?.delete_credential() {
+            Ok(()) => {
+                println!("{}", tr!(auth_deleted));
+                Ok(())
+            }
+            Err(keyring::Error::NoEntry) => {
+                println!("{}", tr!(auth_not_stored));
+                Ok(())
+            }
+            Err(e) => Err(anyhow!(tr!(keyring_failed, e))),
+        },
+    }
+}
+
+async fn set(server: &str, no_verify: bool, config_path: &Path) -> Result<()> {
+    let cookie = if std::io::stdin().is_terminal() {
+        Password::with_theme(&*style::prompt_theme())
+            .with_prompt(tr!(init_cookie_prompt))
+            .interact()?
+    } else {
+        std::io::read_to_string(std::io::stdin())?
+    };
+    if cookie.trim().is_empty() {
+        return Err(anyhow!(tr!(empty_cookie)));
+    }
+    let cookie = Cookie::parse(&cookie)?.to_string();
+
+    if !no_verify {
+        let user = HttpClient::builder(server, &cookie)
+            .build()?
+            .get_user_info()
+            .await?
+            .into_result()
+            .map_err(|e| anyhow!(tr!(init_cookie_invalid, e)))?;
+        println!(
+            "{}",
+            Status::Success.paint(tr!(init_cookie_valid, user.user_name))
+        );
+    }
+
+    save(&cookie)?;
+    println!("{}", Status::Success.paint(tr!(auth_saved)));
+    // 配置文件中的 Cookie 优先，提醒删除
+    if Config::load(config_path).is_ok_and(|config| config.cookie.is_some()) {
+        println!("{}", tr!(auth_config_overrides, config_path.display()));
+    }
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! 配置文件：保存 Cookie、服务器地址和常用筛选项，可由 `bedu-claim init` 生成
+//!
+//! 配置中的值作为各子命令对应参数的默认值，命令行显式指定时优先。默认路径为
+//! `$XDG_CONFIG_HOME/bedu-claim/config.toml`（未设置时为 `~/.config/bedu-claim/config.toml`，
+//! Windows 为 `%APPDATA%\bedu-claim\config.toml`），可用 `--config` 指定。
+//!
+//! ```toml
+//! cookie = "BDUSS=..."
+//! server = "https://easylearn.baidu.com"
+//! fallback_servers = ["https://mirror.example.com"]
+//! task_type = "audittask"
+//! subject = 2
+//! step = "初中"
+//! clue_type = 1
+//!
+//! # run 的认领参数和通知目标
+//! interval = 0.5
+//! limit = 20
+//! daily_limit = 100
+//! webhook = "https://example.com/hook"
+//! ```
+//!
+//! `run` 运行中收到 SIGHUP（或控制接口的 `/reload`）时重新读取配置文件，轮询间隔、认领限制、
+//! 筛选项和通知目标的修改立即生效，已认领数量等计数保留。
+
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// 配置文件内容，未填写的项使用命令行参数的默认值
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub cookie: Option<String>,
+    pub server: Option<String>,
+    pub fallback_servers: Option<Vec<String>>,
+    pub resolve: Option<Vec<String>>,
+    pub task_type: Option<String>,
+    pub subject: Option<FilterValue>,
+    pub step: Option<FilterValue>,
+    pub clue_type: Option<FilterValue>,
+    pub interval: Option<f64>,
+    pub limit: Option<i32>,
+    pub daily_limit: Option<i32>,
+    pub max_claims_per_hour: Option<u32>,
+    pub max_pending: Option<i32>,
+    pub webhook: Option<String>,
+    pub dingtalk_webhook: Option<String>,
+    pub dingtalk_secret: Option<String>,
+    pub wecom_webhook: Option<String>,
+    pub bark_key: Option<String>,
+    pub bark_server: Option<String>,
+    pub serverchan_key: Option<String>,
+}
+
+/// 筛选项，可以填写ID或名称
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterValue {
+    Id(i32),
+    Name(String),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Id(id) => id.fmt(f),
+            FilterValue::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Config {
+    /// 读取配置文件，文件不存在时返回空配置
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| anyhow!(tr!(parse_config_failed, path.display(), e))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(anyhow!(tr!(read_config_failed, path.display(), e))),
+        }
+    }
+
+    /// 读取用作参数默认值的配置：配置文件中没有 Cookie 时使用系统凭据存储中保存的
+    pub fn load_defaults(path: &Path) -> Result<Self> {
+        let config = Self::load(path)?;
+        #[cfg(feature = "keyring")]
+        let config = Self {
+            cookie: config.cookie.or_else(crate::cli::auth::load),
+            ..config
+        };
+        Ok(config)
+    }
+
+    /// 写入配置文件；文件中保存了 Cookie，Unix 下只允许当前用户读写
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)?;
+            }
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let content = toml::to_string(self).map_err(std::io::Error::other)?;
+            options.open(path)?.write_all(content.as_bytes())
+        };
+        write().map_err(|e| anyhow!(tr!(write_config_failed, path.display(), e)))
+    }
+
+    /// 将配置值设为各子命令同名参数的默认值，命令行未指定时生效
+    pub fn apply(&self, mut command: clap::Command) -> clap::Command {
+        let defaults = [
+            ("cookie", self.cookie.clone()),
+            ("server", self.server.clone()),
+            (
+                "fallback_server",
+                self.fallback_servers.as_ref().map(|urls| urls.join(",")),
+            ),
+            (
+                "resolve",
+                self.resolve.as_ref().map(|rules| rules.join(",")),
+            ),
+            ("task_type", self.task_type.clone()),
+            ("subject", self.subject.as_ref().map(ToString::to_string)),
+            ("step", self.step.as_ref().map(ToString::to_string)),
+            (
+                "clue_type",
+                self.clue_type.as_ref().map(ToString::to_string),
+            ),
+            ("interval", self.interval.map(|v| v.to_string())),
+            ("limit", self.limit.map(|v| v.to_string())),
+            ("daily_limit", self.daily_limit.map(|v| v.to_string())),
+            (
+                "max_claims_per_hour",
+                self.max_claims_per_hour.map(|v| v.to_string()),
+            ),
+            ("max_pending", self.max_pending.map(|v| v.to_string())),
+            ("webhook", self.webhook.clone()),
+            ("dingtalk_webhook", self.dingtalk_webhook.clone()),
+            ("dingtalk_secret", self.dingtalk_secret.clone()),
+            ("wecom_webhook", self.wecom_webhook.clone()),
+            ("bark_key", self.bark_key.clone()),
+            ("bark_server", self.bark_server.clone()),
+            ("serverchan_key", self.serverchan_key.clone()),
+        ];
+        // Cookie 和带有密钥的通知地址不在帮助信息中显示
+        let secret = |id: &str| {
+            matches!(
+                id,
+                "cookie"
+                    | "webhook"
+                    | "dingtalk_webhook"
+                    | "dingtalk_secret"
+                    | "wecom_webhook"
+                    | "bark_key"
+                    | "serverchan_key"
+            )
+        };
+        let names: Vec<String> = command
+            .get_subcommands()
+            .map(|sub| sub.get_name().to_string())
+            .collect();
+        for name in names {
+            command = command.mut_subcommand(name, |mut sub| {
+                for (id, value) in &defaults {
+                    let Some(value) = value else { continue };
+                    if sub.get_arguments().any(|arg| arg.get_id() == id) {
+                        sub = sub.mut_arg(id, |arg| {
+                            arg.default_value(value.clone())
+                                .required(false)
+                                .hide_default_value(secret(id))
+                        });
+                    }
+                }
+                sub
+            });
+        }
+        command
+    }
+}
+
+/// 默认的配置文件路径
+pub fn default_path() -> PathBuf {
+    let dir = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    dir.unwrap_or_default()
+        .join("bedu-claim")
+        .join("config.toml")
+}
+
+/// 从原始命令行参数中找出 `--config`：解析命令行前要先读取配置文件，以便用作参数默认值
+pub fn path_from_args(args: impl IntoIterator<Item = OsString>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use bedu_claim::ipc::{ControlClient, default_socket_path};
+use bedu_claim::tr;
+use clap::ValueEnum;
+use serde_json::Value;
+use std::path::PathBuf;
+
+use crate::cli::style::Status;
+
+#[derive(clap::Args, Debug)]
+pub struct CtlArgs {
+    #[arg(value_enum, help = "操作 (status/pause/resume/reload)")]
+    pub action: CtlAction,
+
+    #[arg(
+        long,
+        help = "控制套接字路径，默认 $XDG_RUNTIME_DIR/bedu-claim.sock 或临时目录下的 bedu-claim.sock"
+    )]
+    pub socket: Option<PathBuf>,
+
+    #[arg(long, help = "以 JSON 格式输出运行状态")]
+    pub json: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum CtlAction {
+    /// 查看运行状态
+    Status,
+    /// 暂停认领
+    Pause,
+    /// 恢复认领
+    Resume,
+    /// 重新读取状态文件
+    Reload,
+}
+
+impl CtlAction {
+    fn method(self) -> &'static str {
+        match self {
+            CtlAction::Status => "status",
+            CtlAction::Pause => "pause",
+            CtlAction::Resume => "resume",
+            CtlAction::Reload => "reload",
+        }
+    }
+}
+
+/// 通过控制套接字操作正在运行的认领器，并打印操作后的运行状态
+pub async fn execute(args: CtlArgs) -> Result<()> {
+    let path = args.socket.unwrap_or_else(default_socket_path);
+    let mut client = ControlClient::connect(&path).await?;
+    let status = client.call(args.action.method()).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
+    let state = if status["paused"].as_bool() == Some(true) {
+        Status::Limited.paint(tr!(paused_state))
+    } else if status["dormant"].as_bool() == Some(true) {
+        Status::Limited.paint(tr!(dormant_state))
+    } else {
+        Status::Success.paint(tr!(claiming_state))
+    };
+    println!("{}", tr!(status_line, state));
+    println!(
+        "{}",
+        tr!(claimed_line, status["claimed"], status["claim_limit"])
+    );
+    println!("{}", tr!(claimed_today_line, status["claimed_today"]));
+    println!("{}", tr!(attempts_line, status["attempts"]));
+    if let Some(success_rate) = status["stats"]["success_rate"].as_f64() {
+        println!("{}", tr!(success_rate_line, success_rate * 100.0));
+    }
+    if let Some(Value::Object(failures)) = status["stats"].get("failures_by_errno")
+        && !failures.is_empty()
+    {
+        println!("{}", tr!(failures_by_errno_line));
+        for (errno, count) in failures {
+            println!("  {}: {}", Status::Failure.paint(errno), count);
+        }
+    }
+
+    Ok(())
+}
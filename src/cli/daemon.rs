@@ -0,0 +1,70 @@
+//! 后台运行（仅 Unix）：脱离终端，写入 PID 文件，日志只写入 `--log-file`
+//!
+//! 必须在启动异步运行时之前调用：fork 只复制调用线程，运行时的工作线程不会进入子进程。
+//! 不切换工作目录，命令行中的相对路径在后台进程中仍然有效。
+
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+
+/// PID 文件，值被释放（进程正常退出）时删除
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    fn create(path: &Path) -> Result<Self> {
+        fs::write(path, format!("{}\n", std::process::id()))
+            .map_err(|e| anyhow!(tr!(write_pid_file_failed, path.display(), e)))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// 转入后台运行：父进程输出子进程ID后退出，子进程成为新会话的首进程并关闭标准输入输出
+pub fn daemonize(log_file: &Path, pid_file: Option<&Path>) -> Result<Option<PidFile>> {
+    // SAFETY: 此时进程中只有主线程，子进程可以安全地继续执行任意代码
+    match unsafe { libc::fork() } {
+        -1 => return Err(anyhow!(tr!(daemon_failed, io::Error::last_os_error()))),
+        0 => {}
+        pid => {
+            println!("{}", tr!(daemon_started, pid, log_file.display()));
+            std::process::exit(0);
+        }
+    }
+
+    // SAFETY: setsid 没有内存安全方面的前置条件
+    if unsafe { libc::setsid() } == -1 {
+        return Err(anyhow!(tr!(daemon_failed, io::Error::last_os_error())));
+    }
+    // 先写 PID 文件再关闭标准错误，写入失败时终端上还能看到错误
+    let pid_file = pid_file.map(PidFile::create).transpose()?;
+    redirect_stdio().map_err(|e| anyhow!(tr!(daemon_failed, e)))?;
+    Ok(pid_file)
+}
+
+/// 将标准输入、输出和错误重定向到 `/dev/null`，避免写入已关闭的终端
+fn redirect_stdio() -> io::Result<()> {
+    let null = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/null")?;
+    for fd in 0..=2 {
+        // SAFETY: 两个文件描述符都有效，dup2 会原子地替换目标描述符
+        if unsafe { libc::dup2(null.as_raw_fd(), fd) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+use std::time::Duration;
+
+use crate::cli::ConnectionArgs;
+
+#[derive(clap::Args, Debug)]
+pub struct HealthcheckArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+
+    #[arg(
+        long,
+        default_value = "10",
+        help = "超时时间（秒），超时视为接口不可访问"
+    )]
+    pub timeout: u64,
+}
+
+/// 检查 Cookie 是否有效、接口是否可访问，正常时退出码为 0，否则为 1，用于容器健康检查
+pub async fn execute(args: HealthcheckArgs) -> Result<()> {
+    let client = args.connection.client()?;
+
+    let response = tokio::time::timeout(Duration::from_secs(args.timeout), client.get_user_info())
+        .await
+        .map_err(|_| anyhow!(tr!(healthcheck_timeout, args.timeout)))?
+        .map_err(|e| anyhow!(tr!(healthcheck_unreachable, e)))?;
+    let user = response
+        .into_result()
+        .map_err(|e| anyhow!(tr!(healthcheck_cookie_invalid, e)))?;
+    println!("{}", tr!(healthcheck_ok, user.user_name));
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anyhow::{Result, anyhow};
+use bedu_claim::api::LabelData;
+use bedu_claim::client::{Cookie, HttpClient};
+use bedu_claim::tr;
+use dialoguer::theme::Theme;
+use dialoguer::{Confirm, Password, Select};
+use std::path::Path;
+
+use crate::cli::config::{Config, FilterValue};
+use crate::cli::style::{self, Status};
+
+/// 可选的任务类型
+const TASK_TYPES: [&str; 2] = ["audittask", "producetask"];
+
+#[derive(clap::Args, Debug)]
+pub struct InitArgs {
+    #[arg(
+        long,
+        default_value = "https://easylearn.baidu.com",
+        help = "服务器基础URL"
+    )]
+    pub server: String,
+
+    #[arg(short, long, help = "配置文件已存在时直接覆盖，不再确认")]
+    pub force: bool,
+
+    #[cfg(feature = "encrypted-cookie")]
+    #[arg(
+        long,
+        help = "用口令加密保存 Cookie，口令也可以通过环境变量 BEDU_CLAIM_PASSPHRASE 提供"
+    )]
+    pub encrypt: bool,
+}
+
+/// 交互式生成配置文件：输入并验证 Cookie，按服务器返回的名称选择任务类型和筛选项
+pub async fn execute(args: InitArgs, path: &Path) -> Result<()> {
+    let theme = style::prompt_theme();
+
+    if path.exists()
+        && !args.force
+        && !Confirm::with_theme(&*theme)
+            .with_prompt(tr!(init_overwrite_prompt, path.display()))
+            .default(false)
+            .interact()?
+    {
+        return Ok(());
+    }
+
+    let (client, cookie) = loop {
+        let input = Password::with_theme(&*theme)
+            .with_prompt(tr!(init_cookie_prompt))
+            .interact()?;
+        let cookie = match Cookie::parse(&input) {
+            Ok(cookie) => cookie.to_string(),
+            Err(e) => {
+                eprintln!("{}", Status::Failure.paint(e.to_string()));
+                continue;
+            }
+        };
+        let client = HttpClient::builder(&args.server, &cookie).build()?;
+        match client.get_user_info().await?.into_result() {
+            Ok(user) => {
+                println!(
+                    "{}",
+                    Status::Success.paint(tr!(init_cookie_valid, user.user_name))
+                );
+                break (client, cookie);
+            }
+            Err(e) => eprintln!("{}", Status::Failure.paint(tr!(init_cookie_invalid, e))),
+        }
+    };
+
+    let task_type = TASK_TYPES[Select::with_theme(&*theme)
+        .with_prompt(tr!(init_task_type_prompt))
+        .items([tr!(audit_task_item), tr!(produce_task_item)])
+        .default(0)
+        .interact()?];
+
+    let labels = client
+        .get_labels(task_type)
+        .await?
+        .into_result()
+        .map_err(|e| anyhow!(tr!(fetch_labels_failed, e)))?;
+    #[cfg(feature = "encrypted-cookie")]
+    let cookie = if args.encrypt {
+        crate::cli::secret::encrypt(&cookie)?
+    } else {
+        cookie
+    };
+    let config = Config {
+        cookie: Some(cookie),
+        server: Some(args.server),
+        task_type: Some(task_type.to_string()),
+        subject: select_option(&*theme, &labels, "subject")?,
+        step: select_option(&*theme, &labels, "step")?,
+        clue_type: select_option(&*theme, &labels, "clueType")?,
+        ..Default::default()
+    };
+
+    config.save(path)?;
+    println!("{}", Status::Success.paint(tr!(init_saved, path.display())));
+    println!("{}", tr!(init_next_step));
+
+    Ok(())
+}
+
+/// 从服务器提供的可选项中选择一项，服务器未提供该筛选项时不写入配置
+fn select_option(
+    theme: &dyn Theme,
+    labels: &LabelData,
+    filter_id: &str,
+) -> Result<Option<FilterValue>> {
+    let Some(filter) = labels
+        .find_filter(filter_id)
+        .filter(|filter| !filter.list.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let index = Select::with_theme(theme)
+        .with_prompt(tr!(init_filter_prompt, filter.name))
+        .items(filter.list.iter().map(|option| &option.name))
+        .default(0)
+        .max_length(15)
+        .interact()?;
+    Ok(Some(FilterValue::Id(filter.list[index].id)))
+}
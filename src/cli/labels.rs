@@ -0,0 +1,47 @@
+use anyhow::{Result, anyhow};
+use bedu_claim::client::stats::render_table;
+use bedu_claim::tr;
+
+use crate::cli::{ConnectionArgs, validate_task_type};
+
+#[derive(clap::Args, Debug)]
+pub struct LabelsArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+
+    #[arg(
+        short,
+        long,
+        default_value = "audittask",
+        help = "任务类型 (audittask/producetask)"
+    )]
+    pub task_type: String,
+}
+
+/// 打印所有筛选项，按筛选类型分组
+pub async fn execute(args: LabelsArgs) -> Result<()> {
+    validate_task_type(&args.task_type)?;
+    let client = args.connection.client()?;
+
+    let labels = client
+        .get_labels(&args.task_type)
+        .await?
+        .into_result()
+        .map_err(|e| anyhow!(tr!(fetch_labels_failed, e)))?;
+
+    for (index, filter) in labels.filter.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        let mut rows = vec![vec!["ID".to_string(), tr!(name_header)]];
+        for option in &filter.list {
+            rows.push(vec![option.id.to_string(), option.name.clone()]);
+        }
+
+        println!("{} ({})", filter.name, filter.id);
+        println!("{}", render_table(&rows, true));
+    }
+
+    Ok(())
+}
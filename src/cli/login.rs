@@ -0,0 +1,114 @@
+//! 扫码登录（需 `qr-login` 特性）：在终端显示二维码，用手机百度 App 扫码确认后保存 Cookie
+
+use anyhow::{Result, anyhow};
+use bedu_claim::client::HttpClient;
+use bedu_claim::client::login::{PASSPORT_URL, QrLogin, QrStatus};
+use bedu_claim::tr;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::config::Config;
+use crate::cli::style::Status;
+
+#[derive(clap::Args, Debug)]
+pub struct LoginArgs {
+    #[arg(
+        long,
+        default_value = "https://easylearn.baidu.com",
+        help = "服务器基础URL，登录后取出发送给它的 Cookie"
+    )]
+    pub server: String,
+
+    #[arg(long, default_value = PASSPORT_URL, hide = true)]
+    pub passport_url: String,
+
+    #[arg(
+        long,
+        default_value = "180",
+        help = "等待扫码确认的时间（秒），超时后二维码失效"
+    )]
+    pub timeout: u64,
+
+    #[arg(long, help = "反转二维码颜色，终端背景为浅色时使用")]
+    pub invert: bool,
+
+    #[arg(long, help = "只在标准输出打印 Cookie，不写入配置文件")]
+    pub print: bool,
+
+    #[cfg(feature = "keyring")]
+    #[arg(
+        long,
+        conflicts_with = "print",
+        help = "将 Cookie 保存到系统凭据存储，而不是配置文件"
+    )]
+    pub keyring: bool,
+}
+
+/// 显示登录二维码并等待确认，验证得到的 Cookie 后写入配置文件
+pub async fn execute(args: LoginArgs, config_path: &Path) -> Result<()> {
+    let login = QrLogin::start_with(&args.passport_url, &args.server).await?;
+
+    // 二维码和提示写到 stderr，--print 时 stdout 只有 Cookie
+    eprintln!("{}", render(&login.qr_content(), args.invert)?);
+    eprintln!("{}", tr!(qr_scan_prompt, login.image_url()));
+    let cookie = login
+        .wait(Duration::from_secs(args.timeout), |status| match status {
+            QrStatus::Scanned => eprintln!("{}", tr!(qr_scanned)),
+            QrStatus::Confirmed(_) => eprintln!("{}", tr!(qr_confirmed)),
+            QrStatus::Waiting | QrStatus::Cancelled => {}
+        })
+        .await?
+        .to_string();
+
+    let user = HttpClient::builder(&args.server, &cookie)
+        .build()?
+        .get_user_info()
+        .await?
+        .into_result()
+        .map_err(|e| anyhow!(tr!(init_cookie_invalid, e)))?;
+    eprintln!(
+        "{}",
+        Status::Success.paint(tr!(init_cookie_valid, user.user_name))
+    );
+
+    if args.print {
+        println!("{}", cookie);
+        return Ok(());
+    }
+    #[cfg(feature = "keyring")]
+    if args.keyring {
+        crate::cli::auth::save(&cookie)?;
+        eprintln!("{}", Status::Success.paint(tr!(auth_saved)));
+        return Ok(());
+    }
+
+    // 保留配置文件中的其他设置，只替换 Cookie
+    let mut config = Config::load(config_path)?;
+    config.cookie = Some(cookie);
+    config.save(config_path)?;
+    eprintln!(
+        "{}",
+        Status::Success.paint(tr!(login_saved, config_path.display()))
+    );
+    Ok(())
+}
+
+/// 用上下半块字符把二维码画成终端字符画，每个字符表示上下两个模块
+///
+/// 默认按深色背景绘制：深色模块画成空白，浅色模块画成实心块。
+fn render(content: &str, invert: bool) -> Result<String> {
+    let code = QrCode::new(content.as_bytes()).map_err(|e| anyhow!(tr!(qr_render_failed, e)))?;
+    let (dark, light) = if invert {
+        (Dense1x2::Dark, Dense1x2::Light)
+    } else {
+        (Dense1x2::Light, Dense1x2::Dark)
+    };
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(dark)
+        .light_color(light)
+        .quiet_zone(true)
+        .build())
+}
@@ -0,0 +1,572 @@
+//! 命令行子命令定义与实现（仅供二进制程序使用）
+
+#[cfg(feature = "keyring")]
+pub mod auth;
+pub mod config;
+#[cfg(unix)]
+pub mod ctl;
+#[cfg(unix)]
+pub mod daemon;
+pub mod healthcheck;
+pub mod init;
+pub mod labels;
+#[cfg(feature = "qr-login")]
+pub mod login;
+#[cfg(feature = "progress")]
+pub mod progress;
+pub mod run;
+#[cfg(feature = "encrypted-cookie")]
+pub mod secret;
+#[cfg(all(windows, feature = "windows-service"))]
+pub mod service;
+#[cfg(feature = "sqlite")]
+pub mod stats;
+pub mod style;
+#[cfg(unix)]
+pub mod systemd;
+pub mod tasks;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod whoami;
+
+use anyhow::{Result, anyhow};
+use bedu_claim::client::{
+    Cookie, FixtureMode, HttpClient, PoolOptions, Token, UserAgentPool, UserAgentRotation,
+};
+use bedu_claim::i18n::Lang;
+use bedu_claim::logging::LogRotation;
+use bedu_claim::tr;
+use chrono::{FixedOffset, Offset, Utc};
+use clap::{ArgAction, Parser, Subcommand};
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "百度教育自动认领工具", long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    #[arg(
+        long,
+        global = true,
+        help = "配置文件路径，默认为 ~/.config/bedu-claim/config.toml，其中的值作为参数默认值"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(long, global = true, help = "日志文件路径，设置后同时写入文件")]
+    pub log_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        default_value = "daily",
+        help = "日志轮转策略 (never/hourly/daily 或大小如 10MB)"
+    )]
+    pub log_rotate: LogRotation,
+
+    #[arg(long, global = true, default_value = "7", help = "保留的日志文件数量")]
+    pub log_max_files: usize,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = ArgAction::Count,
+        help = "输出更详细的日志，-v 为 debug，-vv 为 trace（同时输出依赖库的 debug 日志）"
+    )]
+    pub verbose: u8,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        conflicts_with = "verbose",
+        help = "只输出警告和错误日志"
+    )]
+    pub quiet: bool,
+
+    #[arg(
+        long,
+        global = true,
+        default_value = "zh",
+        help = "日志、错误信息和命令输出的语言 (zh/en)，帮助信息固定为中文"
+    )]
+    pub lang: Lang,
+}
+
+impl Cli {
+    /// 日志过滤规则：指定 -v/-q 时按其设置，否则使用 RUST_LOG，默认为 info
+    pub fn log_filter(&self) -> EnvFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => EnvFilter::new("warn"),
+            (false, 0) => {
+                EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"))
+            }
+            (false, 1) => EnvFilter::new("info,bedu_claim=debug"),
+            (false, _) => EnvFilter::new("debug,bedu_claim=trace"),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// 交互式生成配置文件：验证 Cookie 并按名称选择任务类型、学科、学段和线索类型
+    Init(init::InitArgs),
+    /// 用手机百度 App 扫描终端中的二维码登录，将 Cookie 写入配置文件（需 qr-login 特性）
+    #[cfg(feature = "qr-login")]
+    Login(login::LoginArgs),
+    /// 运行自动认领
+    Run(Box<run::RunArgs>),
+    /// 查看任务池中的任务
+    List(tasks::ListArgs),
+    /// 认领指定ID的任务
+    Claim(tasks::ClaimArgs),
+    /// 列出任务池中的任务，用方向键和空格多选后认领
+    Pick(tasks::PickArgs),
+    /// 释放已认领的任务，退回任务池
+    Release(tasks::ClaimArgs),
+    /// 查看当前账号信息（用户名、角色及角色链接）
+    #[command(alias = "user")]
+    Whoami(whoami::WhoamiArgs),
+    /// 列出可用的学科、学段和线索类型
+    Labels(labels::LabelsArgs),
+    /// 检查 Cookie 是否有效、接口是否可访问，异常时退出码为 1（用于 Docker/Kubernetes 健康检查）
+    Healthcheck(healthcheck::HealthcheckArgs),
+    /// 将 Cookie 保存到系统凭据存储，或查看、删除已保存的 Cookie（需 keyring 特性）
+    #[cfg(feature = "keyring")]
+    Auth(auth::AuthArgs),
+    /// 统计认领历史（需 sqlite 特性）
+    #[cfg(feature = "sqlite")]
+    Stats(stats::StatsArgs),
+    /// 通过控制套接字查看或操作正在运行的认领器（run 需指定 --control-socket）
+    #[cfg(unix)]
+    Ctl(ctl::CtlArgs),
+    /// 安装、卸载、启动或停止 Windows 服务（需 windows-service 特性）
+    #[cfg(all(windows, feature = "windows-service"))]
+    Service(service::ServiceArgs),
+}
+
+impl Command {
+    /// 子命令的连接参数，不需要连接服务器的子命令返回 None
+    pub fn connection_mut(&mut self) -> Option<&mut ConnectionArgs> {
+        match self {
+            Command::Run(args) => Some(&mut args.connection),
+            Command::List(args) => Some(&mut args.connection),
+            Command::Claim(args) | Command::Release(args) => Some(&mut args.connection),
+            Command::Pick(args) => Some(&mut args.connection),
+            Command::Whoami(args) => Some(&mut args.connection),
+            Command::Labels(args) => Some(&mut args.connection),
+            Command::Healthcheck(args) => Some(&mut args.connection),
+            _ => None,
+        }
+    }
+
+    /// 是否将日志输出到 stdout；其余情况输出到 stderr，避免干扰命令输出
+    pub fn logs_to_stdout(&self) -> bool {
+        matches!(self, Command::Run(args) if args.output == run::OutputFormat::Text)
+    }
+
+    /// 是否显示终端仪表盘，此时日志不输出到终端
+    #[cfg(feature = "tui")]
+    pub fn shows_dashboard(&self) -> bool {
+        matches!(self, Command::Run(args) if args.tui)
+    }
+
+    #[cfg(not(feature = "tui"))]
+    pub fn shows_dashboard(&self) -> bool {
+        false
+    }
+
+    /// 是否显示认领进度条，此时日志经进度条输出
+    #[cfg(feature = "progress")]
+    pub fn shows_progress(&self) -> bool {
+        matches!(self, Command::Run(args) if args.progress)
+    }
+
+    #[cfg(not(feature = "progress"))]
+    pub fn shows_progress(&self) -> bool {
+        false
+    }
+}
+
+/// 连接服务器所需的参数
+#[derive(clap::Args, Debug)]
+pub struct ConnectionArgs {
+    // 配置文件中的 Cookie 以默认值的形式提供，不能用 required_unless_present，未指定时由
+    // resolve_cookie 报错
+    #[arg(short, long, help = "Cookie字符串")]
+    pub cookie: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "cookie",
+        help = "Netscape 格式的 cookies.txt（浏览器扩展导出），从中取出发送给服务器的 Cookie"
+    )]
+    pub cookie_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "https://easylearn.baidu.com",
+        help = "服务器基础URL"
+    )]
+    pub server: String,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "备用服务器地址（其他网关或镜像），可重复指定或用逗号分隔；主地址连接失败时依次切换"
+    )]
+    pub fallback_server: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "HOST=IP",
+        value_delimiter = ',',
+        value_parser = parse_host_ip,
+        help = "不经过 DNS，将主机名解析为指定 IP，如 easylearn.baidu.com=1.2.3.4；可重复指定"
+    )]
+    pub resolve: Vec<(String, IpAddr)>,
+
+    #[arg(long, help = "每个主机最多保留的空闲连接数，默认不限")]
+    pub pool_max_idle: Option<usize>,
+
+    #[arg(
+        long,
+        default_value = "90",
+        help = "空闲连接保留多久后关闭（秒），0 表示一直保留"
+    )]
+    pub pool_idle_timeout: u64,
+
+    #[arg(
+        long,
+        default_value = "15",
+        help = "TCP 保活探测的空闲时间（秒），0 表示不启用"
+    )]
+    pub tcp_keepalive: u64,
+
+    #[cfg(feature = "compression")]
+    #[arg(long, help = "不请求压缩的响应，排查代理或抓包问题时使用")]
+    pub no_compression: bool,
+
+    #[arg(long, help = "User-Agent 列表文件，每行一个，设置后轮换使用")]
+    pub user_agent_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "session",
+        requires = "user_agent_file",
+        help = "User-Agent 轮换方式 (session: 每次运行选定一个, request: 每个请求随机)"
+    )]
+    pub user_agent_rotation: UserAgentRotation,
+
+    #[arg(long, help = "将接口请求和响应录制到该目录（不含 Cookie）")]
+    pub record_fixtures: Option<PathBuf>,
+
+    #[arg(
+        long,
+        conflicts_with = "record_fixtures",
+        help = "从该目录回放录制的响应，不访问网络"
+    )]
+    pub replay_fixtures: Option<PathBuf>,
+
+    #[arg(long, help = "将每个接口的原始响应写入该目录，便于排查解析失败")]
+    pub dump_responses: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "HEADER=SOURCE",
+        help = "每个请求附带的动态令牌，可重复指定，如 X-Csrf-Token=cookie:csrftoken，格式见 README"
+    )]
+    pub token: Vec<Token>,
+
+    #[arg(
+        long,
+        default_value = "600",
+        help = "从服务器获取的令牌缓存多久后重新获取（秒）"
+    )]
+    pub token_ttl: u64,
+}
+
+/// age 密文的开头，Cookie 以此开头时为配置文件中加密保存的内容
+const ENCRYPTED_COOKIE_PREFIX: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+
+impl ConnectionArgs {
+    /// 确定使用的 Cookie：指定了 `--cookie-file` 时从中读取，否则解密加密保存的 Cookie
+    /// （需 encrypted-cookie 特性），并从粘贴的请求头或 curl 命令中提取规范化的 Cookie
+    ///
+    /// 回放录制的响应时不访问网络，Cookie 可以随意填写，不做检查。
+    pub fn resolve_cookie(&mut self) -> Result<()> {
+        if let Some(path) = &self.cookie_file {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!(tr!(read_cookie_file_failed, path.display(), e)))?;
+            let host = reqwest::Url::parse(&self.server)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .ok_or_else(|| anyhow!(tr!(invalid_server_url, self.server)))?;
+            self.cookie = Some(Cookie::from_netscape(&content, &host)?.to_string());
+            return Ok(());
+        }
+
+        let Some(cookie) = &mut self.cookie else {
+            return Err(anyhow!(tr!(empty_cookie)));
+        };
+        if cookie.trim_start().starts_with(ENCRYPTED_COOKIE_PREFIX) {
+            #[cfg(feature = "encrypted-cookie")]
+            {
+                *cookie = secret::decrypt(cookie)?;
+            }
+            #[cfg(not(feature = "encrypted-cookie"))]
+            return Err(anyhow!(tr!(encrypted_cookie_unsupported)));
+        }
+        if self.replay_fixtures.is_some() || cookie.trim().is_empty() {
+            return Ok(());
+        }
+        *cookie = Cookie::parse(cookie)?.to_string();
+        Ok(())
+    }
+
+    /// 使用的 Cookie，需先调用 [`Self::resolve_cookie`]
+    pub fn cookie(&self) -> &str {
+        self.cookie.as_deref().unwrap_or_default()
+    }
+
+    /// 校验参数并创建HTTP客户端
+    pub fn client(&self) -> Result<HttpClient> {
+        self.validate()?;
+        let mut builder = HttpClient::builder(&self.server, self.cookie());
+        if let Some(pool) = self.user_agent_pool()? {
+            builder = builder.user_agent_pool(pool);
+        }
+        if let Some(mode) = self.fixture_mode() {
+            builder = builder.fixtures(mode);
+        }
+        if let Some(dir) = &self.dump_responses {
+            builder = builder.dump_responses(dir);
+        }
+        for token in self.tokens() {
+            builder = builder.token(token);
+        }
+        for url in &self.fallback_server {
+            builder = builder.fallback_url(url);
+        }
+        for (host, ip) in &self.resolve {
+            builder = builder.resolve(host, *ip);
+        }
+        builder = builder.pool(self.pool_options());
+        #[cfg(feature = "compression")]
+        {
+            builder = builder.compression(!self.no_compression);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// 连接池与 TCP 保活设置，0 表示不限或不启用
+    pub fn pool_options(&self) -> PoolOptions {
+        let seconds = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        PoolOptions {
+            max_idle_per_host: self.pool_max_idle,
+            idle_timeout: seconds(self.pool_idle_timeout),
+            tcp_keepalive: seconds(self.tcp_keepalive),
+        }
+    }
+
+    /// 动态令牌，已应用 `--token-ttl`
+    pub fn tokens(&self) -> Vec<Token> {
+        self.token
+            .iter()
+            .map(|token| token.clone().ttl(Duration::from_secs(self.token_ttl)))
+            .collect()
+    }
+
+    /// 录制或回放模式，两者都未指定时返回 None
+    pub fn fixture_mode(&self) -> Option<FixtureMode> {
+        self.record_fixtures
+            .clone()
+            .map(FixtureMode::Record)
+            .or_else(|| self.replay_fixtures.clone().map(FixtureMode::Replay))
+    }
+
+    /// 加载 User-Agent 池，未指定文件时返回 None
+    pub fn user_agent_pool(&self) -> Result<Option<UserAgentPool>> {
+        self.user_agent_file
+            .as_deref()
+            .map(|path| UserAgentPool::from_file(path, self.user_agent_rotation))
+            .transpose()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.cookie().is_empty() {
+            return Err(anyhow!(tr!(empty_cookie)));
+        }
+        Ok(())
+    }
+}
+
+/// 任务筛选参数，学科、学段和线索类型可以填写ID或名称
+#[derive(clap::Args, Debug)]
+pub struct FilterArgs {
+    #[arg(
+        short,
+        long,
+        visible_alias = "subject-id",
+        default_value = "2",
+        help = "学科ID或名称"
+    )]
+    pub subject: String,
+
+    #[arg(
+        short = 'e',
+        long,
+        visible_alias = "step-id",
+        default_value = "1",
+        help = "学段ID或名称"
+    )]
+    pub step: String,
+
+    #[arg(
+        short = 'u',
+        long,
+        visible_alias = "clue-type-id",
+        default_value = "1",
+        help = "线索类型ID或名称"
+    )]
+    pub clue_type: String,
+
+    #[arg(
+        short,
+        long,
+        default_value = "audittask",
+        help = "任务类型 (audittask/producetask)"
+    )]
+    pub task_type: String,
+}
+
+/// 解析后的筛选ID
+#[derive(Debug, Clone, Copy)]
+pub struct FilterIds {
+    pub subject_id: i32,
+    pub step_id: i32,
+    pub clue_type_id: i32,
+}
+
+impl FilterArgs {
+    pub fn validate(&self) -> Result<()> {
+        validate_task_type(&self.task_type)
+    }
+
+    /// 将筛选参数解析为ID；包含名称时通过标签接口查找对应ID
+    pub async fn resolve(&self, client: &HttpClient) -> Result<FilterIds> {
+        let values = [&self.subject, &self.step, &self.clue_type];
+        let ids: Vec<Option<i32>> = values.iter().map(|v| v.trim().parse().ok()).collect();
+
+        if let [Some(subject_id), Some(step_id), Some(clue_type_id)] = ids[..] {
+            return Ok(FilterIds {
+                subject_id,
+                step_id,
+                clue_type_id,
+            });
+        }
+
+        let labels = client
+            .get_labels(&self.task_type)
+            .await?
+            .into_result()
+            .map_err(|e| anyhow!(tr!(fetch_labels_failed, e)))?;
+
+        let resolve = |filter_id: &str, value: &str| -> Result<i32> {
+            match labels.find_filter(filter_id) {
+                Some(filter) => filter.resolve(value),
+                None => value
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!(tr!(filter_not_provided, filter_id))),
+            }
+        };
+
+        Ok(FilterIds {
+            subject_id: resolve("subject", &self.subject)?,
+            step_id: resolve("step", &self.step)?,
+            clue_type_id: resolve("clueType", &self.clue_type)?,
+        })
+    }
+}
+
+pub fn validate_task_type(task_type: &str) -> Result<()> {
+    if !["audittask", "producetask"].contains(&task_type) {
+        return Err(anyhow!(tr!(invalid_task_type)));
+    }
+    Ok(())
+}
+
+/// 解析时长，支持 `90s`、`30m`、`2h`、`1d` 及组合如 `1h30m`，纯数字按秒计
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let invalid = || anyhow!(tr!(invalid_duration, value));
+    if let Ok(secs) = value.parse::<u64>() {
+        return (secs > 0)
+            .then(|| Duration::from_secs(secs))
+            .ok_or_else(invalid);
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => return Err(invalid()),
+        };
+        let amount: u64 = number.parse().map_err(|_| invalid())?;
+        total = amount
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(invalid)?;
+        number.clear();
+    }
+    if !number.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// 解析 `KEY=VALUE` 形式的配置项
+#[cfg(feature = "kafka")]
+pub fn parse_key_value(value: &str) -> Result<(String, String)> {
+    value
+        .split_once('=')
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| anyhow!(tr!(invalid_config_option, value)))
+}
+
+/// 解析 `HOST=IP` 形式的主机名解析规则，IPv6 地址可以带方括号
+pub fn parse_host_ip(value: &str) -> Result<(String, IpAddr)> {
+    let invalid = || anyhow!(tr!(invalid_resolve, value));
+    let (host, ip) = value.split_once('=').ok_or_else(invalid)?;
+    let host = host.trim();
+    let ip = ip.trim().trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    Ok((host.to_string(), ip.parse().map_err(|_| invalid())?))
+}
+
+/// 解析 UTC 偏移，支持 `UTC`、`+08:00`、`-05:30` 等写法
+pub fn parse_utc_offset(value: &str) -> Result<FixedOffset> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+        return Ok(Utc.fix());
+    }
+    value
+        .parse()
+        .map_err(|_| anyhow!(tr!(invalid_utc_offset, value)))
+}
@@ -0,0 +1,192 @@
+//! 认领进度条（`run --progress`，需 `progress` 特性）
+//!
+//! 进度条显示在 stderr 底部，日志照常输出：日志经 [`stdout`]/[`stderr`] 写出时先擦除进度条，写完后重绘。
+
+use bedu_claim::client::{ClaimEvent, ClaimerStatus};
+use bedu_claim::tr;
+use chrono::{DateTime, FixedOffset};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// 计算认领速度时参考的最近认领次数
+const RATE_WINDOW: usize = 10;
+/// 预计剩余时间的刷新间隔
+const REFRESH: Duration = Duration::from_secs(1);
+
+/// 全局进度条，日志输出需要在启动认领前就拿到它
+static BAR: LazyLock<ProgressBar> = LazyLock::new(|| ProgressBar::new(0));
+
+/// 绕开进度条的日志输出目标
+pub struct LogWriter {
+    stdout: bool,
+}
+
+/// 输出到 stdout 的日志写入器，配合 `BoxMakeWriter::new(progress::stdout)` 使用
+pub fn stdout() -> LogWriter {
+    LogWriter { stdout: true }
+}
+
+/// 输出到 stderr 的日志写入器
+pub fn stderr() -> LogWriter {
+    LogWriter { stdout: false }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        BAR.suspend(|| {
+            if self.stdout {
+                io::stdout().write(buf)
+            } else {
+                io::stderr().write(buf)
+            }
+        })
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        BAR.suspend(|| {
+            if self.stdout {
+                io::stdout().write_all(buf)
+            } else {
+                io::stderr().write_all(buf)
+            }
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.stdout {
+            io::stdout().flush()
+        } else {
+            io::stderr().flush()
+        }
+    }
+}
+
+/// 根据认领事件更新进度条，直到事件通道关闭；认领结束后由调用方取消任务，再调用 [`finish`]
+pub async fn track(mut receiver: broadcast::Receiver<ClaimEvent>, status: ClaimerStatus) {
+    let bar = &*BAR;
+    bar.set_style(
+        ProgressStyle::with_template(
+            "{spinner:.green} [{elapsed_precise}] {bar:30.green/white} {pos}/{len} {msg}",
+        )
+        .expect("进度条模板无效")
+        .progress_chars("=> "),
+    );
+    bar.set_length(status.claim_limit.max(0) as u64);
+    bar.set_position(status.claimed.max(0) as u64);
+    bar.enable_steady_tick(Duration::from_millis(100));
+
+    let mut progress = Progress::new(&status);
+    let mut refresh = tokio::time::interval(REFRESH);
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(event) => progress.record(bar, &event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = refresh.tick() => {}
+        }
+        bar.set_message(progress.message(bar));
+    }
+}
+
+/// 认领结束后在当前进度处定格进度条并换行，避免之后的输出和进度条挤在同一行
+pub fn finish(result: &anyhow::Result<()>) {
+    BAR.abandon_with_message(match result {
+        Ok(()) => tr!(claim_ended),
+        Err(e) => tr!(claim_aborted, e),
+    });
+    if !BAR.is_hidden() {
+        eprintln!();
+    }
+}
+
+/// 进度条右侧显示的运行状态和认领速度
+struct Progress {
+    paused: bool,
+    dormant: bool,
+    /// 因调度规则等待时的恢复时间
+    waiting_until: Option<DateTime<FixedOffset>>,
+    /// 最近几次认领的时间和累计认领数，首项为开始计算速度的基准点
+    claims: VecDeque<(Instant, i32)>,
+}
+
+impl Progress {
+    fn new(status: &ClaimerStatus) -> Self {
+        Self {
+            paused: status.paused,
+            dormant: status.dormant,
+            waiting_until: None,
+            claims: VecDeque::from([(Instant::now(), status.claimed)]),
+        }
+    }
+
+    fn record(&mut self, bar: &ProgressBar, event: &ClaimEvent) {
+        match event {
+            ClaimEvent::AttemptStarted { .. } => self.waiting_until = None,
+            ClaimEvent::Claimed { total, .. } => {
+                bar.set_position((*total).max(0) as u64);
+                if self.claims.len() > RATE_WINDOW {
+                    self.claims.pop_front();
+                }
+                self.claims.push_back((Instant::now(), *total));
+            }
+            ClaimEvent::LimitChanged { limit } => bar.set_length((*limit).max(0) as u64),
+            ClaimEvent::Paused => self.paused = true,
+            ClaimEvent::Unpaused => self.paused = false,
+            ClaimEvent::Dormant { .. } => self.dormant = true,
+            ClaimEvent::Resumed { .. } => self.dormant = false,
+            ClaimEvent::DailyLimitReached { resume_at, .. }
+            | ClaimEvent::HourlyLimitReached { resume_at, .. }
+            | ClaimEvent::NonWorkingDay { resume_at }
+            | ClaimEvent::OutsideActiveHours { resume_at }
+            | ClaimEvent::AwaitingCronTrigger { resume_at } => {
+                self.waiting_until = Some(*resume_at);
+            }
+            _ => {}
+        }
+    }
+
+    fn message(&self, bar: &ProgressBar) -> String {
+        if self.paused {
+            return tr!(paused_state);
+        }
+        if self.dormant {
+            return tr!(dormant_too_many_pending);
+        }
+        if let Some(resume_at) = self.waiting_until {
+            return tr!(waiting_until, resume_at.format("%m-%d %H:%M"));
+        }
+
+        let remaining = bar.length().unwrap_or(0).saturating_sub(bar.position());
+        match self.eta(remaining) {
+            Some(eta) => tr!(eta, format_duration(eta)),
+            None => tr!(eta_unknown),
+        }
+    }
+
+    /// 按最近几次认领的速度估算剩余时间，还没有认领时无法估算
+    fn eta(&self, remaining: u64) -> Option<Duration> {
+        let (since, first) = self.claims.front()?;
+        let (_, last) = self.claims.back()?;
+        let claimed = last - first;
+        if claimed <= 0 {
+            return None;
+        }
+        let per_claim = since.elapsed().as_secs_f64() / claimed as f64;
+        Some(Duration::from_secs_f64(per_claim * remaining as f64))
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => tr!(duration_seconds, s),
+        (0, m, s) => tr!(duration_minutes, m, s),
+        (h, m, _) => tr!(duration_hours, h, m),
+    }
+}
@@ -0,0 +1,57 @@
+//! 配置文件中加密保存的 Cookie（需 `encrypted-cookie` 特性）
+//!
+//! 系统凭据存储不可用时（如无桌面环境的服务器），`init --encrypt` 用口令加密 Cookie 后写入配置
+//! 文件，格式为 ASCII 编码的 age 密文，密钥由口令经 scrypt 派生。读取到加密的 Cookie 时，口令取自
+//! 环境变量 `BEDU_CLAIM_PASSPHRASE`，未设置时在终端提示输入。
+
+use age::secrecy::SecretString;
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+use dialoguer::Password;
+use std::io::IsTerminal;
+
+use crate::cli::style;
+
+/// 提供口令的环境变量
+pub const PASSPHRASE_ENV: &str = "BEDU_CLAIM_PASSPHRASE";
+
+/// 解密时允许的 scrypt 工作量上限 `N = 2^22`
+const MAX_WORK_FACTOR: u8 = 22;
+
+/// 用口令加密 Cookie，返回写入配置文件的密文；在终端输入口令时需要输入两次确认
+pub fn encrypt(cookie: &str) -> Result<String> {
+    let recipient = age::scrypt::Recipient::new(passphrase(true)?);
+    age::encrypt_and_armor(&recipient, cookie.as_bytes())
+        .map_err(|e| anyhow!(tr!(encrypt_cookie_failed, e)))
+}
+
+/// 用口令解密配置文件中的 Cookie
+pub fn decrypt(armored: &str) -> Result<String> {
+    let mut identity = age::scrypt::Identity::new(passphrase(false)?);
+    // 默认拒绝解密耗时超过本机约 16 秒的密文；配置文件可能在更快的机器上加密，适当放宽
+    identity.set_max_work_factor(MAX_WORK_FACTOR);
+    let plaintext = age::decrypt(&identity, armored.trim().as_bytes()).map_err(|e| match e {
+        age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => {
+            anyhow!(tr!(wrong_passphrase))
+        }
+        e => anyhow!(tr!(decrypt_cookie_failed, e)),
+    })?;
+    String::from_utf8(plaintext).map_err(|e| anyhow!(tr!(decrypt_cookie_failed, e)))
+}
+
+/// 读取口令：优先使用环境变量，否则在终端提示输入
+fn passphrase(confirm: bool) -> Result<SecretString> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase.into());
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(tr!(passphrase_required, PASSPHRASE_ENV)));
+    }
+
+    let theme = style::prompt_theme();
+    let mut prompt = Password::with_theme(&*theme).with_prompt(tr!(passphrase_prompt));
+    if confirm {
+        prompt = prompt.with_confirmation(tr!(passphrase_confirm_prompt), tr!(passphrase_mismatch));
+    }
+    Ok(prompt.interact()?.into())
+}
@@ -0,0 +1,234 @@
+//! Windows 服务（需 `windows-service` 特性，仅 Windows）
+//!
+//! `service install` 将 `run` 注册为开机自动启动的服务。服务以 LocalSystem 身份运行，读不到
+//! 当前用户的配置，配置固定从 `%ProgramData%\bedu-claim\config.toml` 读取，日志默认写入
+//! 同目录下的 `logs\bedu-claim.log`。服务控制管理器以 `run --windows-service` 启动本程序，
+//! 停止服务时结束认领。
+
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+use futures::future::LocalBoxFuture;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::error;
+use windows_service::service::{
+    ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+    ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+use windows_service::{define_windows_service, service_dispatcher};
+
+/// 服务名
+pub const SERVICE_NAME: &str = "bedu-claim";
+
+/// 服务入口：服务控制管理器在另一个线程中回调 `service_main`，无法直接传参，先存在这里
+type Entry = Box<dyn FnOnce() -> LocalBoxFuture<'static, Result<()>> + Send>;
+static ENTRY: Mutex<Option<Entry>> = Mutex::new(None);
+
+#[derive(clap::Args, Debug)]
+pub struct ServiceArgs {
+    #[command(subcommand)]
+    pub action: ServiceAction,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum ServiceAction {
+    /// 安装为开机自动启动的服务，`--` 之后的参数原样传给 run，如 `service install -- --limit 10`
+    Install {
+        #[arg(last = true, help = "传给 run 的参数")]
+        args: Vec<OsString>,
+    },
+    /// 停止并卸载服务
+    Uninstall,
+    /// 启动服务
+    Start,
+    /// 停止服务
+    Stop,
+}
+
+/// 服务使用的数据目录 `%ProgramData%\bedu-claim`
+pub fn data_dir() -> PathBuf {
+    std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+        .join("bedu-claim")
+}
+
+/// 服务读取的配置文件路径
+pub fn config_path() -> PathBuf {
+    data_dir().join("config.toml")
+}
+
+pub fn execute(args: ServiceArgs) -> Result<()> {
+    match args.action {
+        ServiceAction::Install { args } => install(args),
+        ServiceAction::Uninstall => uninstall(),
+        ServiceAction::Start => {
+            open_service(ServiceAccess::START)?
+                .start::<&OsStr>(&[])
+                .map_err(|e| anyhow!(tr!(service_start_failed, describe(&e))))?;
+            println!("{}", tr!(service_started, SERVICE_NAME));
+            Ok(())
+        }
+        ServiceAction::Stop => {
+            open_service(ServiceAccess::STOP)?
+                .stop()
+                .map_err(|e| anyhow!(tr!(service_stop_failed, describe(&e))))?;
+            println!("{}", tr!(service_stopped, SERVICE_NAME));
+            Ok(())
+        }
+    }
+}
+
+fn install(run_args: Vec<OsString>) -> Result<()> {
+    let config = config_path();
+    let mut launch_arguments = vec![OsString::from("--config"), config.clone().into()];
+    // run 参数中已指定日志文件时不再添加默认值，否则重复指定会报错
+    if !run_args
+        .iter()
+        .any(|arg| arg == "--log-file" || arg.to_string_lossy().starts_with("--log-file="))
+    {
+        launch_arguments.push("--log-file".into());
+        launch_arguments.push(data_dir().join("logs").join("bedu-claim.log").into());
+    }
+    launch_arguments.push("run".into());
+    launch_arguments.push("--windows-service".into());
+    launch_arguments.extend(run_args);
+
+    let info = ServiceInfo {
+        name: SERVICE_NAME.into(),
+        display_name: tr!(service_display_name).into(),
+        service_type: ServiceType::OWN_PROCESS,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path: std::env::current_exe()?,
+        launch_arguments,
+        dependencies: vec![],
+        account_name: None,
+        account_password: None,
+    };
+    ServiceManager::local_computer(
+        None::<&str>,
+        ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+    )
+    .and_then(|manager| manager.create_service(&info, ServiceAccess::CHANGE_CONFIG))
+    .and_then(|service| service.set_description(tr!(service_description)))
+    .map_err(|e| anyhow!(tr!(service_install_failed, describe(&e))))?;
+
+    println!("{}", tr!(service_installed, SERVICE_NAME));
+    if !config.exists() {
+        println!("{}", tr!(service_config_missing, config.display()));
+    }
+    Ok(())
+}
+
+fn uninstall() -> Result<()> {
+    let service =
+        open_service(ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE)?;
+    // 服务停止后才会真正删除，在此之前同名服务无法重新安装
+    service
+        .query_status()
+        .and_then(|status| {
+            if status.current_state != ServiceState::Stopped {
+                service.stop()?;
+            }
+            service.delete()
+        })
+        .map_err(|e| anyhow!(tr!(service_uninstall_failed, describe(&e))))?;
+    println!("{}", tr!(service_uninstalled, SERVICE_NAME));
+    Ok(())
+}
+
+fn open_service(access: ServiceAccess) -> Result<windows_service::service::Service> {
+    ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+        .and_then(|manager| manager.open_service(SERVICE_NAME, access))
+        .map_err(|e| anyhow!(tr!(service_open_failed, SERVICE_NAME, describe(&e))))
+}
+
+/// windows-service 的错误只显示 "IO error in winapi call"，取出其中系统错误的描述
+fn describe(e: &windows_service::Error) -> String {
+    match e {
+        windows_service::Error::Winapi(e) => e.to_string(),
+        e => e.to_string(),
+    }
+}
+
+/// 以服务方式运行 `entry`（初始化日志并执行 run），阻塞到服务停止
+pub fn run(
+    entry: impl FnOnce() -> LocalBoxFuture<'static, Result<()>> + Send + 'static,
+) -> Result<()> {
+    *ENTRY.lock().expect("服务入口锁已中毒") = Some(Box::new(entry));
+    service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+        .map_err(|e| anyhow!(tr!(service_control_failed, describe(&e))))
+}
+
+define_windows_service!(ffi_service_main, service_main);
+
+fn service_main(_arguments: Vec<OsString>) {
+    // 服务没有控制台，错误只能写入日志
+    if let Err(e) = run_service() {
+        error!(error = %e, "{}", tr!(service_failed, e));
+    }
+}
+
+fn run_service() -> Result<()> {
+    let entry = ENTRY
+        .lock()
+        .expect("服务入口锁已中毒")
+        .take()
+        .expect("服务入口未设置");
+
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+    let stop_tx = Mutex::new(Some(stop_tx));
+    let handler = move |control: ServiceControl| match control {
+        ServiceControl::Stop | ServiceControl::Shutdown => {
+            if let Some(stop_tx) = stop_tx.lock().expect("停止信号锁已中毒").take() {
+                let _ = stop_tx.send(());
+            }
+            ServiceControlHandlerResult::NoError
+        }
+        ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+        _ => ServiceControlHandlerResult::NotImplemented,
+    };
+    let status_handle = service_control_handler::register(SERVICE_NAME, handler)
+        .map_err(|e| anyhow!(tr!(service_control_failed, describe(&e))))?;
+    let set_status = |state, controls_accepted, exit_code| {
+        status_handle
+            .set_service_status(ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted,
+                exit_code,
+                checkpoint: 0,
+                wait_hint: Duration::default(),
+                process_id: None,
+            })
+            .map_err(|e| anyhow!(tr!(service_control_failed, describe(&e))))
+    };
+
+    set_status(
+        ServiceState::Running,
+        ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        ServiceExitCode::NO_ERROR,
+    )?;
+    // 收到停止请求时丢弃认领任务，状态文件在每次认领后已经保存
+    let result = tokio::runtime::Runtime::new()?.block_on(async {
+        tokio::select! {
+            result = entry() => result,
+            _ = stop_rx => Ok(()),
+        }
+    });
+    let exit_code = match &result {
+        Ok(()) => ServiceExitCode::NO_ERROR,
+        Err(_) => ServiceExitCode::ServiceSpecific(1),
+    };
+    set_status(
+        ServiceState::Stopped,
+        ServiceControlAccept::empty(),
+        exit_code,
+    )?;
+    result
+}
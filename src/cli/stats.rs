@@ -0,0 +1,218 @@
+use anyhow::Result;
+use bedu_claim::client::stats::{display_width, render_table};
+use bedu_claim::store::ClaimStore;
+use bedu_claim::tr;
+use chrono::{DateTime, Local, NaiveDate};
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug)]
+pub struct StatsArgs {
+    #[arg(long, help = "认领历史 SQLite 数据库路径")]
+    pub db: PathBuf,
+
+    #[arg(long, help = "起始日期 (YYYY-MM-DD，含)")]
+    pub since: Option<NaiveDate>,
+
+    #[arg(long, help = "截止日期 (YYYY-MM-DD，含)")]
+    pub until: Option<NaiveDate>,
+
+    #[arg(
+        long,
+        help = "按学科和时段统计任务被他人认领的比例和速度，帮助选择值得自动认领的学科和时段"
+    )]
+    pub contention: bool,
+
+    #[arg(
+        long,
+        help = "按星期几和小时显示任务池大小的热力图，并推荐任务最多的轮询时段"
+    )]
+    pub heatmap: bool,
+}
+
+/// 打印认领历史统计
+pub fn execute(args: &StatsArgs) -> Result<()> {
+    let start_of_day = |date: NaiveDate| {
+        date.and_hms_opt(0, 0, 0)
+            .and_then(|t| t.and_local_timezone(Local).earliest())
+    };
+    let since = args.since.and_then(start_of_day);
+    let until = args.until.and_then(|d| d.succ_opt()).and_then(start_of_day);
+
+    let store = ClaimStore::open(&args.db)?;
+
+    let daily = store.daily_summary(since, until)?;
+    let mut rows = vec![vec![
+        tr!(date_header),
+        tr!(claims_header),
+        tr!(requested_header),
+        tr!(success_rate_label),
+    ]];
+    for day in &daily {
+        rows.push(vec![
+            day.date.to_string(),
+            day.claims.to_string(),
+            day.requested.to_string(),
+            format!("{:.1}%", day.success_rate * 100.0),
+        ]);
+    }
+    let total_claims: i64 = daily.iter().map(|d| d.claims).sum();
+    let total_requested: i64 = daily.iter().map(|d| d.requested).sum();
+    rows.push(vec![
+        tr!(total_row),
+        total_claims.to_string(),
+        total_requested.to_string(),
+        if total_requested > 0 {
+            format!(
+                "{:.1}%",
+                total_claims as f64 / total_requested as f64 * 100.0
+            )
+        } else {
+            "-".to_string()
+        },
+    ]);
+    println!("{}", tr!(daily_claims_table, render_table(&rows, true)));
+
+    let mut rows = vec![vec![tr!(subject_header), tr!(claims_header)]];
+    for (subject, count) in store.claims_by_subject(since, until)? {
+        rows.push(vec![subject, count.to_string()]);
+    }
+    println!("{}", tr!(subject_claims_table, render_table(&rows, true)));
+
+    if args.contention {
+        print_contention(&store, since, until)?;
+    }
+    if args.heatmap {
+        print_heatmap(&store, since, until)?;
+    }
+
+    Ok(())
+}
+
+/// 打印竞争指数表
+fn print_contention(
+    store: &ClaimStore,
+    since: Option<DateTime<Local>>,
+    until: Option<DateTime<Local>>,
+) -> Result<()> {
+    let contention = store.contention(since, until)?;
+    if contention.is_empty() {
+        println!("{}", tr!(no_pool_records));
+        return Ok(());
+    }
+
+    let mut rows = vec![vec![
+        tr!(subject_header),
+        tr!(hour_header),
+        tr!(seen_header),
+        tr!(drained_header),
+        tr!(avg_lifetime_header),
+        tr!(contention_header),
+    ]];
+    for row in &contention {
+        rows.push(vec![
+            row.subject.clone(),
+            format!("{:02}:00-{:02}:59", row.hour, row.hour),
+            row.seen.to_string(),
+            format!(
+                "{} ({:.0}%)",
+                row.drained,
+                row.drained as f64 / row.seen as f64 * 100.0
+            ),
+            format!("{}s", row.avg_lifetime.as_secs()),
+            format!("{:.2}", row.index),
+        ]);
+    }
+    println!("{}", tr!(contention_table, render_table(&rows, true)));
+
+    Ok(())
+}
+
+/// 热力图的浓淡等级，按平均任务池大小相对最大值划分
+const SHADES: [char; 5] = ['.', ':', '+', '*', '#'];
+
+/// 推荐时段至少需要的观测次数，避免只轮询过几次的时段排在前面
+const MIN_OBSERVATIONS: i64 = 10;
+
+/// 推荐的时段数
+const RECOMMENDED_WINDOWS: usize = 3;
+
+/// 打印任务池大小热力图和推荐的轮询时段
+fn print_heatmap(
+    store: &ClaimStore,
+    since: Option<DateTime<Local>>,
+    until: Option<DateTime<Local>>,
+) -> Result<()> {
+    let availability = store.pool_availability(since, until)?;
+    if availability.is_empty() {
+        println!("{}", tr!(no_pool_records));
+        return Ok(());
+    }
+
+    let weekdays = tr!(weekday_names);
+    let weekdays: Vec<&str> = weekdays.split(',').collect();
+    let label_width = weekdays
+        .iter()
+        .map(|day| display_width(day))
+        .max()
+        .unwrap_or(0);
+    let max_size = availability
+        .iter()
+        .map(|cell| cell.avg_size)
+        .fold(0.0, f64::max);
+
+    let mut heatmap = " ".repeat(label_width);
+    for hour in 0..24 {
+        heatmap.push_str(&format!(" {:02}", hour));
+    }
+    for (index, day) in weekdays.iter().enumerate() {
+        heatmap.push('\n');
+        heatmap.push_str(day);
+        heatmap.push_str(&" ".repeat(label_width - display_width(day)));
+        for hour in 0..24 {
+            let cell = availability.iter().find(|cell| {
+                cell.weekday.num_days_from_monday() as usize == index && cell.hour == hour
+            });
+            let shade = match cell {
+                Some(cell) if max_size > 0.0 => {
+                    let level = (cell.avg_size / max_size * (SHADES.len() - 1) as f64).ceil();
+                    SHADES[level as usize]
+                }
+                Some(_) => SHADES[0],
+                None => ' ',
+            };
+            heatmap.push_str(&format!("  {}", shade));
+        }
+    }
+    println!("{}", tr!(heatmap_table, max_size, heatmap));
+
+    let mut best: Vec<_> = availability
+        .iter()
+        .filter(|cell| cell.observations >= MIN_OBSERVATIONS && cell.avg_size > 0.0)
+        .collect();
+    best.sort_by(|a, b| {
+        b.avg_size
+            .total_cmp(&a.avg_size)
+            .then(b.nonempty_rate.total_cmp(&a.nonempty_rate))
+    });
+    if best.is_empty() {
+        println!("{}", tr!(no_recommended_windows, MIN_OBSERVATIONS));
+        return Ok(());
+    }
+    println!("{}", tr!(recommended_windows));
+    for cell in best.into_iter().take(RECOMMENDED_WINDOWS) {
+        println!(
+            "{}",
+            tr!(
+                recommended_window,
+                weekdays[cell.weekday.num_days_from_monday() as usize],
+                cell.hour,
+                cell.hour,
+                cell.avg_size,
+                cell.nonempty_rate * 100.0,
+                cell.observations
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! 终端颜色：成功为绿色、失败为红色、限流为黄色
+//!
+//! 输出不是终端或设置了 `NO_COLOR` 环境变量时不带颜色。
+
+use anstyle::{AnsiColor, Style};
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use std::fmt::{self, Display};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::field::{Field, Visit};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::{DefaultFields, Writer};
+
+/// 命令输出（stdout）是否带颜色
+static STDOUT_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// 输出流是否应该带颜色
+pub fn should_color(stream: &impl IsTerminal) -> bool {
+    stream.is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// 根据 stdout 决定命令输出是否带颜色，程序启动时调用一次
+pub fn init() {
+    STDOUT_COLOR.store(should_color(&std::io::stdout()), Ordering::Relaxed);
+}
+
+/// 交互式提示（`init`、`pick`）的主题，提示输出到 stderr，stderr 不需要颜色时使用无颜色主题
+pub fn prompt_theme() -> Box<dyn Theme> {
+    if should_color(&std::io::stderr()) {
+        Box::new(ColorfulTheme::default())
+    } else {
+        Box::new(SimpleTheme)
+    }
+}
+
+/// 输出状态，决定文字颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    /// 限流、达到上限等需要等待的状态
+    Limited,
+}
+
+impl Status {
+    fn style(self) -> Style {
+        match self {
+            Status::Success => AnsiColor::Green.on_default(),
+            Status::Failure => AnsiColor::Red.on_default(),
+            Status::Limited => AnsiColor::Yellow.on_default(),
+        }
+    }
+
+    /// 日志 `outcome` 字段对应的状态
+    fn from_outcome(outcome: &str) -> Option<Self> {
+        match outcome {
+            "claimed" => Some(Status::Success),
+            "failed" => Some(Status::Failure),
+            "limited" => Some(Status::Limited),
+            _ => None,
+        }
+    }
+
+    /// 按状态给打印到 stdout 的文字着色
+    pub fn paint<T: Display>(self, text: T) -> Painted<T> {
+        Painted {
+            style: STDOUT_COLOR.load(Ordering::Relaxed).then(|| self.style()),
+            text,
+        }
+    }
+}
+
+/// 着色后的文字，不需要颜色时原样输出
+pub struct Painted<T> {
+    style: Option<Style>,
+    text: T,
+}
+
+impl<T: Display> Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Some(style) => write!(f, "{}{}{:#}", style, self.text, style),
+            None => self.text.fmt(f),
+        }
+    }
+}
+
+/// 终端日志的字段格式化器，按 `outcome` 字段（`claimed`/`failed`/`limited`）给日志消息着色
+///
+/// 与日志文件使用的 [`PlainFields`](bedu_claim::logging::PlainFields) 是不同的类型，两层不会共享已格式化的字段。
+#[derive(Debug, Default)]
+pub struct StatusFields(DefaultFields);
+
+impl<'writer> FormatFields<'writer> for StatusFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let mut outcome = OutcomeVisitor(None);
+        fields.record(&mut outcome);
+        match outcome.0.filter(|_| writer.has_ansi_escapes()) {
+            Some(status) => {
+                let style = status.style();
+                write!(writer, "{}", style)?;
+                self.0.format_fields(writer.by_ref(), fields)?;
+                write!(writer, "{:#}", style)
+            }
+            None => self.0.format_fields(writer, fields),
+        }
+    }
+}
+
+/// 找出日志中的 `outcome` 字段
+struct OutcomeVisitor(Option<Status>);
+
+impl Visit for OutcomeVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "outcome" {
+            self.0 = Status::from_outcome(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
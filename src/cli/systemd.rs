@@ -0,0 +1,101 @@
+//! systemd 集成（仅 Unix）：在 `Type=notify` 服务中通过 `$NOTIFY_SOCKET` 上报状态
+//!
+//! 启动认领后发送 `READY=1`，之后定期以 `STATUS=` 更新 `systemctl status` 中显示的认领进度；
+//! 服务配置了 `WatchdogSec=` 时同时发送 `WATCHDOG=1`，进程卡死不再发送时由 systemd 重启。
+//! 认领结束时发送 `STOPPING=1`。不在 systemd 下运行时什么也不做。
+
+use bedu_claim::client::{AutoClaimer, ClaimerStatus};
+use bedu_claim::tr;
+#[cfg(target_os = "linux")]
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// 未配置看门狗时的状态刷新间隔
+const STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// systemd 通知套接字
+pub struct Notifier {
+    socket: UnixDatagram,
+    addr: SocketAddr,
+}
+
+impl Notifier {
+    /// 从 `$NOTIFY_SOCKET` 创建，未设置（不在 systemd 下运行）或地址无效时返回 None
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os("NOTIFY_SOCKET")?;
+        let path = path.to_str()?;
+        let addr = match path.strip_prefix('@') {
+            #[cfg(target_os = "linux")]
+            Some(name) => SocketAddr::from_abstract_name(name.as_bytes()).ok()?,
+            #[cfg(not(target_os = "linux"))]
+            Some(_) => return None,
+            None => SocketAddr::from_pathname(path).ok()?,
+        };
+        let socket = UnixDatagram::unbound().ok()?;
+        Some(Self { socket, addr })
+    }
+
+    /// 发送通知，每行一个 `KEY=VALUE`；发送失败不影响认领
+    pub fn notify(&self, state: &str) {
+        if let Err(e) = self.socket.send_to_addr(state.as_bytes(), &self.addr) {
+            debug!(error = %e, "{}", tr!(systemd_notify_failed, e));
+        }
+    }
+
+    /// 认领器状态对应的 `STATUS=` 行
+    fn status_line(status: &ClaimerStatus) -> String {
+        let progress = tr!(
+            systemd_status,
+            status.claimed,
+            status.claim_limit,
+            status.attempts
+        );
+        let state = if status.paused {
+            Some(tr!(paused_state))
+        } else if status.dormant {
+            Some(tr!(dormant_too_many_pending))
+        } else {
+            None
+        };
+        match state {
+            Some(state) => format!("STATUS={}，{}", progress, state),
+            None => format!("STATUS={}", progress),
+        }
+    }
+}
+
+/// systemd 要求的看门狗间隔：`$WATCHDOG_USEC` 的一半，未启用或不是发给本进程时返回 None
+fn watchdog_interval() -> Option<Duration> {
+    if let Ok(pid) = std::env::var("WATCHDOG_PID")
+        && pid.parse::<u32>().ok()? != std::process::id()
+    {
+        return None;
+    }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    (usec >= 2).then(|| Duration::from_micros(usec / 2))
+}
+
+/// 发送 `READY=1`，之后定期上报认领进度并喂看门狗，直到任务被取消
+///
+/// 看门狗在读取认领器状态之后发送，认领器的锁被长期占用时 systemd 也能发现。
+pub async fn supervise(notifier: Arc<Notifier>, claimer: Arc<AutoClaimer>) {
+    let watchdog = watchdog_interval();
+    let status = claimer.status().await;
+    notifier.notify(&format!("READY=1\n{}", Notifier::status_line(&status)));
+
+    let mut interval =
+        tokio::time::interval(watchdog.map_or(STATUS_INTERVAL, |w| w.min(STATUS_INTERVAL)));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let status = Notifier::status_line(&claimer.status().await);
+        if watchdog.is_some() {
+            notifier.notify(&format!("WATCHDOG=1\n{}", status));
+        } else {
+            notifier.notify(&status);
+        }
+    }
+}
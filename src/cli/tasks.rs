@@ -0,0 +1,299 @@
+use anyhow::{Result, anyhow};
+use bedu_claim::api::{TaskItem, TaskListData};
+use bedu_claim::client::stats::render_table;
+use bedu_claim::client::{ClaimOutcome, HttpClient};
+use bedu_claim::tr;
+use chrono::{Local, NaiveDateTime};
+use clap::ValueEnum;
+use dialoguer::{Confirm, MultiSelect};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::cli::style::{self, Status};
+use crate::cli::{ConnectionArgs, FilterArgs, validate_task_type};
+
+/// 任务列表输出格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    /// 表格
+    Table,
+    /// 原始任务列表 JSON
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    #[arg(long, default_value = "1", help = "页码")]
+    pub page: i64,
+
+    #[arg(long, default_value = "20", help = "每页数量")]
+    pub size: i64,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value = "table",
+        help = "输出格式 (table/json)"
+    )]
+    pub format: ListFormat,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ClaimArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+
+    #[arg(
+        short,
+        long,
+        visible_alias = "type",
+        default_value = "audittask",
+        help = "任务类型 (audittask/producetask)"
+    )]
+    pub task_type: String,
+
+    #[arg(required = true, help = "任务ID（生产任务为线索ID）")]
+    pub ids: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct PickArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    #[arg(long, default_value = "50", help = "最多列出的任务数量")]
+    pub size: i64,
+}
+
+/// 按筛选参数获取任务池中的一页任务
+async fn fetch_tasks(
+    client: &HttpClient,
+    filter: &FilterArgs,
+    page: i64,
+    size: i64,
+) -> Result<TaskListData> {
+    filter.validate()?;
+    let ids = filter.resolve(client).await?;
+
+    let mut options = HashMap::new();
+    options.insert("pn".to_string(), json!(page));
+    options.insert("rn".to_string(), json!(size));
+    options.insert("clueType".to_string(), json!(ids.clue_type_id));
+    options.insert("step".to_string(), json!(ids.step_id));
+    options.insert("subject".to_string(), json!(ids.subject_id));
+    options.insert("taskType".to_string(), json!(filter.task_type));
+
+    client
+        .get_audit_task_list(&options)
+        .await?
+        .into_result()
+        .map_err(|e| anyhow!(tr!(fetch_task_list_failed, e)))
+}
+
+/// 列出任务池中的任务
+pub async fn list(args: ListArgs) -> Result<()> {
+    let client = args.connection.client()?;
+    let task_list = fetch_tasks(&client, &args.filter, args.page, args.size).await?;
+
+    match args.format {
+        ListFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&task_list)?);
+        }
+        ListFormat::Table => {
+            let now = Local::now().naive_local();
+            let mut rows = vec![vec![
+                tr!(task_id_header),
+                tr!(clue_id_header),
+                tr!(subject_header),
+                tr!(step_header),
+                tr!(clue_type_header),
+                tr!(waiting_header),
+                tr!(brief_header),
+            ]];
+            for task in &task_list.list {
+                rows.push(vec![
+                    task.task_id.to_string(),
+                    task.clue_id.to_string(),
+                    task.subject_name.clone(),
+                    task.step_name.clone(),
+                    task.clue_type_name.clone(),
+                    task_age(task, now),
+                    truncate(&task.brief, 30),
+                ]);
+            }
+
+            println!("{}", tr!(task_total, task_list.total));
+            println!("{}", render_table(&rows, true));
+        }
+    }
+
+    Ok(())
+}
+
+/// 任务创建至今的时长，创建时间无法解析时显示 `-`
+fn task_age(task: &TaskItem, now: NaiveDateTime) -> String {
+    let Ok(created) = NaiveDateTime::parse_from_str(&task.create_time, "%Y-%m-%d %H:%M:%S") else {
+        return "-".to_string();
+    };
+
+    let minutes = (now - created).num_minutes().max(0);
+    match minutes {
+        0 => tr!(just_now),
+        m if m < 60 => tr!(minutes, m),
+        m if m < 60 * 24 => tr!(hours_minutes, m / 60, m % 60),
+        m => tr!(days_hours, m / (60 * 24), m % (60 * 24) / 60),
+    }
+}
+
+/// 按字符截断过长的文本
+fn truncate(text: &str, max_chars: usize) -> String {
+    let text = text.replace(['\n', '\r', '\t'], " ");
+    if text.chars().count() <= max_chars {
+        text
+    } else {
+        let mut truncated: String = text.chars().take(max_chars).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+/// 认领指定ID的任务
+pub async fn claim(args: ClaimArgs) -> Result<()> {
+    validate_task_type(&args.task_type)?;
+    let client = args.connection.client()?;
+    let ids = parse_ids(&args.ids)?;
+    claim_ids(&client, ids, &args.task_type).await
+}
+
+/// 列出任务池中的任务，多选后认领选中的任务
+pub async fn pick(args: PickArgs) -> Result<()> {
+    let client = args.connection.client()?;
+    let task_list = fetch_tasks(&client, &args.filter, 1, args.size).await?;
+    if task_list.list.is_empty() {
+        println!("{}", tr!(no_matching_tasks));
+        return Ok(());
+    }
+
+    let now = Local::now().naive_local();
+    let items: Vec<String> = task_list
+        .list
+        .iter()
+        .map(|task| {
+            format!(
+                "{}  {}/{}/{}  {}  {}",
+                task.task_id,
+                task.subject_name,
+                task.step_name,
+                task.clue_type_name,
+                task_age(task, now),
+                truncate(&task.brief, 40)
+            )
+        })
+        .collect();
+
+    let theme = style::prompt_theme();
+    let selected = MultiSelect::with_theme(&*theme)
+        .with_prompt(tr!(pick_prompt, task_list.total))
+        .items(&items)
+        .max_length(15)
+        .interact()?;
+    if selected.is_empty() {
+        println!("{}", tr!(pick_none_selected));
+        return Ok(());
+    }
+    if !Confirm::with_theme(&*theme)
+        .with_prompt(tr!(pick_confirm, selected.len()))
+        .default(true)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    // 生产任务按线索ID认领
+    let ids = selected
+        .into_iter()
+        .map(|index| {
+            let task = &task_list.list[index];
+            if args.filter.task_type == "producetask" {
+                task.clue_id.to_string()
+            } else {
+                task.task_id.to_string()
+            }
+        })
+        .collect();
+    claim_ids(&client, ids, &args.filter.task_type).await
+}
+
+/// 认领任务并打印每个任务的结果
+async fn claim_ids(client: &HttpClient, ids: Vec<String>, task_type: &str) -> Result<()> {
+    let response = client.claim_audit_task(ids.clone(), task_type).await?;
+    if response.errno != 0 {
+        return Err(anyhow!(tr!(
+            claim_command_failed,
+            ids,
+            response.errno,
+            response.errmsg
+        )));
+    }
+
+    let outcome = ClaimOutcome::from_response(&ids, &response);
+    println!(
+        "{}",
+        Status::Success.paint(tr!(claim_command_succeeded, outcome.succeeded))
+    );
+    for failed in &outcome.failed {
+        println!(
+            "{}",
+            Status::Failure.paint(tr!(claim_command_task_failed, failed.id, failed.reason))
+        );
+    }
+
+    Ok(())
+}
+
+/// 释放已认领的任务
+pub async fn release(args: ClaimArgs) -> Result<()> {
+    validate_task_type(&args.task_type)?;
+    let client = args.connection.client()?;
+    let ids = parse_ids(&args.ids)?;
+
+    let response = client.release_task(ids.clone(), &args.task_type).await?;
+    if response.errno != 0 {
+        return Err(anyhow!(tr!(
+            release_failed,
+            ids,
+            response.errno,
+            response.errmsg
+        )));
+    }
+
+    println!("{}", Status::Success.paint(tr!(released, ids)));
+
+    Ok(())
+}
+
+/// 去重并提前校验ID格式，避免请求时才报解析错误
+fn parse_ids(raw: &[String]) -> Result<Vec<String>> {
+    let mut ids: Vec<i64> = Vec::new();
+    for id in raw {
+        let id: i64 = id
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!(tr!(invalid_task_id, id.trim())))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    Ok(ids.iter().map(i64::to_string).collect())
+}
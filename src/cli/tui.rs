@@ -0,0 +1,351 @@
+//! 交互式终端仪表盘（`run --tui`，需 `tui` 特性）
+
+use anyhow::Result;
+use bedu_claim::client::bedu_api::BeduApi;
+use bedu_claim::client::{AutoClaimer, ClaimEvent, ClaimerStatus};
+use bedu_claim::tr;
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Gauge, List, ListItem, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// 保留的最近事件条数
+const MAX_EVENTS: usize = 200;
+/// 保留的列表请求耗时样本数
+const MAX_LATENCIES: usize = 300;
+/// 界面刷新间隔
+const TICK: Duration = Duration::from_millis(200);
+
+/// 显示仪表盘直到用户按 `q` 退出；认领结束后仪表盘保留最终状态，中途退出会取消认领
+pub async fn run<A: BeduApi>(
+    claimer: &AutoClaimer<A>,
+    claim: impl Future<Output = Result<()>>,
+) -> Result<()> {
+    let receiver = claimer.subscribe();
+    let mut dashboard = Dashboard::new(claimer.status().await);
+    let mut terminal = ratatui::try_init()?;
+    let result = dashboard.run(&mut terminal, claimer, receiver, claim).await;
+    ratatui::restore();
+    result
+}
+
+struct Dashboard {
+    started_at: Instant,
+    status: ClaimerStatus,
+    pool_size: Option<usize>,
+    latencies: VecDeque<u64>,
+    events: VecDeque<(String, String, Color)>,
+    /// 认领结束后的提示，运行中为 None
+    finished: Option<String>,
+}
+
+impl Dashboard {
+    fn new(status: ClaimerStatus) -> Self {
+        Self {
+            started_at: Instant::now(),
+            status,
+            pool_size: None,
+            latencies: VecDeque::with_capacity(MAX_LATENCIES),
+            events: VecDeque::with_capacity(MAX_EVENTS),
+            finished: None,
+        }
+    }
+
+    async fn run<A: BeduApi>(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        claimer: &AutoClaimer<A>,
+        mut receiver: broadcast::Receiver<ClaimEvent>,
+        claim: impl Future<Output = Result<()>>,
+    ) -> Result<()> {
+        tokio::pin!(claim);
+        let mut result = None;
+        let mut tick = tokio::time::interval(TICK);
+
+        loop {
+            tokio::select! {
+                outcome = &mut claim, if result.is_none() => {
+                    self.finished = Some(match &outcome {
+                        Ok(()) => tr!(dashboard_finished),
+                        Err(e) => tr!(dashboard_aborted, e),
+                    });
+                    result = Some(outcome);
+                }
+                event = receiver.recv() => match event {
+                    Ok(event) => self.record(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    // 认领器由调用方持有，事件通道不会在此期间关闭
+                    Err(broadcast::error::RecvError::Closed) => unreachable!(),
+                },
+                _ = tick.tick() => {
+                    self.status = claimer.status().await;
+                    terminal.draw(|frame| self.draw(frame))?;
+                    while event::poll(Duration::ZERO)? {
+                        let Event::Key(key) = event::read()? else {
+                            continue;
+                        };
+                        if key.kind != KeyEventKind::Press {
+                            continue;
+                        }
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => {
+                                return self.quit(claimer, result).await;
+                            }
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                return self.quit(claimer, result).await;
+                            }
+                            KeyCode::Char('p') | KeyCode::Char(' ') => {
+                                if claimer.is_paused() {
+                                    claimer.resume();
+                                } else {
+                                    claimer.pause();
+                                }
+                            }
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                let limit = claimer.claim_limit().await;
+                                claimer.set_claim_limit(limit + 1).await;
+                            }
+                            KeyCode::Char('-') => {
+                                let limit = claimer.claim_limit().await;
+                                if limit > 1 {
+                                    claimer.set_claim_limit(limit - 1).await;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 退出仪表盘；认领仍在运行时随之取消，并保存状态文件
+    async fn quit<A: BeduApi>(
+        &self,
+        claimer: &AutoClaimer<A>,
+        result: Option<Result<()>>,
+    ) -> Result<()> {
+        match result {
+            Some(result) => result,
+            None => claimer.save_state().await,
+        }
+    }
+
+    fn record(&mut self, event: &ClaimEvent) {
+        if let ClaimEvent::PoolFetched {
+            pool_size,
+            latency_ms,
+            ..
+        } = event
+        {
+            self.pool_size = Some(*pool_size);
+            if self.latencies.len() == MAX_LATENCIES {
+                self.latencies.pop_front();
+            }
+            self.latencies.push_back(*latency_ms);
+        }
+
+        if let Some((text, color)) = describe(event) {
+            if self.events.len() == MAX_EVENTS {
+                self.events.pop_back();
+            }
+            self.events
+                .push_front((Local::now().format("%H:%M:%S").to_string(), text, color));
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, gauge, info, latency, events, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(7),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let status = &self.status;
+        let (state, color) = if self.finished.is_some() {
+            (tr!(finished_state), Color::DarkGray)
+        } else if status.paused {
+            (tr!(paused_state), Color::Yellow)
+        } else if status.dormant {
+            (tr!(dormant_state), Color::Yellow)
+        } else {
+            (tr!(claiming_state), Color::Green)
+        };
+        let elapsed = self.started_at.elapsed().as_secs();
+        frame.render_widget(
+            Line::from(vec![
+                Span::from(" bedu-claim ").bold(),
+                Span::from(format!(" {} ", state))
+                    .fg(Color::Black)
+                    .bg(color),
+                Span::from(tr!(
+                    dashboard_uptime,
+                    elapsed / 3600,
+                    elapsed / 60 % 60,
+                    elapsed % 60
+                )),
+            ]),
+            header,
+        );
+
+        let limit = status.claim_limit.max(1);
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(tr!(dashboard_progress_title)))
+                .gauge_style(Style::new().fg(Color::Green))
+                .ratio((status.claimed as f64 / limit as f64).clamp(0.0, 1.0))
+                .label(format!("{}/{}", status.claimed, status.claim_limit)),
+            gauge,
+        );
+
+        let pool_size = self
+            .pool_size
+            .map_or("-".to_string(), |pool_size| pool_size.to_string());
+        frame.render_widget(
+            Paragraph::new(tr!(
+                dashboard_info,
+                pool_size,
+                status.attempts,
+                status.claimed_today,
+                status.stats.success_rate * 100.0
+            )),
+            info,
+        );
+
+        // 只显示能放下的最近样本
+        let width = latency.width.saturating_sub(2) as usize;
+        let samples = self
+            .latencies
+            .iter()
+            .skip(self.latencies.len().saturating_sub(width))
+            .copied();
+        let title = match self.latencies.back() {
+            Some(last) => tr!(
+                dashboard_latency_title,
+                last,
+                status.stats.avg_list_latency.as_millis()
+            ),
+            None => tr!(dashboard_latency_title_empty),
+        };
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::bordered().title(title))
+                .style(Style::new().fg(Color::Cyan))
+                .data(samples),
+            latency,
+        );
+
+        let items = self.events.iter().map(|(time, text, color)| {
+            ListItem::new(Line::from(vec![
+                Span::from(format!("{} ", time)).fg(Color::DarkGray),
+                Span::from(text.as_str()).fg(*color),
+            ]))
+        });
+        frame.render_widget(
+            List::new(items).block(Block::bordered().title(tr!(dashboard_events_title))),
+            events,
+        );
+
+        let hint = match &self.finished {
+            Some(message) => Line::from(format!(" {}", message)).bold(),
+            None => Line::from(tr!(dashboard_hint)).dark_gray(),
+        };
+        frame.render_widget(hint, footer);
+    }
+}
+
+/// 事件在列表中的一行文字和颜色，不需要显示的事件返回 None
+fn describe(event: &ClaimEvent) -> Option<(String, Color)> {
+    let resume_at = |time: &chrono::DateTime<chrono::FixedOffset>| time.format("%m-%d %H:%M");
+    let line = match event {
+        ClaimEvent::AttemptStarted { .. }
+        | ClaimEvent::PoolFetched { .. }
+        | ClaimEvent::NewTasksAppeared { .. }
+        | ClaimEvent::TasksDrained { .. }
+        | ClaimEvent::TaskClaimed { .. } => return None,
+        ClaimEvent::TasksSpotted { tasks, .. } => (tr!(new_tasks_title, tasks.len()), Color::Cyan),
+        ClaimEvent::PendingLimitReached {
+            pending,
+            max_pending,
+            ..
+        } => (
+            tr!(event_pending_limit, pending, max_pending),
+            Color::Yellow,
+        ),
+        ClaimEvent::Claimed {
+            task_ids, total, ..
+        } => (
+            tr!(event_claimed, task_ids.len(), task_ids.join(", "), total),
+            Color::Green,
+        ),
+        ClaimEvent::ClaimFailed { errno, errmsg, .. } => {
+            (tr!(event_claim_failed, errno, errmsg), Color::Red)
+        }
+        ClaimEvent::LimitReached { total, limit } => {
+            (tr!(event_limit_reached, total, limit), Color::Green)
+        }
+        ClaimEvent::CookieExpired { message } => (tr!(event_cookie_expired, message), Color::Red),
+        ClaimEvent::LimitChanged { limit } => (tr!(event_limit_changed, limit), Color::Blue),
+        ClaimEvent::SettingsChanged { settings } => {
+            (tr!(event_settings_changed, settings.interval), Color::Blue)
+        }
+        ClaimEvent::ReloadRequested => (tr!(reload_requested), Color::Blue),
+        ClaimEvent::Paused => (tr!(event_paused), Color::Yellow),
+        ClaimEvent::Unpaused => (tr!(claiming_resumed), Color::Blue),
+        ClaimEvent::Dormant { errno, .. } => (tr!(event_dormant, errno), Color::Yellow),
+        ClaimEvent::Resumed { pending } => (tr!(event_resumed, pending), Color::Blue),
+        ClaimEvent::DailyLimitReached {
+            claimed_today,
+            daily_limit,
+            resume_at: time,
+        } => (
+            tr!(
+                event_daily_limit,
+                claimed_today,
+                daily_limit,
+                resume_at(time)
+            ),
+            Color::Yellow,
+        ),
+        ClaimEvent::HourlyLimitReached {
+            claimed_last_hour,
+            max_claims_per_hour,
+            resume_at: time,
+        } => (
+            tr!(
+                event_hourly_limit,
+                claimed_last_hour,
+                max_claims_per_hour,
+                resume_at(time)
+            ),
+            Color::Yellow,
+        ),
+        ClaimEvent::NonWorkingDay { resume_at: time } => {
+            (tr!(event_non_working_day, resume_at(time)), Color::Yellow)
+        }
+        ClaimEvent::OutsideActiveHours { resume_at: time } => (
+            tr!(event_outside_active_hours, resume_at(time)),
+            Color::Yellow,
+        ),
+        ClaimEvent::AwaitingCronTrigger { resume_at: time } => {
+            (tr!(event_awaiting_cron, resume_at(time)), Color::Yellow)
+        }
+        ClaimEvent::Error { message, .. } => (tr!(event_error, message), Color::Red),
+        ClaimEvent::Finished { summary } => {
+            (tr!(event_finished, summary.total_claims), Color::Green)
+        }
+        ClaimEvent::Aborted { message } => (tr!(claim_aborted, message), Color::Red),
+    };
+    Some(line)
+}
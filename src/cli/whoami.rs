@@ -0,0 +1,31 @@
+use anyhow::{Result, anyhow};
+use bedu_claim::tr;
+
+use crate::cli::ConnectionArgs;
+
+#[derive(clap::Args, Debug)]
+pub struct WhoamiArgs {
+    #[command(flatten)]
+    pub connection: ConnectionArgs,
+}
+
+/// 打印当前账号信息，用于确认 Cookie 有效且属于预期账号
+pub async fn execute(args: WhoamiArgs) -> Result<()> {
+    let client = args.connection.client()?;
+
+    let data = client
+        .get_user_info()
+        .await?
+        .into_result()
+        .map_err(|e| anyhow!(tr!(fetch_user_info_failed, e)))?;
+    println!("{}", tr!(user_name_line, data.user_name));
+    println!("{}", tr!(roles_line, data.role_names.join(", ")));
+    if !data.role_links.is_empty() {
+        println!("{}", tr!(role_links_line));
+        for link in &data.role_links {
+            println!("  {}", link);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 审计日志中的一条认领记录
+#[derive(Debug, Serialize)]
+pub struct AuditRecord<'a> {
+    pub timestamp: DateTime<Local>,
+    pub url: &'a str,
+    pub task_type: &'a str,
+    pub request: &'a Value,
+    pub status: u16,
+    /// 原始响应，能解析为 JSON 时按 JSON 保存，否则保存为字符串
+    pub response: Value,
+}
+
+/// 只追加的 JSONL 审计日志
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一条认领请求及其响应
+    pub fn append_claim(
+        &self,
+        url: &str,
+        task_type: &str,
+        request: &Value,
+        status: u16,
+        body: &str,
+    ) -> Result<()> {
+        let record = AuditRecord {
+            timestamp: Local::now(),
+            url,
+            task_type,
+            request,
+            status,
+            response: serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.into())),
+        };
+
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().expect("audit log mutex poisoned");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+
+        Ok(())
+    }
+}
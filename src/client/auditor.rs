@@ -0,0 +1,210 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::api::ClueDetailData;
+use crate::client::bedu_api::BeduApi;
+use crate::client::ocr::{ImageTextExtractor, NoExtractor, fill_image_text};
+use crate::client::throttle::Throttle;
+use crate::client::{ClaimEvent, ClaimedTask, HttpClient};
+
+/// 审核结论
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    /// 通过
+    Approve,
+    /// 驳回，附带原因
+    Reject { reason: String },
+    /// 跳过，不提交结论，留给人工处理
+    Skip,
+}
+
+/// 审核判定器，由使用方实现具体的判定逻辑
+///
+/// 实现时可以直接写 `async fn judge(...)`。
+pub trait Judge: Send + Sync {
+    fn judge(
+        &self,
+        task: &ClaimedTask,
+        detail: &ClueDetailData,
+    ) -> impl Future<Output = Result<Verdict>> + Send;
+}
+
+/// 自动审核配置
+#[derive(Debug, Clone)]
+pub struct AutoAuditorConfig {
+    /// 两次提交之间的最小间隔
+    pub submit_interval: Duration,
+    /// 只判定不提交
+    pub dry_run: bool,
+}
+
+impl Default for AutoAuditorConfig {
+    fn default() -> Self {
+        Self {
+            submit_interval: Duration::from_secs(2),
+            dry_run: false,
+        }
+    }
+}
+
+/// 单个任务的审核记录
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditDecision {
+    pub task_id: i64,
+    pub clue_id: i64,
+    /// 判定结果，获取详情或判定出错时为 None
+    pub verdict: Option<Verdict>,
+    /// 是否已成功提交到服务器
+    pub submitted: bool,
+    pub error: Option<String>,
+    pub decided_at: DateTime<Local>,
+}
+
+/// 自动审核器：获取认领任务的详情，交给判定器判定后提交结论
+pub struct AutoAuditor<J, E = NoExtractor, A = HttpClient> {
+    client: Arc<A>,
+    judge: J,
+    extractor: Option<E>,
+    config: AutoAuditorConfig,
+    throttle: Throttle,
+}
+
+impl<J: Judge, A: BeduApi> AutoAuditor<J, NoExtractor, A> {
+    /// 创建新的自动审核器实例
+    pub fn new(client: Arc<A>, judge: J, config: AutoAuditorConfig) -> Self {
+        Self {
+            client,
+            judge,
+            extractor: None,
+            throttle: Throttle::new(config.submit_interval),
+            config,
+        }
+    }
+
+    /// 设置图片文字识别器，纯图片题目在判定前先识别出题干文字
+    pub fn with_image_extractor<E: ImageTextExtractor>(self, extractor: E) -> AutoAuditor<J, E, A> {
+        AutoAuditor {
+            client: self.client,
+            judge: self.judge,
+            extractor: Some(extractor),
+            config: self.config,
+            throttle: self.throttle,
+        }
+    }
+}
+
+impl<J: Judge, E: ImageTextExtractor, A: BeduApi> AutoAuditor<J, E, A> {
+    /// 审核单个任务，出错时记录在返回的审核记录中
+    pub async fn audit(&self, task: &ClaimedTask) -> AuditDecision {
+        let mut decision = AuditDecision {
+            task_id: task.task.task_id,
+            clue_id: task.task.clue_id,
+            verdict: None,
+            submitted: false,
+            error: None,
+            decided_at: Local::now(),
+        };
+
+        match self.judge_task(task).await {
+            Ok(verdict) => {
+                decision.verdict = Some(verdict.clone());
+                if let Err(e) = self.submit(task, &verdict).await {
+                    decision.error = Some(e.to_string());
+                } else {
+                    decision.submitted = verdict != Verdict::Skip && !self.config.dry_run;
+                }
+            }
+            Err(e) => decision.error = Some(e.to_string()),
+        }
+        decision.decided_at = Local::now();
+
+        match &decision.error {
+            None => info!(
+                task_id = decision.task_id,
+                verdict = ?decision.verdict,
+                submitted = decision.submitted,
+                "{}",
+                tr!(audit_verdict, decision.task_id, decision.verdict)
+            ),
+            Some(error) => warn!(
+                task_id = decision.task_id,
+                verdict = ?decision.verdict,
+                error = %error,
+                "{}",
+                tr!(audit_failed, decision.task_id, error)
+            ),
+        }
+
+        decision
+    }
+
+    /// 依次审核事件流中认领成功的任务，直到事件通道关闭
+    pub async fn run(&self, mut receiver: broadcast::Receiver<ClaimEvent>) -> Vec<AuditDecision> {
+        let mut decisions = Vec::new();
+        loop {
+            match receiver.recv().await {
+                Ok(ClaimEvent::TaskClaimed { task }) => {
+                    if task.task_type == "audittask" {
+                        decisions.push(self.audit(&task).await);
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "{}", tr!(auditor_lagged, skipped));
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+
+        decisions
+    }
+
+    async fn judge_task(&self, task: &ClaimedTask) -> Result<Verdict> {
+        let mut detail = self
+            .client
+            .get_clue_detail(task.task.clue_id)
+            .await?
+            .into_result()
+            .map_err(|e| anyhow!(tr!(fetch_clue_detail_failed, e)))?;
+        if let Some(extractor) = &self.extractor {
+            fill_image_text(self.client.as_ref(), extractor, &mut detail.content).await?;
+        }
+
+        self.judge.judge(task, &detail).await
+    }
+
+    /// 按最小间隔限速提交结论，跳过或演练模式下不提交
+    async fn submit(&self, task: &ClaimedTask, verdict: &Verdict) -> Result<()> {
+        let (approved, reason) = match verdict {
+            Verdict::Approve => (true, ""),
+            Verdict::Reject { reason } => (false, reason.as_str()),
+            Verdict::Skip => return Ok(()),
+        };
+        if self.config.dry_run {
+            return Ok(());
+        }
+
+        let _permit = self.throttle.acquire().await;
+        let response = self
+            .client
+            .submit_audit_result(task.task.task_id, approved, reason)
+            .await?;
+        if response.errno != 0 {
+            return Err(anyhow!(tr!(
+                submit_verdict_failed,
+                response.errno,
+                response.errmsg
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::api::{
+    ClaimResponse, ClueDetailResponse, LabelResponse, MyTaskListResponse, ProduceAnswerResponse,
+    QuestionContent, TaskListResponse, UserInfoResponse,
+};
+use crate::client::{HttpClient, TaskListFetch};
+
+/// 百度教育接口的抽象，认领器、审核器和生产器都通过它访问服务器
+///
+/// [`HttpClient`] 是默认实现；测试或离线调试时可以实现一个内存中的假服务替换它。
+/// 实现时可以直接写 `async fn`。各方法的含义见 [`HttpClient`] 上的同名方法。
+pub trait BeduApi: Send + Sync {
+    /// 获取任务池中的任务列表
+    fn get_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> impl Future<Output = Result<TaskListResponse>> + Send;
+
+    /// 获取任务池中的任务列表，并告知任务池自上次获取以来是否有变化
+    ///
+    /// 默认实现总是视为有变化。
+    fn fetch_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> impl Future<Output = Result<TaskListFetch>> + Send {
+        async move {
+            Ok(TaskListFetch {
+                response: self.get_audit_task_list(options).await?,
+                modified: true,
+            })
+        }
+    }
+
+    /// 获取我的任务列表
+    fn get_my_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> impl Future<Output = Result<MyTaskListResponse>> + Send;
+
+    /// 认领任务，生产任务传线索ID
+    fn claim_audit_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> impl Future<Output = Result<ClaimResponse>> + Send;
+
+    /// 释放已认领的任务
+    fn release_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> impl Future<Output = Result<ClaimResponse>> + Send;
+
+    /// 提交审核结论
+    fn submit_audit_result(
+        &self,
+        task_id: i64,
+        approved: bool,
+        reason: &str,
+    ) -> impl Future<Output = Result<ClaimResponse>> + Send;
+
+    /// 提交生产任务的答案
+    fn submit_produce_answer(
+        &self,
+        clue_id: i64,
+        content: &QuestionContent,
+    ) -> impl Future<Output = Result<ProduceAnswerResponse>> + Send;
+
+    /// 上传图片，返回访问地址
+    fn upload_image(
+        &self,
+        bytes: Vec<u8>,
+        filename: &str,
+    ) -> impl Future<Output = Result<String>> + Send;
+
+    /// 下载图片等附件
+    fn download_image(&self, url: &str) -> impl Future<Output = Result<Vec<u8>>> + Send;
+
+    /// 获取线索详情
+    fn get_clue_detail(
+        &self,
+        clue_id: i64,
+    ) -> impl Future<Output = Result<ClueDetailResponse>> + Send;
+
+    /// 获取筛选标签
+    fn get_labels(&self, task_type: &str) -> impl Future<Output = Result<LabelResponse>> + Send;
+
+    /// 获取用户信息
+    fn get_user_info(&self) -> impl Future<Output = Result<UserInfoResponse>> + Send;
+}
+
+impl BeduApi for HttpClient {
+    async fn get_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListResponse> {
+        HttpClient::get_audit_task_list(self, options).await
+    }
+
+    async fn fetch_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListFetch> {
+        HttpClient::fetch_audit_task_list(self, options).await
+    }
+
+    async fn get_my_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<MyTaskListResponse> {
+        HttpClient::get_my_task_list(self, options).await
+    }
+
+    async fn claim_audit_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> Result<ClaimResponse> {
+        HttpClient::claim_audit_task(self, task_ids, task_type).await
+    }
+
+    async fn release_task(&self, task_ids: Vec<String>, task_type: &str) -> Result<ClaimResponse> {
+        HttpClient::release_task(self, task_ids, task_type).await
+    }
+
+    async fn submit_audit_result(
+        &self,
+        task_id: i64,
+        approved: bool,
+        reason: &str,
+    ) -> Result<ClaimResponse> {
+        HttpClient::submit_audit_result(self, task_id, approved, reason).await
+    }
+
+    async fn submit_produce_answer(
+        &self,
+        clue_id: i64,
+        content: &QuestionContent,
+    ) -> Result<ProduceAnswerResponse> {
+        HttpClient::submit_produce_answer(self, clue_id, content).await
+    }
+
+    async fn upload_image(&self, bytes: Vec<u8>, filename: &str) -> Result<String> {
+        HttpClient::upload_image(self, bytes, filename).await
+    }
+
+    async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        HttpClient::download_image(self, url).await
+    }
+
+    async fn get_clue_detail(&self, clue_id: i64) -> Result<ClueDetailResponse> {
+        HttpClient::get_clue_detail(self, clue_id).await
+    }
+
+    async fn get_labels(&self, task_type: &str) -> Result<LabelResponse> {
+        HttpClient::get_labels(self, task_type).await
+    }
+
+    async fn get_user_info(&self) -> Result<UserInfoResponse> {
+        HttpClient::get_user_info(self).await
+    }
+}
@@ -0,0 +1,202 @@
+//! 同步（阻塞）版本的客户端和认领器，需启用 `blocking` 特性
+//!
+//! 这些类型没有使用 `reqwest::blocking`，而是在每个实例内部创建并持有一个单线程 tokio 运行时，
+//! 调用时在该运行时上阻塞等待对应的异步实现完成，因此行为（中间件、夹具、审计日志等）与异步版本一致。
+//!
+//! # Panics
+//!
+//! 在 tokio 运行时内（如 `#[tokio::main]` 函数或异步任务中）调用这些类型的方法或销毁实例都会 panic，
+//! 只能在普通同步代码中使用；异步程序请直接使用 [`HttpClient`] 和 [`AutoClaimer`]。
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::broadcast;
+
+use crate::api::{
+    ClaimResponse, ClueDetailResponse, LabelResponse, MyTaskListResponse, ProduceAnswerResponse,
+    QuestionContent, TaskListResponse, UserInfoResponse,
+};
+use crate::client::claimer::{AutoClaimConfig, AutoClaimer, ClaimSummary, ClaimedTask};
+use crate::client::events::ClaimEvent;
+use crate::client::http::HttpClient;
+use crate::client::stats::ClaimStats;
+
+fn runtime() -> Result<Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| anyhow!(tr!(runtime_create_failed, e)))
+}
+
+/// 同步 HTTP 客户端，方法与 [`HttpClient`] 一一对应，内部持有独立的 tokio 运行时
+pub struct HttpClientBlocking {
+    inner: HttpClient,
+    runtime: Runtime,
+}
+
+impl HttpClientBlocking {
+    /// 使用默认配置创建客户端
+    pub fn try_new(base_url: String, cookie: String) -> Result<Self> {
+        Self::from_client(HttpClient::try_new(base_url, cookie)?)
+    }
+
+    /// 包装已构建好的异步客户端，用于自定义请求头、代理、中间件等
+    ///
+    /// ```rust,no_run
+    /// use bedu_claim::client::{HttpClient, HttpClientBlocking};
+    ///
+    /// let client = HttpClient::builder("https://easylearn.baidu.com", "your_cookie_here")
+    ///     .proxy("http://127.0.0.1:8080")
+    ///     .build()?;
+    /// let client = HttpClientBlocking::from_client(client)?;
+    /// let user_info = client.get_user_info()?.into_result()?;
+    /// println!("用户名: {}", user_info.user_name);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn from_client(client: HttpClient) -> Result<Self> {
+        Ok(Self {
+            inner: client,
+            runtime: runtime()?,
+        })
+    }
+
+    /// 内部的异步客户端
+    pub fn inner(&self) -> &HttpClient {
+        &self.inner
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// 获取审核任务列表
+    pub fn get_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListResponse> {
+        self.block_on(self.inner.get_audit_task_list(options))
+    }
+
+    /// 获取我的任务列表
+    pub fn get_my_task_list(&self, options: &HashMap<String, Value>) -> Result<MyTaskListResponse> {
+        self.block_on(self.inner.get_my_task_list(options))
+    }
+
+    /// 认领审核任务
+    pub fn claim_audit_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> Result<ClaimResponse> {
+        self.block_on(self.inner.claim_audit_task(task_ids, task_type))
+    }
+
+    /// 释放已认领的任务
+    pub fn release_task(&self, task_ids: Vec<String>, task_type: &str) -> Result<ClaimResponse> {
+        self.block_on(self.inner.release_task(task_ids, task_type))
+    }
+
+    /// 提交审核结论
+    pub fn submit_audit_result(
+        &self,
+        task_id: i64,
+        approved: bool,
+        reason: &str,
+    ) -> Result<ClaimResponse> {
+        self.block_on(self.inner.submit_audit_result(task_id, approved, reason))
+    }
+
+    /// 提交生产任务的题目内容
+    pub fn submit_produce_answer(
+        &self,
+        clue_id: i64,
+        content: &QuestionContent,
+    ) -> Result<ProduceAnswerResponse> {
+        self.block_on(self.inner.submit_produce_answer(clue_id, content))
+    }
+
+    /// 上传图片，返回图片地址
+    pub fn upload_image(&self, bytes: Vec<u8>, filename: &str) -> Result<String> {
+        self.block_on(self.inner.upload_image(bytes, filename))
+    }
+
+    /// 下载图片
+    pub fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        self.block_on(self.inner.download_image(url))
+    }
+
+    /// 获取线索详情
+    pub fn get_clue_detail(&self, clue_id: i64) -> Result<ClueDetailResponse> {
+        self.block_on(self.inner.get_clue_detail(clue_id))
+    }
+
+    /// 获取筛选标签
+    pub fn get_labels(&self, task_type: &str) -> Result<LabelResponse> {
+        self.block_on(self.inner.get_labels(task_type))
+    }
+
+    /// 获取用户信息
+    pub fn get_user_info(&self) -> Result<UserInfoResponse> {
+        self.block_on(self.inner.get_user_info())
+    }
+}
+
+/// 同步自动认领器，`start` 会阻塞直到认领循环结束，内部持有独立的 tokio 运行时
+pub struct AutoClaimerBlocking {
+    inner: AutoClaimer,
+    runtime: Runtime,
+}
+
+impl AutoClaimerBlocking {
+    /// 按配置创建认领器
+    pub fn new(config: AutoClaimConfig) -> Result<Self> {
+        Ok(Self {
+            inner: AutoClaimer::new(config)?,
+            runtime: runtime()?,
+        })
+    }
+
+    /// 内部的异步认领器
+    pub fn inner(&self) -> &AutoClaimer {
+        &self.inner
+    }
+
+    /// 订阅认领事件，可在其他线程中用 `blocking_recv` 接收
+    pub fn subscribe(&self) -> broadcast::Receiver<ClaimEvent> {
+        self.inner.subscribe()
+    }
+
+    /// 运行认领循环直到达到认领限制，结束后返回运行汇总
+    pub fn start(&self) -> Result<ClaimSummary> {
+        self.runtime.block_on(self.inner.start())
+    }
+
+    /// 执行单次认领尝试，返回本次认领成功的任务数
+    pub fn perform_single_claim(&self) -> Result<i32> {
+        self.runtime.block_on(self.inner.perform_single_claim())
+    }
+
+    /// 验证 Cookie 并返回用户名
+    pub fn validate_user(&self) -> Result<String> {
+        self.runtime.block_on(self.inner.validate_user())
+    }
+
+    /// 已成功认领的任务数
+    pub fn get_successful_claims(&self) -> i32 {
+        self.runtime.block_on(self.inner.get_successful_claims())
+    }
+
+    /// 已认领的任务
+    pub fn get_claimed_tasks(&self) -> Vec<ClaimedTask> {
+        self.runtime.block_on(self.inner.get_claimed_tasks())
+    }
+
+    /// 认领统计
+    pub fn get_stats(&self) -> ClaimStats {
+        self.runtime.block_on(self.inner.get_stats())
+    }
+}
@@ -0,0 +1,251 @@
+//! 幂等查询接口的短期响应缓存
+//!
+//! 筛选标签、用户信息和线索详情在短时间内很少变化，上层流程（审核、生产、多个认领器共用一个客户端）
+//! 却可能反复查询。[`CachedApi`] 包装任意 [`BeduApi`] 实现，把这些查询的成功响应在内存中缓存一段
+//! 时间；认领、提交等写操作原样转发，提交后清除对应线索的缓存。
+
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::api::{
+    ApiResponse, ClaimResponse, ClueDetailResponse, LabelResponse, MyTaskListResponse,
+    ProduceAnswerResponse, QuestionContent, TaskListResponse, UserInfoResponse,
+};
+use crate::client::{BeduApi, TaskListFetch};
+
+/// 各接口响应的缓存时长，为 0 时不缓存该接口
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheTtl {
+    /// 筛选标签，默认 10 分钟
+    pub labels: Duration,
+    /// 用户信息，默认 1 分钟
+    pub user_info: Duration,
+    /// 线索详情，默认 30 秒
+    pub clue_detail: Duration,
+}
+
+impl Default for CacheTtl {
+    fn default() -> Self {
+        Self {
+            labels: Duration::from_secs(600),
+            user_info: Duration::from_secs(60),
+            clue_detail: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 一类响应的缓存，过期的条目在下次读取时丢弃
+struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, (V, Instant)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<K, (V, Instant)>> {
+        self.entries.lock().expect("response cache mutex poisoned")
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some((value, cached_at)) if cached_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: K, value: V) {
+        if !self.ttl.is_zero() {
+            self.entries().insert(key, (value, Instant::now()));
+        }
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries().remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries().clear();
+    }
+}
+
+/// 先查缓存，未命中时调用 `fetch`；只缓存成功且完整解析的响应
+async fn cached<K, T>(
+    cache: &TtlCache<K, ApiResponse<T>>,
+    key: K,
+    fetch: impl Future<Output = Result<ApiResponse<T>>>,
+) -> Result<ApiResponse<T>>
+where
+    K: Eq + Hash,
+    ApiResponse<T>: Clone,
+{
+    if let Some(response) = cache.get(&key) {
+        return Ok(response);
+    }
+    let response = fetch.await?;
+    if response.is_ok() && response.raw.is_none() {
+        cache.insert(key, response.clone());
+    }
+    Ok(response)
+}
+
+/// 为幂等查询接口加上短期缓存的 [`BeduApi`] 包装
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{BeduApi, CacheTtl, CachedApi, HttpClient};
+/// use std::time::Duration;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let client = HttpClient::builder("https://easylearn.baidu.com", "BDUSS=...").build()?;
+/// let api = CachedApi::with_ttl(
+///     client,
+///     CacheTtl {
+///         clue_detail: Duration::from_secs(120),
+///         ..CacheTtl::default()
+///     },
+/// );
+/// // 第二次查询直接返回缓存的响应
+/// api.get_labels("audittask").await?;
+/// api.get_labels("audittask").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachedApi<A> {
+    inner: A,
+    labels: TtlCache<String, LabelResponse>,
+    user_info: TtlCache<(), UserInfoResponse>,
+    clue_details: TtlCache<i64, ClueDetailResponse>,
+}
+
+impl<A: BeduApi> CachedApi<A> {
+    /// 使用默认缓存时长
+    pub fn new(inner: A) -> Self {
+        Self::with_ttl(inner, CacheTtl::default())
+    }
+
+    pub fn with_ttl(inner: A, ttl: CacheTtl) -> Self {
+        Self {
+            inner,
+            labels: TtlCache::new(ttl.labels),
+            user_info: TtlCache::new(ttl.user_info),
+            clue_details: TtlCache::new(ttl.clue_detail),
+        }
+    }
+
+    /// 被包装的接口实现
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// 清空所有缓存，如更换 Cookie 后
+    pub fn invalidate(&self) {
+        self.labels.clear();
+        self.user_info.clear();
+        self.clue_details.clear();
+    }
+}
+
+impl<A: BeduApi> BeduApi for CachedApi<A> {
+    async fn get_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListResponse> {
+        self.inner.get_audit_task_list(options).await
+    }
+
+    async fn fetch_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListFetch> {
+        self.inner.fetch_audit_task_list(options).await
+    }
+
+    async fn get_my_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<MyTaskListResponse> {
+        self.inner.get_my_task_list(options).await
+    }
+
+    async fn claim_audit_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> Result<ClaimResponse> {
+        self.inner.claim_audit_task(task_ids, task_type).await
+    }
+
+    async fn release_task(&self, task_ids: Vec<String>, task_type: &str) -> Result<ClaimResponse> {
+        self.inner.release_task(task_ids, task_type).await
+    }
+
+    async fn submit_audit_result(
+        &self,
+        task_id: i64,
+        approved: bool,
+        reason: &str,
+    ) -> Result<ClaimResponse> {
+        // 审核结果按任务ID提交，无法对应到线索，清空全部线索详情
+        self.clue_details.clear();
+        self.inner
+            .submit_audit_result(task_id, approved, reason)
+            .await
+    }
+
+    async fn submit_produce_answer(
+        &self,
+        clue_id: i64,
+        content: &QuestionContent,
+    ) -> Result<ProduceAnswerResponse> {
+        self.clue_details.remove(&clue_id);
+        self.inner.submit_produce_answer(clue_id, content).await
+    }
+
+    async fn upload_image(&self, bytes: Vec<u8>, filename: &str) -> Result<String> {
+        self.inner.upload_image(bytes, filename).await
+    }
+
+    async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        self.inner.download_image(url).await
+    }
+
+    async fn get_clue_detail(&self, clue_id: i64) -> Result<ClueDetailResponse> {
+        cached(
+            &self.clue_details,
+            clue_id,
+            self.inner.get_clue_detail(clue_id),
+        )
+        .await
+    }
+
+    async fn get_labels(&self, task_type: &str) -> Result<LabelResponse> {
+        cached(
+            &self.labels,
+            task_type.to_string(),
+            self.inner.get_labels(task_type),
+        )
+        .await
+    }
+
+    async fn get_user_info(&self) -> Result<UserInfoResponse> {
+        cached(&self.user_info, (), self.inner.get_user_info()).await
+    }
+}
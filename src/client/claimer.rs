@@ -1,14 +1,32 @@
+use crate::tr;
 use anyhow::{Result, anyhow};
-use log::{error, info, warn};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Offset, Utc};
+use futures::{Stream, StreamExt, future, stream};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::Mutex;
-use tokio::time::{interval, sleep};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore, broadcast, mpsc, watch};
+use tokio::time::{self, interval, sleep};
+use tracing::{Span, debug, error, field, info, instrument, warn};
 
-use crate::api::TaskItem;
+use crate::api::{ClaimFailedTask, ClaimResponse, TaskItem, UserInfoResponse};
 use crate::client::HttpClient;
+use crate::client::bedu_api::BeduApi;
+use crate::client::correlation::{new_correlation_id, with_correlation_id};
+use crate::client::events::ClaimEvent;
+use crate::client::fixture::FixtureMode;
+use crate::client::http::PoolOptions;
+use crate::client::rate_limit::RateLimiter;
+use crate::client::schedule::{ActiveHours, CronSchedule, WorkCalendar};
+use crate::client::state::ClaimState;
+use crate::client::stats::{ClaimStats, StatsRecorder};
+use crate::client::token::Token;
+use crate::client::user_agent::UserAgentPool;
+use crate::error::{BeduError, NOT_LOGGED_IN_ERRNO};
 
 /// 自动认领配置
 #[derive(Clone)]
@@ -21,172 +39,1556 @@ pub struct AutoClaimConfig {
     pub step_id: i32,
     pub subject_id: i32,
     pub clue_type_id: i32,
+    /// 状态文件路径，设置后认领计数和去重信息在重启后保留
+    pub state_file: Option<PathBuf>,
+    /// 认领审计日志路径（JSONL，只追加）
+    pub audit_log: Option<PathBuf>,
+    /// 待完成任务数上限，认领前查询我的任务，达到上限时跳过本次认领
+    pub max_pending: Option<i32>,
+    /// 遇到 10003 后进入休眠，按此间隔（秒）复查待完成任务，清空后自动恢复认领；为 None 时不休眠
+    pub pending_recheck_interval: Option<f64>,
+    /// User-Agent 池，为 None 时使用默认的浏览器 User-Agent
+    pub user_agents: Option<UserAgentPool>,
+    /// 录制或回放接口请求，用于离线回归测试
+    pub fixtures: Option<FixtureMode>,
+    /// 原始响应转储目录，用于排查解析失败
+    pub dump_responses: Option<PathBuf>,
+    /// 每个请求附带的动态令牌，见 [`Token`]
+    pub tokens: Vec<Token>,
+    /// 备用服务器地址，主地址连接失败时依次切换，见 [`failover`](crate::client::failover)
+    pub fallback_urls: Vec<String>,
+    /// 不经过 DNS 的主机名解析，见 [`HttpClientBuilder::resolve`](crate::client::HttpClientBuilder::resolve)
+    pub resolve: Vec<(String, IpAddr)>,
+    /// 连接池与 TCP 保活设置；空闲超时短于两倍轮询间隔时自动延长，使轮询始终复用同一个连接
+    pub pool: PoolOptions,
+    /// 是否请求压缩的响应并自动解压（需 `compression` 特性）
+    #[cfg(feature = "compression")]
+    pub compression: bool,
+    /// 最长运行时间，到达后不论认领数多少都停止，为 None 时不限制
+    ///
+    /// 进行中的认领尝试会先完成，不会在请求中途被打断。
+    pub max_runtime: Option<Duration>,
+    /// 本次运行最多进行的轮询尝试次数，用尽后即使未达到认领限制也停止，为 None 时不限制
+    pub max_attempts: Option<u32>,
+    /// 每日认领上限，当天达到后暂停认领，到次日 0 点自动恢复，为 None 时不限制
+    ///
+    /// 每日认领数记录在认领状态中，配合 `state_file` 可跨重启累计。
+    pub daily_limit: Option<i32>,
+    /// 任意连续一小时内最多认领成功的任务数，达到后暂停到窗口内最早的认领满一小时，为 None 时不限制
+    ///
+    /// 与认领限制、每日上限相互独立，用于均匀分配认领节奏。
+    pub max_claims_per_hour: Option<u32>,
+    /// 工作时段，时段外暂停认领（不退出），到下一个时段开始时自动恢复，为 None 时全天认领
+    pub active_hours: Option<ActiveHours>,
+    /// 定时认领，只在 cron 表达式触发后的一段时间内认领，其余时间暂停，为 None 时持续认领
+    ///
+    /// 与 `active_hours` 同时设置时，两者都满足才认领。
+    pub cron_schedule: Option<CronSchedule>,
+    /// 工作日历，非工作日（不在每周工作日内或为节假日）全天暂停认领，为 None 时每天都认领
+    pub work_calendar: Option<WorkCalendar>,
+    /// 计算每日认领数、按小时的认领统计、工作日历、工作时段和定时认领时使用的时区，为 None 时使用系统本地时区
+    pub timezone: Option<FixedOffset>,
+    /// 连续失败的尝试达到此次数时停止认领并返回错误，为 None 时不限制
+    pub max_consecutive_errors: Option<u32>,
+    /// 请求限速器，多个认领器共用同一个限速器时合计请求速率不超过上限
+    pub rate_limiter: Option<RateLimiter>,
+    /// 只监视不认领：轮询任务池，发现新的符合条件的任务时记录日志并发出
+    /// [`ClaimEvent::TasksSpotted`] 事件，可通过 [`notify`](crate::client::notify) 转发为通知
+    ///
+    /// 此模式下认领限制不会达到，需配合 `max_runtime` 或 `max_attempts` 结束运行。
+    pub watch_only: bool,
+    /// 并发认领的工作者数量，大于 1 时启用工作池模式，见 [`AutoClaimer::start`]
+    pub claim_workers: usize,
 }
 
-/// 自动认领器
-pub struct AutoClaimer {
+impl Default for AutoClaimConfig {
+    fn default() -> Self {
+        Self {
+            server_base_url: "https://easylearn.baidu.com".to_string(),
+            cookie: String::new(),
+            task_type: "audittask".to_string(),
+            claim_limit: 10,
+            interval: 3.0,
+            step_id: 1,
+            subject_id: 2,
+            clue_type_id: 1,
+            state_file: None,
+            audit_log: None,
+            max_pending: None,
+            pending_recheck_interval: Some(30.0),
+            user_agents: None,
+            fixtures: None,
+            dump_responses: None,
+            tokens: Vec::new(),
+            fallback_urls: Vec::new(),
+            resolve: Vec::new(),
+            pool: PoolOptions::default(),
+            #[cfg(feature = "compression")]
+            compression: true,
+            max_runtime: None,
+            max_attempts: None,
+            daily_limit: None,
+            max_claims_per_hour: None,
+            active_hours: None,
+            cron_schedule: None,
+            work_calendar: None,
+            timezone: None,
+            max_consecutive_errors: None,
+            rate_limiter: None,
+            watch_only: false,
+            claim_workers: 1,
+        }
+    }
+}
+
+/// 单次失败记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimFailure {
+    /// 发生失败的尝试序号
+    pub attempt: i32,
+    /// 涉及的任务ID（获取列表阶段失败时为空）
+    pub task_ids: Vec<String>,
+    /// 接口返回的错误码，网络或解析错误时为 None
+    pub errno: Option<i32>,
+    pub message: String,
+}
+
+/// 认领成功的任务记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimedTask {
+    pub task: TaskItem,
+    pub task_type: String,
+    /// 认领成功的尝试序号
+    pub attempt: i32,
+    pub claimed_at: DateTime<Local>,
+}
+
+/// 发现后未被自己认领、从任务池中消失（被他人认领）的任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainedTask {
+    pub task: TaskItem,
+    /// 从出现在任务池中到消失的时长，精度为一个轮询间隔
+    pub lifetime_ms: u64,
+}
+
+/// 单次认领请求的逐任务结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClaimOutcome {
+    /// 认领成功的任务ID
+    pub succeeded: Vec<String>,
+    /// 认领失败的任务及原因
+    pub failed: Vec<ClaimFailedTask>,
+}
+
+impl ClaimOutcome {
+    /// 根据接口响应确定每个任务的认领结果
+    ///
+    /// 接口返回成功ID时以其为准；只返回失败列表时其余视为成功；
+    /// 只返回成功数量时按请求顺序取前若干个；都没有时视为全部成功。
+    pub fn from_response(task_ids: &[String], response: &ClaimResponse) -> Self {
+        if response.errno != 0 {
+            return Self {
+                succeeded: Vec::new(),
+                failed: task_ids
+                    .iter()
+                    .map(|id| ClaimFailedTask {
+                        id: id.clone(),
+                        reason: response.errmsg.clone(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            };
+        }
+
+        let data = response.data.clone().unwrap_or_default();
+        let succeeded: Vec<String> = if !data.success_ids.is_empty() {
+            data.success_ids
+        } else if !data.fail_list.is_empty() {
+            task_ids
+                .iter()
+                .filter(|id| !data.fail_list.iter().any(|f| &f.id == *id))
+                .cloned()
+                .collect()
+        } else if let Some(success) = data.success {
+            task_ids
+                .iter()
+                .take(success.max(0) as usize)
+                .cloned()
+                .collect()
+        } else {
+            task_ids.to_vec()
+        };
+
+        Self {
+            succeeded,
+            failed: data.fail_list,
+        }
+    }
+
+    /// 认领成功的数量
+    pub fn count(&self) -> i32 {
+        self.succeeded.len() as i32
+    }
+}
+
+/// 自动认领运行结果汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimSummary {
+    pub total_claims: i32,
+    pub attempts: i32,
+    pub duration: Duration,
+    pub claimed_task_ids: Vec<String>,
+    pub failures: Vec<ClaimFailure>,
+}
+
+/// 运行中可以修改的认领参数，初始值取自 [`AutoClaimConfig`] 的同名字段
+///
+/// 通过 [`AutoClaimer::update_settings`] 修改，下一次轮询起生效。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ClaimSettings {
+    pub interval: f64,
+    pub subject_id: i32,
+    pub step_id: i32,
+    pub clue_type_id: i32,
+    pub daily_limit: Option<i32>,
+    pub max_claims_per_hour: Option<u32>,
+    pub max_pending: Option<i32>,
+}
+
+impl From<&AutoClaimConfig> for ClaimSettings {
+    fn from(config: &AutoClaimConfig) -> Self {
+        Self {
+            interval: config.interval,
+            subject_id: config.subject_id,
+            step_id: config.step_id,
+            clue_type_id: config.clue_type_id,
+            daily_limit: config.daily_limit,
+            max_claims_per_hour: config.max_claims_per_hour,
+            max_pending: config.max_pending,
+        }
+    }
+}
+
+/// 认领器的运行状态快照
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimerStatus {
+    /// 本次运行的认领成功数
+    pub claimed: i32,
+    /// 当前认领限制
+    pub claim_limit: i32,
+    /// 今日认领数（含状态文件中恢复的）
+    pub claimed_today: i32,
+    pub attempts: i32,
+    /// 是否被手动暂停
+    pub paused: bool,
+    /// 是否因待完成任务过多而休眠
+    pub dormant: bool,
+    pub stats: ClaimStats,
+}
+
+/// 自动认领器，默认通过 [`HttpClient`] 访问服务器
+pub struct AutoClaimer<A = HttpClient> {
     config: AutoClaimConfig,
-    client: Arc<HttpClient>,
+    client: Arc<A>,
     successful_claims: Arc<Mutex<i32>>,
     attempt_count: Arc<Mutex<i32>>,
+    /// 从状态文件恢复的认领数和尝试次数，只用于认领限制和尝试序号，统计只计算本次运行的
+    restored: Arc<Mutex<(i32, i32)>>,
+    claimed_task_ids: Arc<Mutex<Vec<String>>>,
+    claimed_tasks: Arc<Mutex<Vec<ClaimedTask>>>,
+    failures: Arc<Mutex<Vec<ClaimFailure>>>,
+    stats: Arc<Mutex<StatsRecorder>>,
+    state: Arc<Mutex<ClaimState>>,
+    /// 因待完成任务过多（10003）而暂停认领
+    dormant: Arc<Mutex<bool>>,
+    /// 自上次成功以来连续失败的次数
+    consecutive_errors: Arc<Mutex<u32>>,
+    /// 最近一小时内每个认领成功任务的时间，用于 `max_claims_per_hour`
+    recent_claims: Arc<Mutex<VecDeque<Instant>>>,
+    /// 只监视模式下已通知过的任务
+    spotted: Arc<Mutex<HashSet<i64>>>,
+    /// 上次获取的任务池，用于找出新出现的任务
+    pool_snapshot: Arc<Mutex<Option<PoolSnapshot>>>,
+    /// 候选任务所在的任务列表返回的时间，用于统计从获取列表到认领完成的耗时与成功率的关系
+    listed_at: Arc<Mutex<HashMap<String, Instant>>>,
+    /// 是否已发出过 Cookie 过期事件
+    cookie_expired: Arc<Mutex<bool>>,
+    /// 当前认领限制，初始为配置中的 `claim_limit`，可在运行中修改
+    claim_limit: Arc<Mutex<i32>>,
+    /// 是否被手动暂停
+    paused: Arc<watch::Sender<bool>>,
+    /// 运行中可修改的认领参数
+    settings: Arc<watch::Sender<ClaimSettings>>,
+    events: broadcast::Sender<ClaimEvent>,
+    /// [`AutoClaimer::claim_stream`] 的发送端，消费者处理不过来时认领循环在此等待
+    task_sink: Arc<Mutex<Option<mpsc::Sender<ClaimedTask>>>>,
 }
 
 impl AutoClaimer {
-    /// 创建新的自动认领器实例
-    pub fn new(config: AutoClaimConfig) -> Self {
-        let client = Arc::new(HttpClient::new(
-            config.server_base_url.clone(),
-            config.cookie.clone(),
-        ));
+    /// 创建新的自动认领器实例，HTTP客户端构建失败时返回错误
+    pub fn new(config: AutoClaimConfig) -> Result<Self, BeduError> {
+        let mut builder = HttpClient::builder(&config.server_base_url, &config.cookie);
+        if let Some(path) = &config.audit_log {
+            builder = builder.audit_log(path);
+        }
+        if let Some(pool) = &config.user_agents {
+            builder = builder.user_agent_pool(pool.clone());
+        }
+        if let Some(mode) = &config.fixtures {
+            builder = builder.fixtures(mode.clone());
+        }
+        if let Some(dir) = &config.dump_responses {
+            builder = builder.dump_responses(dir);
+        }
+        if let Some(limiter) = &config.rate_limiter {
+            builder = builder.middleware(limiter.clone());
+        }
+        for token in &config.tokens {
+            builder = builder.token(token.clone());
+        }
+        for url in &config.fallback_urls {
+            builder = builder.fallback_url(url);
+        }
+        for (host, ip) in &config.resolve {
+            builder = builder.resolve(host, *ip);
+        }
+        // 空闲超时短于轮询间隔时，每次轮询都要重新建立连接和 TLS 握手，在竞争中白白落后
+        let mut pool = config.pool;
+        if let Some(idle_timeout) = pool.idle_timeout
+            && let Ok(min) = Duration::try_from_secs_f64(config.interval * 2.0)
+        {
+            pool.idle_timeout = Some(idle_timeout.max(min));
+        }
+        builder = builder.pool(pool);
+        #[cfg(feature = "compression")]
+        {
+            builder = builder.compression(config.compression);
+        }
 
+        Ok(Self::with_client(config, Arc::new(builder.build()?)))
+    }
+}
+
+impl<A: BeduApi> AutoClaimer<A> {
+    /// 使用指定的接口实现创建自动认领器，如测试用的假服务
+    ///
+    /// 配置中的 `server_base_url`、`cookie`、`audit_log`、`user_agents`、`fixtures`、`dump_responses`、`tokens`、`fallback_urls`、`resolve`、`pool` 和 `rate_limiter` 此时不生效。
+    pub fn with_client(config: AutoClaimConfig, client: Arc<A>) -> Self {
         Self {
+            claim_limit: Arc::new(Mutex::new(config.claim_limit)),
+            settings: Arc::new(watch::channel(ClaimSettings::from(&config)).0),
             config,
             client,
             successful_claims: Arc::new(Mutex::new(0)),
             attempt_count: Arc::new(Mutex::new(0)),
+            restored: Arc::new(Mutex::new((0, 0))),
+            claimed_task_ids: Arc::new(Mutex::new(Vec::new())),
+            claimed_tasks: Arc::new(Mutex::new(Vec::new())),
+            failures: Arc::new(Mutex::new(Vec::new())),
+            stats: Arc::new(Mutex::new(StatsRecorder::default())),
+            state: Arc::new(Mutex::new(ClaimState::default())),
+            dormant: Arc::new(Mutex::new(false)),
+            consecutive_errors: Arc::new(Mutex::new(0)),
+            recent_claims: Arc::new(Mutex::new(VecDeque::new())),
+            spotted: Arc::new(Mutex::new(HashSet::new())),
+            pool_snapshot: Arc::new(Mutex::new(None)),
+            listed_at: Arc::new(Mutex::new(HashMap::new())),
+            cookie_expired: Arc::new(Mutex::new(false)),
+            paused: Arc::new(watch::channel(false).0),
+            events: broadcast::channel(1024).0,
+            task_sink: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// 获取当前成功认领的数量
+    /// 获取当前成功认领的数量，含从状态文件恢复的，用于判断认领限制
     #[allow(dead_code)]
     pub async fn get_successful_claims(&self) -> i32 {
         *self.successful_claims.lock().await
     }
 
-    /// 获取尝试次数
+    /// 获取尝试次数，含从状态文件恢复的
     #[allow(dead_code)]
     pub async fn get_attempt_count(&self) -> i32 {
         *self.attempt_count.lock().await
     }
 
+    /// 当前的认领限制
+    pub async fn claim_limit(&self) -> i32 {
+        *self.claim_limit.lock().await
+    }
+
+    /// 在运行中修改认领限制，低于已认领数时认领循环随即结束
+    pub async fn set_claim_limit(&self, limit: i32) {
+        let mut claim_limit = self.claim_limit.lock().await;
+        if *claim_limit == limit {
+            return;
+        }
+        info!(
+            old = *claim_limit,
+            limit,
+            "{}",
+            tr!(limit_changed, *claim_limit, limit)
+        );
+        *claim_limit = limit;
+        self.emit(ClaimEvent::LimitChanged { limit });
+    }
+
+    /// 当前的认领参数
+    pub fn settings(&self) -> ClaimSettings {
+        *self.settings.borrow()
+    }
+
+    /// 在运行中修改轮询间隔、筛选项和各项上限，下一次轮询起生效
+    ///
+    /// 轮询间隔不是有限值或小于 1 毫秒时返回错误，不应用任何修改。
+    pub fn update_settings(&self, settings: ClaimSettings) -> Result<(), BeduError> {
+        if !settings.interval.is_finite() || settings.interval < MIN_INTERVAL.as_secs_f64() {
+            return Err(BeduError::Config(tr!(interval_too_small)));
+        }
+        if self.settings.send_replace(settings) == settings {
+            return Ok(());
+        }
+        info!(
+            interval = settings.interval,
+            subject = settings.subject_id,
+            step = settings.step_id,
+            clue_type = settings.clue_type_id,
+            daily_limit = settings.daily_limit,
+            max_claims_per_hour = settings.max_claims_per_hour,
+            max_pending = settings.max_pending,
+            "{}",
+            tr!(
+                settings_changed,
+                settings.interval,
+                settings.subject_id,
+                settings.step_id,
+                settings.clue_type_id
+            )
+        );
+        self.emit(ClaimEvent::SettingsChanged { settings });
+        Ok(())
+    }
+
+    /// 请求重新加载配置：发出 [`ClaimEvent::ReloadRequested`] 事件，由订阅方读取配置后调用
+    /// [`AutoClaimer::update_settings`] 等方法应用
+    pub fn request_reload(&self) {
+        info!("{}", tr!(reload_requested));
+        self.emit(ClaimEvent::ReloadRequested);
+    }
+
+    /// 手动暂停认领，正在进行的认领请求不受影响，调用 [`AutoClaimer::resume`] 后恢复
+    pub fn pause(&self) {
+        if !self.paused.send_replace(true) {
+            info!("{}", tr!(claiming_paused));
+            self.emit(ClaimEvent::Paused);
+        }
+    }
+
+    /// 恢复手动暂停的认领
+    pub fn resume(&self) {
+        if self.paused.send_replace(false) {
+            info!("{}", tr!(claiming_resumed));
+            self.emit(ClaimEvent::Unpaused);
+        }
+    }
+
+    /// 是否处于手动暂停状态
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// 获取已成功认领的任务ID
+    pub async fn get_claimed_task_ids(&self) -> Vec<String> {
+        self.claimed_task_ids.lock().await.clone()
+    }
+
+    /// 获取内部使用的接口客户端
+    pub fn client(&self) -> Arc<A> {
+        self.client.clone()
+    }
+
+    /// 订阅认领事件
+    pub fn subscribe(&self) -> broadcast::Receiver<ClaimEvent> {
+        self.events.subscribe()
+    }
+
+    /// 运行认领循环，并以流的形式逐个返回认领成功的任务
+    ///
+    /// 与 [`AutoClaimer::subscribe`] 不同，流带有背压：上一个任务还没被取走时认领循环会暂停等待，
+    /// 因此可以在消费端慢慢获取详情、发送通知或入队。认领循环结束（达到认领限制或出错）后流随之结束，
+    /// 出错时错误会记录到日志中。丢弃流即停止认领。
+    ///
+    /// ```rust,no_run
+    /// use bedu_claim::client::{AutoClaimConfig, AutoClaimer};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run(config: AutoClaimConfig) -> anyhow::Result<()> {
+    /// let claimer = AutoClaimer::new(config)?;
+    /// let mut tasks = std::pin::pin!(claimer.claim_stream());
+    /// while let Some(claimed) = tasks.next().await {
+    ///     println!("认领成功: {}", claimed.task.task_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn claim_stream(&self) -> impl Stream<Item = ClaimedTask> + '_ {
+        let (sender, receiver) = mpsc::channel(1);
+        let driver = stream::once(async move {
+            *self.task_sink.lock().await = Some(sender);
+            if let Err(e) = self.start().await {
+                error!(error = %e, "{}", tr!(auto_claim_crashed, e));
+            }
+            self.task_sink.lock().await.take();
+            None
+        });
+        let tasks = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|task| (Some(task), receiver))
+        });
+
+        stream::select(tasks, driver).filter_map(future::ready)
+    }
+
+    /// 发送事件，无订阅者时直接丢弃
+    fn emit(&self, event: ClaimEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// 从状态文件恢复认领计数和去重信息，未配置状态文件时不做任何事
+    pub async fn load_state(&self) -> Result<()> {
+        let Some(path) = &self.config.state_file else {
+            return Ok(());
+        };
+
+        let state = ClaimState::load(path)?;
+        info!(
+            path = %path.display(),
+            claimed = state.successful_claims,
+            attempts = state.attempt_count,
+            "{}",
+            tr!(
+                state_restored,
+                state.successful_claims,
+                state.attempt_count,
+                state.claimed_ids.len()
+            )
+        );
+
+        *self.successful_claims.lock().await = state.successful_claims;
+        *self.attempt_count.lock().await = state.attempt_count;
+        *self.restored.lock().await = (state.successful_claims, state.attempt_count);
+        *self.state.lock().await = state;
+
+        Ok(())
+    }
+
+    /// 将当前认领状态写入状态文件，未配置状态文件时不做任何事
+    pub async fn save_state(&self) -> Result<()> {
+        let Some(path) = &self.config.state_file else {
+            return Ok(());
+        };
+
+        let mut state = self.state.lock().await;
+        state.successful_claims = *self.successful_claims.lock().await;
+        state.attempt_count = *self.attempt_count.lock().await;
+        state.save(path)
+    }
+
+    /// 获取持久化的认领状态
+    pub async fn get_state(&self) -> ClaimState {
+        self.state.lock().await.clone()
+    }
+
+    /// 按配置时区的当前时间
+    fn now(&self) -> DateTime<FixedOffset> {
+        let timezone = self
+            .config
+            .timezone
+            .unwrap_or_else(|| Local::now().offset().fix());
+        Utc::now().with_timezone(&timezone)
+    }
+
+    /// 按配置时区的当天日期
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+
+    /// 今天已认领成功的任务数
+    pub async fn claims_today(&self) -> i32 {
+        self.state.lock().await.claims_on(self.today())
+    }
+
+    /// 今天还可以认领的任务数，未设置每日上限时为 None
+    async fn daily_remaining(&self) -> Option<i32> {
+        let daily_limit = self.settings().daily_limit?;
+        Some(daily_limit - self.claims_today().await)
+    }
+
+    /// 最近一小时内认领成功的任务数，顺带清理超出窗口的记录
+    async fn claims_last_hour(&self) -> (i32, Option<Instant>) {
+        let mut recent_claims = self.recent_claims.lock().await;
+        while recent_claims
+            .front()
+            .is_some_and(|claimed_at| claimed_at.elapsed() >= HOUR)
+        {
+            recent_claims.pop_front();
+        }
+        (recent_claims.len() as i32, recent_claims.front().copied())
+    }
+
+    /// 当前还可以认领的任务数，取每日和每小时额度中较小者，均未设置时为 None
+    async fn claim_allowance(&self) -> Option<i32> {
+        let hourly = match self.settings().max_claims_per_hour {
+            Some(max_claims) => Some(max_claims as i32 - self.claims_last_hour().await.0),
+            None => None,
+        };
+        match (self.daily_remaining().await, hourly) {
+            (Some(daily), Some(hourly)) => Some(daily.min(hourly)),
+            (daily, hourly) => daily.or(hourly),
+        }
+    }
+
+    /// 是否需要暂停认领：被手动暂停、不在工作时段内，或每日、每小时额度已用完
+    async fn should_pause(&self) -> bool {
+        self.is_paused()
+            || !self.in_schedule()
+            || self.claim_allowance().await.is_some_and(|n| n <= 0)
+    }
+
+    /// 等待到工作时段内且额度有空余，可以认领时立即返回
+    async fn wait_until_allowed(&self) {
+        while self.should_pause().await {
+            self.wait_for_resume().await;
+            self.wait_for_schedule().await;
+            self.wait_for_daily_reset().await;
+            self.wait_for_hourly_window().await;
+        }
+    }
+
+    /// 手动暂停时等待到恢复
+    async fn wait_for_resume(&self) {
+        let mut paused = self.paused.subscribe();
+        let _ = paused.wait_for(|paused| !paused).await;
+    }
+
+    /// 当前是否为工作日，且在工作时段和定时认领时段内，未设置时视为满足
+    fn in_schedule(&self) -> bool {
+        let now = self.now();
+        self.config
+            .work_calendar
+            .as_ref()
+            .is_none_or(|calendar| calendar.is_workday(now.date_naive()))
+            && self
+                .config
+                .active_hours
+                .as_ref()
+                .is_none_or(|active_hours| active_hours.contains(now.time()))
+            && self
+                .config
+                .cron_schedule
+                .as_ref()
+                .is_none_or(|schedule| schedule.contains(now))
+    }
+
+    /// 不是工作日或不在工作时段、定时认领时段内时，等待到下一个时段开始
+    async fn wait_for_schedule(&self) {
+        while !self.in_schedule() {
+            let now = self.now();
+            let resume_at = match (&self.config.work_calendar, &self.config.active_hours) {
+                (Some(calendar), _) if !calendar.is_workday(now.date_naive()) => {
+                    let Some(resume_at) = calendar.next_workday_start(now) else {
+                        warn!("{}", tr!(no_workday_within_year));
+                        sleep(Duration::from_secs(86400)).await;
+                        continue;
+                    };
+                    info!(
+                        date = %now.date_naive(),
+                        resume_at = %resume_at,
+                        "{}",
+                        tr!(
+                            non_working_day,
+                            now.date_naive(),
+                            resume_at.format("%Y-%m-%d %H:%M:%S %:z")
+                        )
+                    );
+                    self.emit(ClaimEvent::NonWorkingDay { resume_at });
+                    resume_at
+                }
+                (_, Some(active_hours)) if !active_hours.contains(now.time()) => {
+                    let resume_at = active_hours.next_start(now);
+                    info!(
+                        active_hours = %active_hours,
+                        resume_at = %resume_at,
+                        "{}",
+                        tr!(
+                            outside_active_hours,
+                            active_hours,
+                            resume_at.format("%Y-%m-%d %H:%M:%S %:z")
+                        )
+                    );
+                    self.emit(ClaimEvent::OutsideActiveHours { resume_at });
+                    resume_at
+                }
+                _ => {
+                    let Some(schedule) = &self.config.cron_schedule else {
+                        return;
+                    };
+                    let resume_at = schedule.next_start(now);
+                    info!(
+                        schedule = %schedule,
+                        resume_at = %resume_at,
+                        "{}",
+                        tr!(awaiting_cron, schedule, resume_at.format("%Y-%m-%d %H:%M:%S %:z"))
+                    );
+                    self.emit(ClaimEvent::AwaitingCronTrigger { resume_at });
+                    resume_at
+                }
+            };
+
+            // 多等一秒，避免因时钟误差在时段开始前醒来
+            let wait = (resume_at - now).to_std().unwrap_or_default() + Duration::from_secs(1);
+            sleep(wait).await;
+        }
+    }
+
+    /// 最近一小时的认领数达到每小时上限时，等待到窗口内最早的认领满一小时
+    async fn wait_for_hourly_window(&self) {
+        loop {
+            let Some(max_claims) = self.settings().max_claims_per_hour else {
+                return;
+            };
+            let (claimed_last_hour, oldest) = self.claims_last_hour().await;
+            let Some(oldest) = oldest.filter(|_| claimed_last_hour >= max_claims as i32) else {
+                return;
+            };
+
+            let wait = HOUR.saturating_sub(oldest.elapsed());
+            let resume_at = self.now() + wait;
+            info!(
+                outcome = "limited",
+                claimed_last_hour,
+                max_claims_per_hour = max_claims,
+                resume_at = %resume_at,
+                "{}",
+                tr!(
+                    hourly_limit_reached,
+                    claimed_last_hour,
+                    max_claims,
+                    resume_at.format("%H:%M:%S")
+                )
+            );
+            self.emit(ClaimEvent::HourlyLimitReached {
+                claimed_last_hour,
+                max_claims_per_hour: max_claims,
+                resume_at,
+            });
+
+            self.sleep_unless_reconfigured(wait).await;
+        }
+    }
+
+    /// 今天的认领数已达到每日上限时，等待到次日 0 点
+    ///
+    /// 未达到上限时立即返回。
+    async fn wait_for_daily_reset(&self) {
+        loop {
+            let Some(daily_limit) = self.settings().daily_limit else {
+                return;
+            };
+            let claimed_today = self.claims_today().await;
+            if claimed_today < daily_limit {
+                return;
+            }
+
+            let now = self.now();
+            let resume_at = now
+                .date_naive()
+                .succ_opt()
+                .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 0))
+                .and_then(|midnight| midnight.and_local_timezone(*now.offset()).single())
+                .unwrap_or(now);
+            info!(
+                outcome = "limited",
+                claimed_today,
+                daily_limit,
+                resume_at = %resume_at,
+                "{}",
+                tr!(
+                    daily_limit_reached,
+                    claimed_today,
+                    daily_limit,
+                    resume_at.format("%Y-%m-%d %H:%M:%S %:z")
+                )
+            );
+            self.emit(ClaimEvent::DailyLimitReached {
+                claimed_today,
+                daily_limit,
+                resume_at,
+            });
+
+            // 多等一秒，避免因时钟误差在 0 点前醒来
+            let wait = (resume_at - now).to_std().unwrap_or_default() + Duration::from_secs(1);
+            self.sleep_unless_reconfigured(wait).await;
+        }
+    }
+
+    /// 等待指定时长，期间认领参数被修改时提前返回，以便按新的上限重新判断
+    async fn sleep_unless_reconfigured(&self, wait: Duration) {
+        let mut settings = self.settings.subscribe();
+        tokio::select! {
+            _ = sleep(wait) => {}
+            _ = settings.changed() => {}
+        }
+    }
+
+    /// 认领时使用的任务ID：生产任务用线索ID，审核任务用任务ID
+    fn task_key(&self, task: &TaskItem) -> i64 {
+        if self.config.task_type == "producetask" {
+            task.clue_id
+        } else {
+            task.task_id
+        }
+    }
+
+    /// 获取本次运行的认领统计信息，不含从状态文件恢复的认领数和尝试次数
+    pub async fn get_stats(&self) -> ClaimStats {
+        let (restored_claims, restored_attempts) = *self.restored.lock().await;
+        let attempts = *self.attempt_count.lock().await - restored_attempts;
+        let total_claims = *self.successful_claims.lock().await - restored_claims;
+        self.stats.lock().await.snapshot(attempts, total_claims)
+    }
+
+    /// 获取本次运行认领成功的任务记录
+    pub async fn get_claimed_tasks(&self) -> Vec<ClaimedTask> {
+        self.claimed_tasks.lock().await.clone()
+    }
+
+    /// 当前运行状态，运行期间可在其他任务中调用
+    pub async fn status(&self) -> ClaimerStatus {
+        let stats = self.get_stats().await;
+        ClaimerStatus {
+            claimed: stats.total_claims,
+            claim_limit: self.claim_limit().await,
+            claimed_today: self.claims_today().await,
+            attempts: stats.attempts,
+            paused: self.is_paused(),
+            dormant: self.is_dormant().await,
+            stats,
+        }
+    }
+
+    /// 记录一次失败
+    async fn record_failure(&self, task_ids: Vec<String>, errno: Option<i32>, message: String) {
+        if errno.is_none() {
+            self.stats.lock().await.record_request_error();
+        }
+
+        let attempt = *self.attempt_count.lock().await;
+        match errno {
+            Some(errno) => self.emit(ClaimEvent::ClaimFailed {
+                attempt,
+                task_ids: task_ids.clone(),
+                errno,
+                errmsg: message.clone(),
+            }),
+            None => self.emit(ClaimEvent::Error {
+                attempt,
+                message: message.clone(),
+            }),
+        }
+        self.failures.lock().await.push(ClaimFailure {
+            attempt,
+            task_ids,
+            errno,
+            message,
+        });
+        *self.consecutive_errors.lock().await += 1;
+    }
+
+    /// 连续失败次数达到 `max_consecutive_errors` 时返回汇总错误
+    async fn check_error_budget(&self) -> Result<()> {
+        let Some(max_errors) = self.config.max_consecutive_errors else {
+            return Ok(());
+        };
+
+        let consecutive = *self.consecutive_errors.lock().await;
+        if consecutive < max_errors {
+            return Ok(());
+        }
+
+        let last_error = self
+            .failures
+            .lock()
+            .await
+            .last()
+            .map(|failure| failure.message.clone())
+            .unwrap_or_default();
+        Err(anyhow!(tr!(
+            error_budget_exhausted,
+            consecutive,
+            last_error
+        )))
+    }
+
+    /// 达到认领限制：记录日志并发出事件
+    async fn limit_reached(&self) {
+        info!("{}", tr!(claim_limit_reached_stopping));
+        self.emit(ClaimEvent::LimitReached {
+            total: *self.successful_claims.lock().await,
+            limit: self.claim_limit().await,
+        });
+    }
+
+    /// 接口返回未登录时发出 Cookie 过期事件，每次运行只发出一次
+    async fn check_cookie_expired(&self, errno: Option<i32>, message: &str) {
+        if errno != Some(NOT_LOGGED_IN_ERRNO) {
+            return;
+        }
+
+        let mut cookie_expired = self.cookie_expired.lock().await;
+        if *cookie_expired {
+            return;
+        }
+        *cookie_expired = true;
+        warn!(errno, "{}", tr!(cookie_maybe_expired, message));
+        self.emit(ClaimEvent::CookieExpired {
+            message: message.to_string(),
+        });
+    }
+
     /// 验证Cookie和用户信息
+    #[instrument(name = "claimer.validate_user", skip_all)]
     pub async fn validate_user(&self) -> Result<String> {
-        match self.client.get_user_info().await {
-            Ok(user_info) => {
-                if user_info.errno == 0 {
-                    Ok(user_info.data.user_name)
-                } else {
-                    Err(anyhow!("用户验证失败: {}", user_info.errmsg))
+        let started = Instant::now();
+        let user_info = self.client.get_user_info().await;
+        self.stats.lock().await.record_user_info(started.elapsed());
+        self.user_name(user_info).await
+    }
+
+    /// 与 [`AutoClaimer::validate_user`] 相同，但不计入用户信息接口的延迟统计，
+    /// 供存活探针等外部检查使用
+    #[cfg(feature = "control-server")]
+    pub(crate) async fn check_user(&self) -> Result<String> {
+        let user_info = self.client.get_user_info().await;
+        self.user_name(user_info).await
+    }
+
+    /// 从用户信息响应中取出用户名，Cookie 失效时标记过期
+    async fn user_name(&self, user_info: Result<UserInfoResponse>) -> Result<String> {
+        match user_info {
+            Ok(user_info) => match user_info.into_result() {
+                Ok(data) => Ok(data.user_name),
+                Err(e) => {
+                    self.check_cookie_expired(e.errno(), &e.to_string()).await;
+                    Err(anyhow!(tr!(user_validation_failed, e)))
+                }
+            },
+            Err(e) => Err(anyhow!(tr!(cookie_validation_failed, e))),
+        }
+    }
+
+    /// 查询当前持有的待完成任务数
+    pub async fn pending_task_count(&self) -> Result<i32> {
+        let mut options = HashMap::new();
+        options.insert("pn".to_string(), json!(1));
+        options.insert("rn".to_string(), json!(1));
+        options.insert("taskType".to_string(), json!(self.config.task_type));
+
+        let data = self
+            .client
+            .get_my_task_list(&options)
+            .await?
+            .into_result()
+            .map_err(|e| anyhow!(tr!(fetch_my_tasks_failed, e)))?;
+
+        Ok(data.total)
+    }
+
+    /// 是否因待完成任务过多而暂停认领
+    pub async fn is_dormant(&self) -> bool {
+        *self.dormant.lock().await
+    }
+
+    /// 休眠期间定期复查待完成任务数，直到可以继续认领
+    async fn wait_until_eligible(&self) {
+        let Some(recheck) = self.config.pending_recheck_interval else {
+            *self.dormant.lock().await = false;
+            return;
+        };
+
+        info!("{}", tr!(entering_dormancy, recheck));
+
+        loop {
+            sleep(poll_period(recheck)).await;
+
+            // 配置了上限时低于上限即可恢复，否则需全部完成
+            let threshold = self.settings().max_pending.unwrap_or(1);
+            match self.pending_task_count().await {
+                Ok(pending) if pending < threshold => {
+                    info!(pending, "{}", tr!(pending_cleared, pending));
+                    self.emit(ClaimEvent::Resumed { pending });
+                    break;
+                }
+                Ok(pending) => {
+                    info!(pending, "{}", tr!(still_pending, pending));
+                }
+                Err(e) => {
+                    // 无法确认时直接尝试认领，由认领结果决定是否继续休眠
+                    warn!(error = %e, "{}", tr!(pending_check_failed_resuming, e));
+                    break;
                 }
             }
-            Err(e) => Err(anyhow!("Cookie验证失败: {}", e)),
         }
+
+        *self.dormant.lock().await = false;
+    }
+
+    /// 根据服务器标签校验学科、学段和线索类型ID，无效时列出可选项
+    pub async fn validate_filters(&self) -> Result<()> {
+        let labels = match self
+            .client
+            .get_labels(&self.config.task_type)
+            .await
+            .and_then(|labels| Ok(labels.into_result()?))
+        {
+            Ok(labels) => labels,
+            Err(e) => {
+                warn!(error = %e, "{}", tr!(labels_unavailable_skip_check, e));
+                return Ok(());
+            }
+        };
+
+        let settings = self.settings();
+        let checks = [
+            ("subject", settings.subject_id),
+            ("step", settings.step_id),
+            ("clueType", settings.clue_type_id),
+        ];
+
+        for (filter_id, value) in checks {
+            // 服务器未提供该筛选项时不做校验
+            let Some(filter) = labels.find_filter(filter_id) else {
+                continue;
+            };
+
+            if filter.find_option(value).is_none() {
+                return Err(anyhow!(tr!(
+                    filter_id_not_found,
+                    filter.name,
+                    value,
+                    filter.describe_options()
+                )));
+            }
+        }
+
+        Ok(())
     }
 
     /// 执行单次认领尝试
+    ///
+    /// 每次尝试生成一个关联ID，记录在日志 span 中并随该次尝试的所有请求
+    /// 以 `X-Request-Id` 请求头发出，出错时也会附在错误信息里。
     pub async fn perform_single_claim(&self) -> Result<i32> {
+        let correlation_id = new_correlation_id();
+        with_correlation_id(correlation_id.clone(), self.attempt(&correlation_id))
+            .await
+            .map_err(|e| anyhow!(tr!(with_correlation_id, e, correlation_id)))
+    }
+
+    #[instrument(
+        name = "claimer.attempt",
+        skip_all,
+        fields(attempt = field::Empty, claimed = field::Empty, correlation_id = %correlation_id)
+    )]
+    async fn attempt(&self, correlation_id: &str) -> Result<i32> {
+        let (current_attempt, successful_claims) = self.begin_attempt().await;
+
+        if self.config.watch_only {
+            self.watch(current_attempt).await?;
+            return Ok(0);
+        }
+
+        // 检查是否达到认领限制
+        let claim_limit = self.claim_limit().await;
+        if successful_claims >= claim_limit {
+            info!(
+                attempt = current_attempt,
+                claimed = successful_claims,
+                limit = claim_limit,
+                "{}",
+                tr!(claim_limit_already_reached, successful_claims, claim_limit)
+            );
+            return Ok(0);
+        }
+
+        // 计算还需要认领多少个任务，不超过剩余的每日和每小时额度
+        let mut remaining_claims_needed = claim_limit - successful_claims;
+        if let Some(allowance) = self.claim_allowance().await {
+            remaining_claims_needed = remaining_claims_needed.min(allowance);
+        }
+        if remaining_claims_needed <= 0 {
+            return Ok(0);
+        }
+        let filtered_tasks = self
+            .list_candidates(current_attempt, remaining_claims_needed, &HashSet::new())
+            .await?;
+        if filtered_tasks.is_empty() {
+            return Ok(0);
+        }
+
+        // 提取任务ID
+        let task_ids: Vec<String> = filtered_tasks
+            .iter()
+            .map(|task| self.task_key(task).to_string())
+            .collect();
+
+        info!(
+            attempt = current_attempt,
+            subject = self.settings().subject_id,
+            task_ids = ?task_ids,
+            "{}",
+            tr!(claiming_tasks, task_ids.len(), task_ids)
+        );
+
+        // 执行认领
+        let outcome = self.claim_tasks(task_ids).await?;
+        let claim_result = outcome.count();
+        Span::current().record("claimed", claim_result);
+
+        self.record_claimed(current_attempt, filtered_tasks, &outcome)
+            .await;
+
+        Ok(claim_result)
+    }
+
+    /// 只监视模式的一次轮询：通知此前没见过的符合条件的任务
+    async fn watch(&self, current_attempt: i32) -> Result<()> {
+        let spotted = self.spotted.lock().await.clone();
+        let tasks = self
+            .list_candidates(current_attempt, i32::MAX, &spotted)
+            .await?;
+        if tasks.is_empty() {
+            return Ok(());
+        }
+
+        self.spotted
+            .lock()
+            .await
+            .extend(tasks.iter().map(|task| self.task_key(task)));
+        let briefs: Vec<&str> = tasks.iter().map(|task| task.brief.as_str()).collect();
+        info!(
+            attempt = current_attempt,
+            count = tasks.len(),
+            briefs = ?briefs,
+            "{}",
+            tr!(new_tasks_spotted, tasks.len(), briefs.join("；"))
+        );
+        self.emit(ClaimEvent::TasksSpotted {
+            attempt: current_attempt,
+            tasks,
+        });
+
+        Ok(())
+    }
+
+    /// 开始新一次尝试：递增尝试序号并发出事件，返回尝试序号和当前认领数
+    async fn begin_attempt(&self) -> (i32, i32) {
         let mut attempt_count = self.attempt_count.lock().await;
         *attempt_count += 1;
         let current_attempt = *attempt_count;
         drop(attempt_count);
+        Span::current().record("attempt", current_attempt);
 
         let successful_claims = *self.successful_claims.lock().await;
+        let claim_limit = self.claim_limit().await;
 
         info!(
-            "认领尝试 #{} 开始，当前认领数：{}/{}",
-            current_attempt, successful_claims, self.config.claim_limit
+            attempt = current_attempt,
+            claimed = successful_claims,
+            limit = claim_limit,
+            "{}",
+            tr!(
+                attempt_started,
+                current_attempt,
+                successful_claims,
+                claim_limit
+            )
         );
 
-        // 检查是否达到认领限制
-        if successful_claims >= self.config.claim_limit {
+        self.emit(ClaimEvent::AttemptStarted {
+            attempt: current_attempt,
+            claimed: successful_claims,
+            limit: claim_limit,
+        });
+
+        (current_attempt, successful_claims)
+    }
+
+    /// 与上次的任务池比较，发出新出现和被他人认领的任务事件；筛选项变化后重新开始比较
+    ///
+    /// 返回新出现的任务，以及上次任务池中是否没有可认领的任务。
+    async fn diff_pool(
+        &self,
+        current_attempt: i32,
+        filter: (i32, i32, i32),
+        tasks: &[TaskItem],
+    ) -> (Vec<TaskItem>, bool) {
+        let mut snapshot = self.pool_snapshot.lock().await;
+        let mut previous = snapshot.take().filter(|previous| previous.filter == filter);
+        let now = Local::now();
+        let mut new_tasks = Vec::new();
+        let mut current = HashMap::new();
+        for task in tasks {
+            let key = self.task_key(task);
+            // 基准任务池中的任务不知道何时出现，不计入消耗速度
+            let appeared_at = match &mut previous {
+                Some(previous) => match previous.tasks.remove(&key) {
+                    Some((_, appeared_at)) => appeared_at,
+                    None => {
+                        new_tasks.push(task.clone());
+                        Some(now)
+                    }
+                },
+                None => None,
+            };
+            current.insert(key, (task.clone(), appeared_at));
+        }
+        let exhausted = previous.as_ref().is_some_and(|previous| previous.exhausted);
+        let vanished = previous.map(|previous| previous.tasks).unwrap_or_default();
+        *snapshot = Some(PoolSnapshot {
+            filter,
+            tasks: current,
+            exhausted: tasks.is_empty(),
+        });
+        drop(snapshot);
+
+        // 第一页已满时，消失的任务可能只是被挤到了后面的页，无法判断是否被他人认领
+        if !vanished.is_empty() && tasks.len() < LIST_PAGE_SIZE as usize {
+            let claimed_ids = &self.state.lock().await.claimed_ids;
+            let drained: Vec<DrainedTask> = vanished
+                .into_iter()
+                .filter(|(key, _)| !claimed_ids.contains(key))
+                .filter_map(|(_, (task, appeared_at))| {
+                    Some(DrainedTask {
+                        task,
+                        lifetime_ms: (now - appeared_at?).num_milliseconds().max(0) as u64,
+                    })
+                })
+                .collect();
+            if !drained.is_empty() {
+                debug!(
+                    attempt = current_attempt,
+                    count = drained.len(),
+                    "{}",
+                    tr!(tasks_drained, drained.len())
+                );
+                self.emit(ClaimEvent::TasksDrained {
+                    attempt: current_attempt,
+                    tasks: drained,
+                });
+            }
+        }
+
+        if !new_tasks.is_empty() {
+            let briefs: Vec<&str> = new_tasks.iter().map(|task| task.brief.as_str()).collect();
             info!(
-                "认领限制已达到 ({}/{})",
-                successful_claims, self.config.claim_limit
+                attempt = current_attempt,
+                count = new_tasks.len(),
+                briefs = ?briefs,
+                "{}",
+                tr!(new_tasks_appeared, new_tasks.len(), briefs.join("；"))
             );
-            return Ok(0);
+            self.emit(ClaimEvent::NewTasksAppeared {
+                attempt: current_attempt,
+                tasks: new_tasks.clone(),
+            });
         }
 
-        // 计算还需要认领多少个任务
-        let remaining_claims_needed = self.config.claim_limit - successful_claims;
+        (new_tasks, exhausted)
+    }
 
-        // 获取任务列表的选项
+    /// 获取任务列表并筛选出本次要认领的任务，跳过已认领过和 `exclude` 中的任务
+    ///
+    /// 任务池为空、没有符合条件的任务或待完成任务已达上限时返回空列表。
+    async fn list_candidates(
+        &self,
+        current_attempt: i32,
+        remaining_claims_needed: i32,
+        exclude: &HashSet<i64>,
+    ) -> Result<Vec<TaskItem>> {
+        // 获取任务列表的选项，筛选项可能在运行中被修改，本次尝试内保持一致
+        let settings = self.settings();
         let mut options = HashMap::new();
         options.insert("pn".to_string(), json!(1));
-        options.insert("rn".to_string(), json!(20));
+        options.insert("rn".to_string(), json!(LIST_PAGE_SIZE));
         options.insert("clueID".to_string(), json!(""));
-        options.insert("clueType".to_string(), json!(self.config.clue_type_id));
-        options.insert("step".to_string(), json!(self.config.step_id));
-        options.insert("subject".to_string(), json!(self.config.subject_id));
+        options.insert("clueType".to_string(), json!(settings.clue_type_id));
+        options.insert("step".to_string(), json!(settings.step_id));
+        options.insert("subject".to_string(), json!(settings.subject_id));
         options.insert("taskType".to_string(), json!(self.config.task_type));
 
         // 获取任务列表
-        let task_response = self.client.get_audit_task_list(&options).await?;
+        let list_started = Instant::now();
+        let fetched = self.client.fetch_audit_task_list(&options).await?;
+        let task_list = match fetched.response.into_result() {
+            Ok(task_list) => task_list,
+            Err(e) => {
+                self.check_cookie_expired(e.errno(), &e.to_string()).await;
+                return Err(anyhow!(tr!(fetch_task_list_failed, e)));
+            }
+        };
 
-        if task_response.errno != 0 {
-            return Err(anyhow!("获取任务列表失败: {}", task_response.errmsg));
-        }
+        let listed_at = Instant::now();
+        let list_latency = listed_at - list_started;
+        self.stats
+            .lock()
+            .await
+            .record_list(list_latency, task_list.list.len());
+
+        let tasks = task_list.list;
+        self.emit(ClaimEvent::PoolFetched {
+            attempt: current_attempt,
+            pool_size: tasks.len(),
+            latency_ms: list_latency.as_millis() as u64,
+        });
+        info!(
+            attempt = current_attempt,
+            subject = settings.subject_id,
+            pool_size = tasks.len(),
+            "{}",
+            tr!(tasks_fetched, tasks.len())
+        );
 
-        let tasks = task_response.data.list;
-        info!("获取到 {} 个任务", tasks.len());
+        // 条件请求返回 304 时任务池与上次相同，没有新出现或消失的任务，不需要比较
+        let filter = (settings.subject_id, settings.step_id, settings.clue_type_id);
+        let unchanged = if fetched.modified {
+            None
+        } else {
+            self.pool_snapshot
+                .lock()
+                .await
+                .as_ref()
+                .filter(|previous| previous.filter == filter)
+                .map(|previous| previous.exhausted)
+        };
+        let (new_tasks, exhausted) = match unchanged {
+            Some(exhausted) => (Vec::new(), exhausted),
+            None => self.diff_pool(current_attempt, filter, &tasks).await,
+        };
 
         if tasks.is_empty() {
-            warn!("线索池中没任务");
-            return Ok(0);
+            warn!(
+                attempt = current_attempt,
+                subject = settings.subject_id,
+                "{}",
+                tr!(pool_empty)
+            );
+            return Ok(Vec::new());
         }
 
-        // 简单筛选
-        let filtered_tasks: Vec<TaskItem> = tasks
+        // 上次任务池中没有可认领的任务时，只有新出现的任务需要筛选
+        let tasks = if exhausted {
+            if new_tasks.is_empty() {
+                debug!(attempt = current_attempt, "{}", tr!(pool_unchanged));
+                return Ok(Vec::new());
+            }
+            new_tasks
+        } else {
+            tasks
+        };
+
+        // 简单筛选，跳过已认领过的任务
+        let claimed_ids = self.state.lock().await.claimed_ids.clone();
+        let mut filtered_tasks: Vec<TaskItem> = tasks
             .into_iter()
-            .take(remaining_claims_needed as usize)
+            .filter(|task| {
+                let key = self.task_key(task);
+                !claimed_ids.contains(&key) && !exclude.contains(&key)
+            })
+            .take(remaining_claims_needed.max(0) as usize)
             .collect();
 
+        // 正在认领中而被排除的任务可能认领失败，之后需要重新筛选
+        if let Some(snapshot) = self.pool_snapshot.lock().await.as_mut() {
+            snapshot.exhausted = filtered_tasks.is_empty() && exclude.is_empty();
+        }
         if filtered_tasks.is_empty() {
-            warn!("没有符合条件的任务");
-            return Ok(0);
+            warn!(attempt = current_attempt, "{}", tr!(no_matching_tasks));
+            return Ok(Vec::new());
         }
 
-        // 提取任务ID
-        let task_ids: Vec<String> = filtered_tasks
-            .iter()
-            .map(|task| {
-                if self.config.task_type == "producetask" {
-                    task.clue_id.to_string()
-                } else {
-                    task.task_id.to_string()
+        // 认领前检查待完成任务数，避免触发 10003
+        if let Some(max_pending) = self
+            .settings()
+            .max_pending
+            .filter(|_| !self.config.watch_only)
+        {
+            match self.pending_task_count().await {
+                Ok(pending) if pending >= max_pending => {
+                    info!(
+                        outcome = "limited",
+                        attempt = current_attempt,
+                        pending,
+                        max_pending,
+                        "{}",
+                        tr!(pending_limit_reached, pending, max_pending)
+                    );
+                    self.emit(ClaimEvent::PendingLimitReached {
+                        attempt: current_attempt,
+                        pending,
+                        max_pending,
+                    });
+                    return Ok(Vec::new());
                 }
-            })
-            .collect();
+                Ok(pending) => filtered_tasks.truncate((max_pending - pending) as usize),
+                Err(e) => {
+                    warn!(error = %e, "{}", tr!(pending_check_failed_continuing, e));
+                }
+            }
+        }
 
-        info!("尝试认领 {} 个任务: {:?}", task_ids.len(), task_ids);
+        if !self.config.watch_only {
+            let mut listed = self.listed_at.lock().await;
+            // 没有认领的候选任务（如额度用完被丢弃）不会被取出，过期后清理
+            listed.retain(|_, at| at.elapsed() < LISTED_AT_RETENTION);
+            listed.extend(
+                filtered_tasks
+                    .iter()
+                    .map(|task| (self.task_key(task).to_string(), listed_at)),
+            );
+        }
 
-        // 执行认领
-        let claim_result = self.claim_tasks(task_ids).await?;
+        Ok(filtered_tasks)
+    }
 
-        Ok(claim_result)
+    /// 按学科记录认领成功数，并发出每个任务的认领记录
+    async fn record_claimed(
+        &self,
+        current_attempt: i32,
+        tasks: Vec<TaskItem>,
+        outcome: &ClaimOutcome,
+    ) {
+        if outcome.count() == 0 {
+            return;
+        }
+
+        let mut newly_claimed = Vec::new();
+        {
+            let claimed_at = Local::now();
+            let mut stats = self.stats.lock().await;
+            let mut claimed_tasks = self.claimed_tasks.lock().await;
+            for task in tasks
+                .into_iter()
+                .filter(|task| outcome.succeeded.contains(&self.task_key(task).to_string()))
+            {
+                stats.record_subject_claims(&task.subject_name, 1);
+                let claimed = ClaimedTask {
+                    task,
+                    task_type: self.config.task_type.clone(),
+                    attempt: current_attempt,
+                    claimed_at,
+                };
+                claimed_tasks.push(claimed.clone());
+                newly_claimed.push(claimed.clone());
+                self.emit(ClaimEvent::TaskClaimed {
+                    task: Box::new(claimed),
+                });
+            }
+        }
+
+        // 交给 claim_stream 的消费者，释放锁后再等待以免阻塞状态查询
+        let sink = self.task_sink.lock().await.clone();
+        if let Some(sink) = sink {
+            for claimed in newly_claimed {
+                if sink.send(claimed).await.is_err() {
+                    break;
+                }
+            }
+        }
     }
 
-    /// 执行认领任务操作
-    pub async fn claim_tasks(&self, task_ids: Vec<String>) -> Result<i32> {
+    /// 执行认领任务操作，返回逐任务的认领结果
+    #[instrument(name = "claimer.claim_tasks", skip_all, fields(count = task_ids.len()))]
+    pub async fn claim_tasks(&self, task_ids: Vec<String>) -> Result<ClaimOutcome> {
+        let claim_started = Instant::now();
         let claim_response = self
             .client
             .claim_audit_task(task_ids.clone(), &self.config.task_type)
             .await?;
+        let claim_finished = Instant::now();
 
-        let success_count = if claim_response.errno == 0 {
-            // 尝试从响应中提取成功数量
-            let count = if let Some(data) = &claim_response.data {
-                if let Some(data_obj) = data.as_object() {
-                    if let Some(success) = data_obj.get("success").and_then(|v| v.as_i64()) {
-                        success as i32
-                    } else {
-                        task_ids.len() as i32 // 假设全部成功
-                    }
-                } else {
-                    task_ids.len() as i32 // 假设全部成功
-                }
-            } else {
-                task_ids.len() as i32 // 假设全部成功
-            };
+        let outcome = ClaimOutcome::from_response(&task_ids, &claim_response);
+
+        let listed: Vec<(String, Instant)> = {
+            let mut listed_at = self.listed_at.lock().await;
+            task_ids
+                .iter()
+                .filter_map(|id| listed_at.remove_entry(id))
+                .collect()
+        };
+        let mut stats = self.stats.lock().await;
+        stats.record_claim(
+            claim_finished - claim_started,
+            task_ids.len(),
+            claim_response.errno,
+        );
+        for (id, listed_at) in listed {
+            stats.record_listed_to_claimed(
+                claim_finished - listed_at,
+                outcome.succeeded.contains(&id),
+            );
+        }
+        drop(stats);
+
+        if claim_response.errno == 0 {
+            let count = outcome.count();
+            let succeeded = &outcome.succeeded;
 
             let mut successful_claims = self.successful_claims.lock().await;
             *successful_claims += count;
+            self.claimed_task_ids
+                .lock()
+                .await
+                .extend(succeeded.iter().cloned());
+            self.state
+                .lock()
+                .await
+                .record_claims(self.today(), succeeded, count);
+            if self.settings().max_claims_per_hour.is_some() {
+                let claimed_at = Instant::now();
+                self.recent_claims
+                    .lock()
+                    .await
+                    .extend(std::iter::repeat_n(claimed_at, count.max(0) as usize));
+            }
+            self.stats.lock().await.record_claimed(count, self.now());
+            self.emit(ClaimEvent::Claimed {
+                attempt: *self.attempt_count.lock().await,
+                task_ids: succeeded.clone(),
+                requested: task_ids.len(),
+                count,
+                total: *successful_claims,
+            });
 
+            let claim_limit = self.claim_limit().await;
             info!(
-                "认领成功：{} 个任务，TaskID: {:?}，总计：{}/{}",
-                count, task_ids, *successful_claims, self.config.claim_limit
+                outcome = "claimed",
+                task_ids = ?succeeded,
+                count,
+                claimed = *successful_claims,
+                limit = claim_limit,
+                "{}",
+                tr!(claim_succeeded, count, succeeded, *successful_claims, claim_limit)
             );
 
-            count
+            for failed in &outcome.failed {
+                warn!(
+                    outcome = "failed",
+                    task_id = %failed.id,
+                    reason = %failed.reason,
+                    "{}",
+                    tr!(task_claim_failed, failed.id, failed.reason)
+                );
+            }
         } else {
             // 详细记录认领失败信息
             let task_type = if self.config.task_type == "producetask" {
@@ -196,70 +1598,560 @@ impl AutoClaimer {
             };
 
             let data_info = match &claim_response.data {
-                Some(data) => format!("响应数据: {}", data),
-                None => "响应数据: null".to_string(),
+                Some(data) => tr!(response_data, data),
+                None => tr!(response_data_null),
             };
 
+            let log_id = claim_response.log_id();
+            let log_info = log_id
+                .as_ref()
+                .map(|id| tr!(log_id_suffix, id))
+                .unwrap_or_default();
+
             warn!(
-                "认领失败 {}: {:?}，错误码: {}，错误信息: {}，{}",
-                task_type, task_ids, claim_response.errno, claim_response.errmsg, data_info
+                outcome = "failed",
+                task_ids = ?task_ids,
+                errno = claim_response.errno,
+                errmsg = %claim_response.errmsg,
+                log_id = log_id.as_deref(),
+                "{}",
+                tr!(
+                    claim_failed,
+                    task_type,
+                    task_ids,
+                    claim_response.errno,
+                    claim_response.errmsg,
+                    log_info,
+                    data_info
+                )
             );
 
+            self.record_failure(
+                task_ids.clone(),
+                Some(claim_response.errno),
+                format!("{}{}", claim_response.errmsg, log_info),
+            )
+            .await;
+            self.check_cookie_expired(Some(claim_response.errno), &claim_response.errmsg)
+                .await;
+
             // 对于特定错误码，可以给出更友好的提示
-            match claim_response.errno {
-                10003 => {
-                    warn!("提示：请先完成待审核的任务后再尝试认领新任务");
+            if claim_response.errno == 10003 {
+                warn!(
+                    outcome = "limited",
+                    errno = 10003,
+                    "{}",
+                    tr!(finish_pending_hint)
+                );
+
+                if self.config.pending_recheck_interval.is_some() {
+                    *self.dormant.lock().await = true;
+                    self.emit(ClaimEvent::Dormant {
+                        attempt: *self.attempt_count.lock().await,
+                        errno: claim_response.errno,
+                    });
                 }
-                _ => {}
             }
+        }
 
-            0
-        };
+        if outcome.count() > 0
+            && let Err(e) = self.save_state().await
+        {
+            warn!(error = %e, "{}", tr!(save_state_failed, e));
+        }
 
-        Ok(success_count)
+        Ok(outcome)
     }
 
-    /// 开始自动认领循环
-    pub async fn start(&self) -> Result<()> {
-        info!("开始自动认领任务...");
+    /// 开始自动认领循环，结束后返回运行汇总
+    ///
+    /// `claim_workers` 大于 1 时，任务列表轮询和认领分别在独立的任务中运行，
+    /// 通过通道连接：轮询到的任务立即交给空闲的认领工作者，缩短竞争激烈时从发现任务到认领的间隔。
+    /// 出错结束时发出 [`ClaimEvent::Aborted`] 事件。
+    pub async fn start(&self) -> Result<ClaimSummary> {
+        let result = self.run().await;
+        if let Err(e) = &result {
+            self.emit(ClaimEvent::Aborted {
+                message: e.to_string(),
+            });
+        }
+        result
+    }
+
+    async fn run(&self) -> Result<ClaimSummary> {
+        let started_at = Instant::now();
+        let claim_limit = self.claim_limit().await;
+        let settings = self.settings();
+        info!("{}", tr!(auto_claim_starting));
         info!(
-            "配置: 任务类型={}, 认领限制={}, 轮询间隔={:.1}秒, 学科ID={}, 学段ID={}, 线索类型ID={}",
-            self.config.task_type,
-            self.config.claim_limit,
-            self.config.interval,
-            self.config.subject_id,
-            self.config.step_id,
-            self.config.clue_type_id
+            task_type = %self.config.task_type,
+            limit = claim_limit,
+            subject = settings.subject_id,
+            step = settings.step_id,
+            clue_type = settings.clue_type_id,
+            "{}",
+            tr!(
+                claim_config,
+                self.config.task_type,
+                claim_limit,
+                settings.interval,
+                settings.subject_id,
+                settings.step_id,
+                settings.clue_type_id
+            )
         );
 
         // 验证cookie有效性
         let user_name = self.validate_user().await?;
-        info!("用户验证成功: {}", user_name);
+        info!(user = %user_name, "{}", tr!(user_validated, user_name));
+
+        // 校验筛选ID，避免轮询错误或为空的任务池
+        self.validate_filters().await?;
+
+        self.load_state().await?;
 
-        let mut interval = interval(Duration::from_secs_f64(self.config.interval));
+        // 尝试次数可能从状态文件恢复，只计算本次运行的尝试
+        let attempts_before = *self.attempt_count.lock().await;
+        let limits = RunLimits {
+            deadline: self
+                .config
+                .max_runtime
+                .map(|max_runtime| time::Instant::now() + max_runtime),
+            last_attempt: self
+                .config
+                .max_attempts
+                .map(|max_attempts| attempts_before.saturating_add_unsigned(max_attempts)),
+        };
+        let result = if self.config.claim_workers > 1 && !self.config.watch_only {
+            self.run_worker_pool(&limits).await
+        } else {
+            self.run_loop(&limits).await
+        };
+
+        if let Err(e) = self.save_state().await {
+            warn!(error = %e, "{}", tr!(save_state_failed, e));
+        }
+        result?;
+
+        let stats = self.get_stats().await;
+        let summary = ClaimSummary {
+            total_claims: stats.total_claims,
+            attempts: stats.attempts,
+            duration: started_at.elapsed(),
+            claimed_task_ids: self.claimed_task_ids.lock().await.clone(),
+            failures: self.failures.lock().await.clone(),
+        };
+        info!(
+            claimed = summary.total_claims,
+            attempts = summary.attempts,
+            failures = summary.failures.len(),
+            "{}",
+            tr!(
+                auto_claim_finished,
+                // 与认领限制比较的是含恢复部分的认领数
+                *self.successful_claims.lock().await,
+                self.claim_limit().await,
+                summary.attempts,
+                summary.duration.as_secs_f64()
+            )
+        );
+
+        self.emit(ClaimEvent::Finished {
+            summary: summary.clone(),
+        });
+
+        Ok(summary)
+    }
+
+    /// 轮询间隔在运行中被修改时按新间隔重建定时器，从修改时起计时
+    fn refresh_interval(&self, timer: &mut time::Interval) {
+        let period = poll_period(self.settings().interval);
+        if timer.period() != period {
+            *timer = time::interval_at(time::Instant::now() + period, period);
+        }
+    }
+
+    /// 按轮询间隔依次执行认领尝试，直到达到认领限制或连续失败次数用尽
+    async fn run_loop(&self, limits: &RunLimits) -> Result<()> {
+        let mut interval = interval(poll_period(self.settings().interval));
 
         loop {
-            interval.tick().await;
+            self.refresh_interval(&mut interval);
+            if before_deadline(limits.deadline, interval.tick())
+                .await
+                .is_none()
+            {
+                self.log_runtime_reached();
+                break;
+            }
+
+            if self.attempts_exhausted(limits).await {
+                break;
+            }
 
             let successful_claims = *self.successful_claims.lock().await;
-            if successful_claims >= self.config.claim_limit {
-                info!("已达到认领限制，停止自动认领");
+            if successful_claims >= self.claim_limit().await {
+                self.limit_reached().await;
                 break;
             }
 
+            if self.is_dormant().await {
+                if before_deadline(limits.deadline, self.wait_until_eligible())
+                    .await
+                    .is_none()
+                {
+                    self.log_runtime_reached();
+                    break;
+                }
+                interval.reset();
+                continue;
+            }
+
+            if self.should_pause().await {
+                if before_deadline(limits.deadline, self.wait_until_allowed())
+                    .await
+                    .is_none()
+                {
+                    self.log_runtime_reached();
+                    break;
+                }
+                interval.reset();
+                continue;
+            }
+
+            let errors_before = *self.consecutive_errors.lock().await;
             if let Err(e) = self.perform_single_claim().await {
-                error!("认领过程出错: {}", e);
-                sleep(Duration::from_secs(1)).await;
+                error!(error = %e, "{}", tr!(claim_error, e));
+                self.record_failure(Vec::new(), None, e.to_string()).await;
+            }
+
+            // 本次尝试没有新的失败时清零连续失败计数
+            let mut consecutive_errors = self.consecutive_errors.lock().await;
+            if *consecutive_errors == errors_before {
+                *consecutive_errors = 0;
+                continue;
             }
+            drop(consecutive_errors);
+
+            self.check_error_budget().await?;
+            sleep(Duration::from_secs(1)).await;
         }
 
-        let final_claims = *self.successful_claims.lock().await;
-        let final_attempts = *self.attempt_count.lock().await;
+        Ok(())
+    }
+
+    /// 本次运行的尝试次数是否已用尽，用尽时记录日志
+    async fn attempts_exhausted(&self, limits: &RunLimits) -> bool {
+        let Some(last_attempt) = limits.last_attempt else {
+            return false;
+        };
+        if *self.attempt_count.lock().await < last_attempt {
+            return false;
+        }
+
+        let max_attempts = self.config.max_attempts.unwrap_or_default();
+        info!(max_attempts, "{}", tr!(max_attempts_reached, max_attempts));
+        true
+    }
+
+    fn log_runtime_reached(&self) {
+        if let Some(max_runtime) = self.config.max_runtime {
+            info!(
+                max_runtime_secs = max_runtime.as_secs_f64(),
+                "{}",
+                tr!(max_runtime_reached, max_runtime.as_secs_f64())
+            );
+        }
+    }
+
+    /// 工作池模式：一个任务轮询任务列表，`claim_workers` 个工作者并发认领
+    ///
+    /// 工作者共享一个信号量，许可数为剩余认领额度，认领成功即消耗一个许可，
+    /// 避免并发认领超出认领限制；额度用完时关闭信号量，轮询和工作者随之退出。
+    async fn run_worker_pool(&self, limits: &RunLimits) -> Result<()> {
+        let workers = self.config.claim_workers;
+        let claim_limit = self.claim_limit().await;
+        let remaining = claim_limit - *self.successful_claims.lock().await;
+        let quota = Semaphore::new(remaining.max(0) as usize);
+        let in_flight = Mutex::new(HashSet::new());
+        let (sender, receiver) = mpsc::channel(workers);
+        let receiver = Mutex::new(receiver);
+
+        info!(workers, "{}", tr!(worker_pool_mode, workers));
+
+        let lister = self.list_into(sender, &quota, claim_limit, &in_flight, limits);
+        let claimers = future::join_all(
+            (1..=workers).map(|worker| self.claim_worker(worker, &receiver, &quota, &in_flight)),
+        );
+        let (listed, _) = tokio::join!(lister, claimers);
+        listed
+    }
+
+    /// 按轮询间隔获取任务列表，把待认领的任务送入通道
+    async fn list_into(
+        &self,
+        sender: mpsc::Sender<(i32, TaskItem)>,
+        quota: &Semaphore,
+        mut quota_limit: i32,
+        in_flight: &Mutex<HashSet<i64>>,
+        limits: &RunLimits,
+    ) -> Result<()> {
+        let mut interval = interval(poll_period(self.settings().interval));
+
+        loop {
+            self.refresh_interval(&mut interval);
+            if before_deadline(limits.deadline, interval.tick())
+                .await
+                .is_none()
+            {
+                self.log_runtime_reached();
+                quota.close();
+                return Ok(());
+            }
+
+            if self.attempts_exhausted(limits).await {
+                quota.close();
+                return Ok(());
+            }
+
+            if let Err(e) = self.check_error_budget().await {
+                quota.close();
+                return Err(e);
+            }
+
+            // 认领限制在运行中被修改时同步调整额度
+            let claim_limit = self.claim_limit().await;
+            if claim_limit > quota_limit {
+                quota.add_permits((claim_limit - quota_limit) as usize);
+            } else if claim_limit < quota_limit {
+                quota.forget_permits((quota_limit - claim_limit) as usize);
+            }
+            quota_limit = claim_limit;
+
+            if quota.is_closed() || *self.successful_claims.lock().await >= claim_limit {
+                self.limit_reached().await;
+                quota.close();
+                return Ok(());
+            }
+
+            if self.is_dormant().await {
+                if before_deadline(limits.deadline, self.wait_until_eligible())
+                    .await
+                    .is_none()
+                {
+                    self.log_runtime_reached();
+                    quota.close();
+                    return Ok(());
+                }
+                interval.reset();
+                continue;
+            }
+
+            // 认领中的任务占用额度，全部完成后再判断是否需要暂停
+            if in_flight.lock().await.is_empty() && self.should_pause().await {
+                if before_deadline(limits.deadline, self.wait_until_allowed())
+                    .await
+                    .is_none()
+                {
+                    self.log_runtime_reached();
+                    quota.close();
+                    return Ok(());
+                }
+                interval.reset();
+                continue;
+            }
+
+            let correlation_id = new_correlation_id();
+            let errors_before = *self.consecutive_errors.lock().await;
+            let listed = with_correlation_id(
+                correlation_id.clone(),
+                self.list_round(&correlation_id, in_flight),
+            )
+            .await;
+
+            match listed {
+                Ok((attempt, tasks)) => {
+                    // 没有要认领的任务且期间没有新的失败时清零连续失败计数，
+                    // 有任务时由工作者根据认领结果决定
+                    let mut consecutive_errors = self.consecutive_errors.lock().await;
+                    if tasks.is_empty() && *consecutive_errors == errors_before {
+                        *consecutive_errors = 0;
+                    }
+                    drop(consecutive_errors);
+
+                    for task in tasks {
+                        in_flight.lock().await.insert(self.task_key(&task));
+                        if sender.send((attempt, task)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!(error = %e, "{}", tr!(fetch_task_list_error, e, correlation_id));
+                    self.record_failure(Vec::new(), None, e.to_string()).await;
+                    sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    /// 工作池模式下的一次轮询，跳过正在认领中的任务
+    #[instrument(
+        name = "claimer.attempt",
+        skip_all,
+        fields(attempt = field::Empty, correlation_id = %correlation_id)
+    )]
+    async fn list_round(
+        &self,
+        correlation_id: &str,
+        in_flight: &Mutex<HashSet<i64>>,
+    ) -> Result<(i32, Vec<TaskItem>)> {
+        let (current_attempt, successful_claims) = self.begin_attempt().await;
+        // 持有锁读取额度，认领完成的任务先计入认领数再移出认领中列表
+        let in_flight_guard = in_flight.lock().await;
+        let mut remaining = self.claim_limit().await - successful_claims;
+        if let Some(allowance) = self.claim_allowance().await {
+            remaining = remaining.min(allowance);
+        }
+        let in_flight = in_flight_guard.clone();
+        drop(in_flight_guard);
+        let remaining = remaining - in_flight.len() as i32;
+        if remaining <= 0 {
+            return Ok((current_attempt, Vec::new()));
+        }
+
+        let tasks = self
+            .list_candidates(current_attempt, remaining, &in_flight)
+            .await?;
+        Ok((current_attempt, tasks))
+    }
+
+    /// 认领工作者：从通道取出任务，取得额度许可后逐个认领
+    async fn claim_worker(
+        &self,
+        worker: usize,
+        receiver: &Mutex<mpsc::Receiver<(i32, TaskItem)>>,
+        quota: &Semaphore,
+        in_flight: &Mutex<HashSet<i64>>,
+    ) {
+        loop {
+            let Some((attempt, task)) = receiver.lock().await.recv().await else {
+                break;
+            };
+            let key = self.task_key(&task);
+
+            // 额度用完或进入休眠时丢弃剩余任务
+            if let Ok(permit) = quota.acquire().await
+                && !self.is_dormant().await
+            {
+                let correlation_id = new_correlation_id();
+                let claimed = with_correlation_id(
+                    correlation_id.clone(),
+                    self.claim_one(worker, attempt, task, &correlation_id),
+                )
+                .await;
+                match claimed {
+                    Ok(true) => {
+                        permit.forget();
+                        *self.consecutive_errors.lock().await = 0;
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        error!(
+                            worker,
+                            error = %e,
+                            "{}",
+                            tr!(worker_claim_error, worker, e, correlation_id)
+                        );
+                        self.record_failure(vec![key.to_string()], None, e.to_string())
+                            .await;
+                    }
+                }
+
+                if *self.successful_claims.lock().await >= self.claim_limit().await
+                    || self.check_error_budget().await.is_err()
+                {
+                    quota.close();
+                }
+            }
+
+            in_flight.lock().await.remove(&key);
+        }
+    }
+
+    /// 认领单个任务，返回是否认领成功
+    #[instrument(
+        name = "claimer.worker",
+        skip_all,
+        fields(worker = worker, attempt = attempt, correlation_id = %correlation_id)
+    )]
+    async fn claim_one(
+        &self,
+        worker: usize,
+        attempt: i32,
+        task: TaskItem,
+        correlation_id: &str,
+    ) -> Result<bool> {
+        let task_id = self.task_key(&task).to_string();
         info!(
-            "自动认领完成，最终认领数：{}/{}，总尝试次数：{}",
-            final_claims, self.config.claim_limit, final_attempts
+            worker,
+            attempt,
+            task_id = %task_id,
+            "{}",
+            tr!(worker_claiming, worker, task_id)
         );
 
-        Ok(())
+        let outcome = self.claim_tasks(vec![task_id]).await?;
+        self.record_claimed(attempt, vec![task], &outcome).await;
+
+        Ok(outcome.count() > 0)
+    }
+}
+
+/// 一次任务列表的结果，下次获取时与之比较
+struct PoolSnapshot {
+    /// 获取时的筛选项（学科、学段、线索类型）
+    filter: (i32, i32, i32),
+    /// 按认领键索引的任务及其出现在任务池中的时间，基准任务池中的任务没有出现时间
+    tasks: HashMap<i64, (TaskItem, Option<DateTime<Local>>)>,
+    /// 任务池中没有可认领的任务
+    exhausted: bool,
+}
+
+/// 候选任务的列表时间最多保留多久
+const LISTED_AT_RETENTION: Duration = Duration::from_secs(300);
+
+/// 每次获取的任务列表条数
+const LIST_PAGE_SIZE: i32 = 20;
+
+/// 每小时认领上限的滑动窗口长度
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// 最短轮询间隔
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// 轮询或复查间隔对应的时长，无效值按最短间隔处理，避免构造计时器或等待时 panic
+fn poll_period(interval: f64) -> Duration {
+    Duration::try_from_secs_f64(interval)
+        .unwrap_or(MIN_INTERVAL)
+        .max(MIN_INTERVAL)
+}
+
+/// 单次运行的停止条件
+struct RunLimits {
+    /// 最长运行时间对应的截止时间
+    deadline: Option<time::Instant>,
+    /// 尝试计数达到此值后停止
+    last_attempt: Option<i32>,
+}
+
+/// 在截止时间前等待完成，超过截止时间返回 None；没有截止时间时一直等待
+async fn before_deadline<F: Future>(
+    deadline: Option<time::Instant>,
+    future: F,
+) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => time::timeout_at(deadline, future).await.ok(),
+        None => Some(future.await),
     }
 }
@@ -0,0 +1,311 @@
+//! Cookie 字符串的解析与规范化
+//!
+//! 用户复制 Cookie 的方式五花八门：`Cookie:` 请求头、开发者工具中“复制请求标头”的整段内容、
+//! “复制为 cURL”得到的 bash 或 cmd 命令。[`Cookie::parse`] 从这些内容中提取 `name=value`
+//! 字段，规范化为 `a=1; b=2` 的形式，并检查登录态必需的字段是否齐全。浏览器扩展导出的
+//! cookies.txt 由 [`Cookie::from_netscape`] 读取。
+
+use crate::error::BeduError;
+use crate::tr;
+use std::fmt;
+use std::str::FromStr;
+
+/// 登录态必需的 Cookie 字段
+pub const REQUIRED_KEYS: [&str; 1] = ["BDUSS"];
+
+/// 规范化后的 Cookie，字段按首次出现的顺序保存，同名字段取最后一次的值
+///
+/// ```
+/// use bedu_claim::client::Cookie;
+///
+/// let cookie = Cookie::parse(
+///     r#"curl 'https://easylearn.baidu.com/edushop/user/common/info' \
+///   -H 'accept: application/json' \
+///   -H 'cookie: BAIDUID=abc:FG=1; BDUSS=xyz'"#,
+/// )?;
+/// assert_eq!(cookie.get("BDUSS"), Some("xyz"));
+/// assert_eq!(cookie.to_string(), "BAIDUID=abc:FG=1; BDUSS=xyz");
+///
+/// assert!(Cookie::parse("Cookie: BAIDUID=abc").is_err());
+/// # Ok::<(), bedu_claim::BeduError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pairs: Vec<(String, String)>,
+}
+
+impl Cookie {
+    /// 从 Cookie 字符串、`Cookie:` 请求头、整段请求头或 curl 命令中提取 Cookie，
+    /// 缺少 [`REQUIRED_KEYS`] 中的字段时返回错误
+    pub fn parse(input: &str) -> Result<Self, BeduError> {
+        let input = input.trim();
+        let values = if is_curl(input) {
+            let values = curl_cookies(input);
+            if values.is_empty() {
+                return Err(BeduError::Config(tr!(cookie_not_in_curl)));
+            }
+            values
+        } else {
+            header_cookies(input)
+        };
+
+        let mut cookie = Self { pairs: Vec::new() };
+        for (name, value) in values
+            .iter()
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.split_once('='))
+        {
+            cookie.insert(name.trim(), value.trim());
+        }
+        if cookie.pairs.is_empty() {
+            return Err(BeduError::Config(tr!(cookie_no_pairs)));
+        }
+
+        cookie.check_required()
+    }
+
+    /// 从 Netscape 格式的 cookies.txt（浏览器扩展和 curl 导出的格式）中取出浏览器会发送给 `host`
+    /// 的 Cookie，跳过已过期的；缺少 [`REQUIRED_KEYS`] 中的字段时返回错误
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_netscape(content: &str, host: &str) -> Result<Self, BeduError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let host = host.to_ascii_lowercase();
+
+        let mut cookie = Self { pairs: Vec::new() };
+        for line in content.lines() {
+            // curl 导出时 HttpOnly 的 Cookie 带有该前缀，其余 `#` 开头的行是注释
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.starts_with('#') {
+                continue;
+            }
+            let [domain, _, _, _, expires, name, value] = line.split('\t').collect::<Vec<_>>()[..]
+            else {
+                continue;
+            };
+            let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+            let matches_host = host == domain
+                || host
+                    .strip_suffix(&domain)
+                    .is_some_and(|prefix| prefix.ends_with('.'));
+            // 过期时间为 0 表示会话 Cookie
+            let expired = expires
+                .parse::<u64>()
+                .is_ok_and(|expires| expires != 0 && expires < now);
+            if matches_host && !expired {
+                cookie.insert(name.trim(), value.trim());
+            }
+        }
+        if cookie.pairs.is_empty() {
+            return Err(BeduError::Config(tr!(cookie_file_no_match, host)));
+        }
+        cookie.check_required()
+    }
+
+    fn check_required(self) -> Result<Self, BeduError> {
+        let missing: Vec<&str> = REQUIRED_KEYS
+            .into_iter()
+            .filter(|key| self.get(key).is_none_or(str::is_empty))
+            .collect();
+        if !missing.is_empty() {
+            return Err(BeduError::Config(tr!(
+                cookie_missing_keys,
+                missing.join(", ")
+            )));
+        }
+        Ok(self)
+    }
+
+    /// 字段的值
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 按顺序遍历全部字段
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    fn insert(&mut self, name: &str, value: &str) {
+        // 名称中不能有空白，多半是误粘贴的其他内容
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return;
+        }
+        match self.pairs.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.pairs.push((name.to_string(), value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Cookie {
+    type Err = BeduError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_curl(input: &str) -> bool {
+    input.split_whitespace().next().is_some_and(|first| {
+        first.eq_ignore_ascii_case("curl") || first.eq_ignore_ascii_case("curl.exe")
+    })
+}
+
+/// 请求头形式：有以 `Cookie:` 开头的行时取这些行，否则整段内容都是 Cookie
+fn header_cookies(input: &str) -> Vec<String> {
+    let headers: Vec<String> = input
+        .lines()
+        .filter_map(|line| strip_cookie_header(line.trim()))
+        .map(str::to_string)
+        .collect();
+    if !headers.is_empty() {
+        return headers;
+    }
+    // 整段被引号包住时去掉引号
+    let unquoted = ['"', '\'']
+        .into_iter()
+        .find_map(|quote| input.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(input);
+    vec![unquoted.to_string()]
+}
+
+/// 去掉 `Cookie:` 请求头名称（不区分大小写），不是 Cookie 请求头时返回 None
+fn strip_cookie_header(line: &str) -> Option<&str> {
+    let (name, value) = line.split_once(':')?;
+    name.trim().eq_ignore_ascii_case("cookie").then_some(value)
+}
+
+/// curl 命令中 `-H 'Cookie: ...'` 和 `-b 'a=1'` 指定的 Cookie
+fn curl_cookies(input: &str) -> Vec<String> {
+    // “复制为 cURL (cmd)”用 `^` 转义，行尾的 `^` 表示续行
+    let input = if input.contains("^\"") {
+        unescape_cmd(input)
+    } else {
+        input.to_string()
+    };
+
+    let mut cookies = Vec::new();
+    let mut args = split_shell(&input).into_iter();
+    while let Some(arg) = args.next() {
+        let header = match arg.as_str() {
+            "-H" | "--header" => args.next(),
+            "-b" | "--cookie" => {
+                // 不含 `=` 时是 Cookie 文件路径
+                cookies.extend(args.next().filter(|value| value.contains('=')));
+                continue;
+            }
+            _ => arg.strip_prefix("-H").map(str::to_string),
+        };
+        if let Some(value) = header.as_deref().and_then(strip_cookie_header) {
+            cookies.push(value.to_string());
+        }
+    }
+    cookies
+}
+
+fn unescape_cmd(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' => output.extend(chars.next().filter(|next| *next != '\n' && *next != '\r')),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// 按 bash 的规则拆分命令行：支持单引号、双引号、`$'...'`、反斜杠转义和续行
+fn split_shell(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(current.take()),
+            '\\' => match chars.next() {
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some('\n') | None => {}
+                Some(next) => current.get_or_insert_default().push(next),
+            },
+            '\'' => {
+                let arg = current.get_or_insert_default();
+                arg.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                let arg = current.get_or_insert_default();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\' | '$' | '`')) => arg.push(next),
+                            Some('\n') | None => {}
+                            Some(next) => {
+                                arg.push('\\');
+                                arg.push(next);
+                            }
+                        },
+                        c => arg.push(c),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let arg = current.get_or_insert_default();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' => break,
+                        '\\' => unescape_ansi_c(&mut chars, arg),
+                        c => arg.push(c),
+                    }
+                }
+            }
+            c => current.get_or_insert_default().push(c),
+        }
+    }
+    args.extend(current);
+    args
+}
+
+/// `$'...'` 中反斜杠后的转义序列，浏览器用它表示非 ASCII 字符和控制字符
+fn unescape_ansi_c(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, arg: &mut String) {
+    let hex = |chars: &mut std::iter::Peekable<std::str::Chars<'_>>, len: usize| {
+        let digits: String = std::iter::from_fn(|| chars.next_if(char::is_ascii_hexdigit))
+            .take(len)
+            .collect();
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+    };
+    match chars.next() {
+        Some('n') => arg.push('\n'),
+        Some('t') => arg.push('\t'),
+        Some('r') => arg.push('\r'),
+        Some('x') => arg.extend(hex(chars, 2)),
+        Some('u') => arg.extend(hex(chars, 4)),
+        Some('U') => arg.extend(hex(chars, 8)),
+        Some(c) => arg.push(c),
+        None => {}
+    }
+}
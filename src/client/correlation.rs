@@ -0,0 +1,23 @@
+use std::future::Future;
+
+/// 携带关联ID的请求头
+pub const CORRELATION_HEADER: &str = "X-Request-Id";
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// 生成新的关联ID（16 位十六进制）
+pub fn new_correlation_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+/// 当前任务的关联ID，不在 [`with_correlation_id`] 范围内时为 None
+pub fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// 在指定关联ID的范围内执行，期间发出的请求都会带上该ID
+pub async fn with_correlation_id<F: Future>(id: String, future: F) -> F::Output {
+    CORRELATION_ID.scope(id, future).await
+}
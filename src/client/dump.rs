@@ -0,0 +1,47 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::client::fixture::slug;
+
+/// 将每个接口的原始响应体写入目录，便于事后排查解析失败
+#[derive(Debug)]
+pub(crate) struct ResponseDump {
+    dir: PathBuf,
+    sequence: AtomicUsize,
+}
+
+impl ResponseDump {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            sequence: AtomicUsize::new(0),
+        }
+    }
+
+    /// 写入一个响应，文件名包含时间戳、序号、接口名和状态码
+    pub(crate) fn write(&self, path: &str, status: u16, body: &str) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| anyhow!(tr!(create_dump_dir_failed, self.dir.display(), e)))?;
+
+        let extension = if serde_json::from_str::<serde_json::Value>(body).is_ok() {
+            "json"
+        } else {
+            "txt"
+        };
+        let file = self.dir.join(format!(
+            "{}-{:05}-{}-{}.{}",
+            Local::now().format("%Y%m%dT%H%M%S%.3f"),
+            self.sequence.fetch_add(1, Ordering::SeqCst),
+            slug(path),
+            status,
+            extension
+        ));
+        fs::write(&file, body).map_err(|e| anyhow!(tr!(write_dump_failed, file.display(), e)))?;
+
+        Ok(file)
+    }
+}
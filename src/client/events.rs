@@ -0,0 +1,135 @@
+use chrono::{DateTime, FixedOffset, Local};
+use serde::Serialize;
+use std::io::Write;
+use tokio::sync::broadcast;
+
+use crate::api::TaskItem;
+use crate::client::{ClaimSettings, ClaimSummary, ClaimedTask, DrainedTask};
+
+/// 认领过程中产生的事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ClaimEvent {
+    /// 开始一次认领尝试
+    AttemptStarted {
+        attempt: i32,
+        claimed: i32,
+        limit: i32,
+    },
+    /// 获取到任务列表，`latency_ms` 为列表请求耗时
+    PoolFetched {
+        attempt: i32,
+        pool_size: usize,
+        latency_ms: u64,
+    },
+    /// 任务池中出现了上次获取时没有的任务，`tasks` 只包含新出现的任务；
+    /// 启动或修改筛选项后的第一次获取只作为比较的基准，不发出此事件
+    NewTasksAppeared { attempt: i32, tasks: Vec<TaskItem> },
+    /// 此前出现过、未被自己认领的任务从任务池中消失，通常是被他人认领
+    TasksDrained {
+        attempt: i32,
+        tasks: Vec<DrainedTask>,
+    },
+    /// 只监视模式下发现新的符合条件的任务
+    TasksSpotted { attempt: i32, tasks: Vec<TaskItem> },
+    /// 待完成任务数达到上限，跳过本次认领
+    PendingLimitReached {
+        attempt: i32,
+        pending: i32,
+        max_pending: i32,
+    },
+    /// 认领成功，`task_ids` 只包含认领成功的任务
+    Claimed {
+        attempt: i32,
+        task_ids: Vec<String>,
+        /// 本次认领请求的任务数，包括认领失败的
+        requested: usize,
+        count: i32,
+        total: i32,
+    },
+    /// 单个任务认领成功，附带任务详情
+    TaskClaimed { task: Box<ClaimedTask> },
+    /// 接口拒绝认领
+    ClaimFailed {
+        attempt: i32,
+        task_ids: Vec<String>,
+        errno: i32,
+        errmsg: String,
+    },
+    /// 认领数达到认领限制，认领循环即将结束
+    LimitReached { total: i32, limit: i32 },
+    /// 接口返回未登录，Cookie 可能已过期，每次运行只发出一次
+    CookieExpired { message: String },
+    /// 认领限制在运行中被修改
+    LimitChanged { limit: i32 },
+    /// 轮询间隔、筛选项或各项上限在运行中被修改
+    SettingsChanged { settings: ClaimSettings },
+    /// 请求重新加载配置，见 [`AutoClaimer::request_reload`](crate::client::AutoClaimer::request_reload)
+    ReloadRequested,
+    /// 被手动暂停
+    Paused,
+    /// 从手动暂停中恢复
+    Unpaused,
+    /// 待完成任务过多，暂停认领
+    Dormant { attempt: i32, errno: i32 },
+    /// 待完成任务已清空，恢复认领
+    Resumed { pending: i32 },
+    /// 今日认领数达到每日上限，暂停到次日 0 点
+    DailyLimitReached {
+        claimed_today: i32,
+        daily_limit: i32,
+        resume_at: DateTime<FixedOffset>,
+    },
+    /// 最近一小时认领数达到每小时上限，暂停到窗口内有空余额度
+    HourlyLimitReached {
+        claimed_last_hour: i32,
+        max_claims_per_hour: u32,
+        resume_at: DateTime<FixedOffset>,
+    },
+    /// 今天不是工作日，暂停到下一个工作日
+    NonWorkingDay { resume_at: DateTime<FixedOffset> },
+    /// 不在工作时段内，暂停到下一个时段开始
+    OutsideActiveHours { resume_at: DateTime<FixedOffset> },
+    /// 不在定时认领时段内，暂停到下一次触发
+    AwaitingCronTrigger { resume_at: DateTime<FixedOffset> },
+    /// 请求或解析出错
+    Error { attempt: i32, message: String },
+    /// 自动认领结束
+    Finished { summary: ClaimSummary },
+    /// 自动认领因错误退出，如 Cookie 验证失败或连续失败次数用尽
+    Aborted { message: String },
+}
+
+/// 带时间戳的事件记录，用于序列化输出
+#[derive(Debug, Serialize)]
+pub struct EventRecord<'a> {
+    pub timestamp: DateTime<Local>,
+    #[serde(flatten)]
+    pub event: &'a ClaimEvent,
+}
+
+impl ClaimEvent {
+    /// 序列化为一行 JSON（不含换行符）
+    pub fn to_json_line(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&EventRecord {
+            timestamp: Local::now(),
+            event: self,
+        })
+    }
+}
+
+/// 将事件逐行以 NDJSON 格式写出，直到事件通道关闭
+pub async fn write_ndjson<W: Write>(mut receiver: broadcast::Receiver<ClaimEvent>, mut writer: W) {
+    loop {
+        match receiver.recv().await {
+            Ok(event) => {
+                if let Ok(line) = event.to_json_line() {
+                    let _ = writeln!(writer, "{}", line);
+                    let _ = writer.flush();
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
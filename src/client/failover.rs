@@ -0,0 +1,113 @@
+//! 多个服务器地址之间的故障切换
+//!
+//! 主地址之外可以配置若干备用地址（其他网关或镜像）。请求按配置顺序发往第一个可用的地址，连接失败时
+//! 将该地址标记为不可用并换下一个重试；不可用的地址冷却一段时间后重新参与选择，主地址恢复后请求自动
+//! 回到主地址。只有连接失败才切换，已发出的请求超时或服务器返回错误时不会重复发送。
+
+use crate::error::BeduError;
+use crate::tr;
+use reqwest::Url;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 连接失败的地址默认冷却 30 秒后再尝试
+pub const DEFAULT_FAILOVER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// 一个服务器地址的健康状况
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// 服务器基础URL
+    pub url: String,
+    /// 是否可用，冷却中的地址为 false
+    pub healthy: bool,
+    /// 连续连接失败的次数
+    pub failures: u32,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    failures: u32,
+    /// 冷却结束的时间，之前不优先选择该地址
+    down_until: Option<Instant>,
+}
+
+/// 主地址和备用地址，按配置顺序排列
+pub(crate) struct Endpoints {
+    urls: Vec<String>,
+    health: Mutex<Vec<Health>>,
+    cooldown: Duration,
+}
+
+impl Endpoints {
+    /// 地址按 [`Url`] 规范化（如主机名转为小写），以便与请求地址比较前缀
+    pub(crate) fn new(
+        primary: &str,
+        fallbacks: &[String],
+        cooldown: Duration,
+    ) -> Result<Self, BeduError> {
+        let urls = std::iter::once(primary)
+            .chain(fallbacks.iter().map(String::as_str))
+            .map(|url| {
+                Url::parse(url)
+                    .map(|parsed| parsed.as_str().trim_end_matches('/').to_string())
+                    .map_err(|_| BeduError::Config(tr!(invalid_server_url, url)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let health = urls.iter().map(|_| Health::default()).collect();
+        Ok(Self {
+            urls,
+            health: Mutex::new(health),
+            cooldown,
+        })
+    }
+
+    /// 主地址，请求地址以它开头时才会切换
+    pub(crate) fn primary(&self) -> &str {
+        &self.urls[0]
+    }
+
+    pub(crate) fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    /// 本次请求尝试地址的顺序：可用的按配置顺序在前，冷却中的按冷却结束时间在后
+    pub(crate) fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().expect("endpoint health mutex poisoned");
+        let (mut up, mut down): (Vec<usize>, Vec<usize>) = (0..self.urls.len())
+            .partition(|&i| health[i].down_until.is_none_or(|until| until <= now));
+        down.sort_by_key(|&i| health[i].down_until);
+        up.append(&mut down);
+        up
+    }
+
+    /// 记录请求成功，返回该地址之前是否处于不可用状态
+    pub(crate) fn mark_up(&self, index: usize) -> bool {
+        let mut health = self.health.lock().expect("endpoint health mutex poisoned");
+        let was_down = health[index].failures > 0;
+        health[index] = Health::default();
+        was_down
+    }
+
+    /// 记录连接失败，该地址进入冷却
+    pub(crate) fn mark_down(&self, index: usize) {
+        let mut health = self.health.lock().expect("endpoint health mutex poisoned");
+        health[index].failures += 1;
+        health[index].down_until = Some(Instant::now() + self.cooldown);
+    }
+
+    /// 各地址的健康状况，按配置顺序排列
+    pub(crate) fn status(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        let health = self.health.lock().expect("endpoint health mutex poisoned");
+        self.urls
+            .iter()
+            .zip(health.iter())
+            .map(|(url, health)| EndpointStatus {
+                url: url.clone(),
+                healthy: health.down_until.is_none_or(|until| until <= now),
+                failures: health.failures,
+            })
+            .collect()
+    }
+}
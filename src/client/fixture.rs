@@ -0,0 +1,162 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// HTTP 夹具模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// 将每次请求和响应录制到目录中，每个请求一个 JSON 文件
+    Record(PathBuf),
+    /// 从目录中回放录制的响应，不访问网络
+    Replay(PathBuf),
+}
+
+/// 一次录制的请求与响应，不包含 Cookie 等请求头
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exchange {
+    pub method: String,
+    /// 去掉服务器地址后的路径和查询参数
+    pub path: String,
+    /// JSON 请求体，GET 或 multipart 请求为 None
+    pub request: Option<Value>,
+    pub status: u16,
+    /// 原始响应体
+    pub response: String,
+}
+
+impl Exchange {
+    fn key(&self) -> String {
+        request_key(&self.method, &self.path, self.request.as_ref())
+    }
+}
+
+/// 请求的匹配键：方法、路径和请求体都相同才视为同一个请求
+fn request_key(method: &str, path: &str, request: Option<&Value>) -> String {
+    match request {
+        Some(request) => format!("{} {} {}", method, path, request),
+        None => format!("{} {}", method, path),
+    }
+}
+
+/// 录制或回放中的夹具
+#[derive(Debug)]
+pub(crate) enum Fixtures {
+    Record {
+        dir: PathBuf,
+        sequence: AtomicUsize,
+    },
+    Replay {
+        /// 每个请求的剩余响应，按录制顺序排列
+        exchanges: Mutex<HashMap<String, VecDeque<Exchange>>>,
+    },
+}
+
+impl Fixtures {
+    /// 按模式准备夹具：录制时创建目录，回放时加载目录下的全部文件
+    pub(crate) fn open(mode: &FixtureMode) -> Result<Self> {
+        match mode {
+            FixtureMode::Record(dir) => {
+                fs::create_dir_all(dir)
+                    .map_err(|e| anyhow!(tr!(create_fixture_dir_failed, dir.display(), e)))?;
+                Ok(Self::Record {
+                    dir: dir.clone(),
+                    sequence: AtomicUsize::new(next_sequence(dir)?),
+                })
+            }
+            FixtureMode::Replay(dir) => {
+                let mut exchanges: HashMap<String, VecDeque<Exchange>> = HashMap::new();
+                for path in fixture_files(dir)? {
+                    let content = fs::read_to_string(&path)?;
+                    let exchange: Exchange = serde_json::from_str(&content)
+                        .map_err(|e| anyhow!(tr!(parse_fixture_failed, path.display(), e)))?;
+                    exchanges
+                        .entry(exchange.key())
+                        .or_default()
+                        .push_back(exchange);
+                }
+                Ok(Self::Replay {
+                    exchanges: Mutex::new(exchanges),
+                })
+            }
+        }
+    }
+
+    /// 回放模式下查找请求对应的响应，录制模式下返回 None
+    ///
+    /// 同一请求按录制顺序依次返回，用完后重复最后一个响应，便于回放轮询。
+    pub(crate) fn replay(
+        &self,
+        method: &Method,
+        path: &str,
+        request: Option<&Value>,
+    ) -> Option<Result<Exchange>> {
+        let Self::Replay { exchanges } = self else {
+            return None;
+        };
+
+        let key = request_key(method.as_str(), path, request);
+        let mut exchanges = exchanges.lock().expect("fixture mutex poisoned");
+        let result = match exchanges.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => Ok(queue.pop_front().expect("queue is not empty")),
+            Some(queue) if !queue.is_empty() => Ok(queue[0].clone()),
+            _ => Err(anyhow!(tr!(fixture_not_found, key))),
+        };
+        Some(result)
+    }
+
+    /// 录制模式下保存一次请求与响应
+    pub(crate) fn record(&self, exchange: &Exchange) -> Result<()> {
+        let Self::Record { dir, sequence } = self else {
+            return Ok(());
+        };
+
+        let sequence = sequence.fetch_add(1, Ordering::SeqCst);
+        let path = dir.join(format!(
+            "{:05}-{}-{}.json",
+            sequence,
+            exchange.method.to_ascii_lowercase(),
+            slug(&exchange.path)
+        ));
+        fs::write(&path, serde_json::to_vec_pretty(exchange)?)
+            .map_err(|e| anyhow!(tr!(write_fixture_failed, path.display(), e)))?;
+
+        Ok(())
+    }
+}
+
+/// 目录下的夹具文件，按文件名（即录制顺序）排序
+fn fixture_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| anyhow!(tr!(read_fixture_dir_failed, dir.display(), e)))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+/// 继续录制到已有目录时接着之前的序号
+fn next_sequence(dir: &Path) -> Result<usize> {
+    Ok(fixture_files(dir)?
+        .iter()
+        .filter_map(|path| path.file_name()?.to_str()?.split('-').next()?.parse().ok())
+        .map(|sequence: usize| sequence + 1)
+        .max()
+        .unwrap_or(0))
+}
+
+/// 将路径转换为可读的文件名片段
+pub(crate) fn slug(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    path.trim_start_matches("/edushop/")
+        .trim_matches('/')
+        .replace('/', "_")
+}
@@ -1,33 +1,674 @@
+use crate::tr;
 use anyhow::{Result, anyhow};
-use log::debug;
-use reqwest::Client;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::StatusCode;
+use reqwest::header::{
+    ACCEPT, COOKIE, ETAG, HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, ORIGIN, REFERER, USER_AGENT,
+};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Method, Request, RequestBuilder, Response};
 use serde_json::{Value, json};
 use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tracing::info;
+use tracing::{debug, instrument, warn};
 
-use crate::api::{ClaimResponse, TaskListResponse, UserInfoResponse};
+use crate::api::{
+    ApiResponse, ClaimResponse, ClueDetailResponse, LabelResponse, MyTaskListResponse,
+    ProduceAnswerRequest, ProduceAnswerResponse, QuestionContent, TaskListResponse, UploadResponse,
+    UploadTokenResponse, UserInfoResponse,
+};
+use crate::client::audit::AuditLog;
+use crate::client::correlation::{CORRELATION_HEADER, current_correlation_id};
+use crate::client::dump::ResponseDump;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::failover::{DEFAULT_FAILOVER_COOLDOWN, EndpointStatus, Endpoints};
+use crate::client::fixture::{Exchange, FixtureMode, Fixtures};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::middleware::{Middleware, Next};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::token::{Token, TokenState};
+use crate::client::user_agent::{UserAgentPool, UserAgentRotation};
+use crate::error::BeduError;
+
+/// 默认的浏览器 User-Agent
+const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// 连接池与 TCP 保活设置，默认值与 reqwest 相同
+///
+/// 高频轮询时同一个连接会被反复复用，省去每次建立连接和 TLS 握手的时间；空闲超时应长于轮询间隔，
+/// 否则每次轮询都要重新连接。
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolOptions {
+    /// 每个主机最多保留的空闲连接数，None 表示不限
+    pub max_idle_per_host: Option<usize>,
+    /// 空闲连接保留多久后关闭，None 表示一直保留
+    pub idle_timeout: Option<Duration>,
+    /// TCP 保活探测的空闲时间，None 时不启用；可以及早发现被 NAT 或防火墙静默断开的连接
+    pub tcp_keepalive: Option<Duration>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: None,
+            idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(15)),
+        }
+    }
+}
 
 /// HTTP客户端，封装了与百度教育API的所有交互
 pub struct HttpClient {
     client: Client,
+    base_url: String,
+    /// 只随发往平台接口的请求发送，不放入默认请求头
+    cookie: HeaderValue,
+    audit_log: Option<AuditLog>,
+    /// 按请求轮换时使用的 User-Agent 池
+    user_agents: Option<UserAgentPool>,
+    /// 录制或回放请求的夹具
+    fixtures: Option<Fixtures>,
+    /// 原始响应转储
+    response_dump: Option<ResponseDump>,
+    /// 请求中间件，按添加顺序由外到内执行
+    #[cfg(not(target_arch = "wasm32"))]
+    middlewares: Vec<Arc<dyn Middleware>>,
+    /// 每个请求附带的动态令牌
+    #[cfg(not(target_arch = "wasm32"))]
+    tokens: Vec<TokenState>,
+    /// 主地址和备用地址，未配置备用地址时为 None
+    #[cfg(not(target_arch = "wasm32"))]
+    endpoints: Option<Endpoints>,
+    /// 是否对任务列表发送条件请求
+    conditional_requests: bool,
+    /// 按请求地址缓存的任务列表及其校验值，用于条件请求
+    list_cache: Mutex<HashMap<String, CachedList>>,
+}
+
+/// 上次获取的任务列表及服务器返回的 `ETag`、`Last-Modified`
+struct CachedList {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    response: TaskListResponse,
+}
+
+/// [`HttpClient::fetch_audit_task_list`] 的结果
+#[derive(Debug, Clone)]
+pub struct TaskListFetch {
+    /// 任务列表，未变化时为上次获取的结果
+    pub response: TaskListResponse,
+    /// 任务池自上次获取以来是否有变化；服务器返回 304 时为 false
+    pub modified: bool,
+}
+
+/// HTTP客户端构建器，统一配置请求头、User-Agent、超时和代理
+///
+/// ```rust,no_run
+/// use bedu_claim::client::HttpClient;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), bedu_claim::BeduError> {
+/// let client = HttpClient::builder("https://easylearn.baidu.com", "your_cookie_here")
+///     .referer("https://easylearn.baidu.com/edushop/")
+///     .timeout(Duration::from_secs(5))
+///     .proxy("http://127.0.0.1:7890")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HttpClientBuilder {
     base_url: String,
     cookie: String,
+    user_agent: String,
+    user_agents: Option<UserAgentPool>,
+    referer: Option<String>,
+    origin: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    proxy: Option<String>,
+    audit_log: Option<PathBuf>,
+    fixtures: Option<FixtureMode>,
+    dump_responses: Option<PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    middlewares: Vec<Arc<dyn Middleware>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    tokens: Vec<Token>,
+    #[cfg(not(target_arch = "wasm32"))]
+    fallback_urls: Vec<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    failover_cooldown: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    resolve: Vec<(String, IpAddr)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pool: PoolOptions,
+    #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+    compression: bool,
+    conditional_requests: bool,
+}
+
+impl HttpClientBuilder {
+    /// 覆盖默认的 User-Agent
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// 从 User-Agent 池中轮换选取，优先于 [`HttpClientBuilder::user_agent`]
+    pub fn user_agent_pool(mut self, pool: UserAgentPool) -> Self {
+        self.user_agents = Some(pool);
+        self
+    }
+
+    /// 每个请求附带的 Referer
+    pub fn referer(mut self, referer: impl Into<String>) -> Self {
+        self.referer = Some(referer.into());
+        self
+    }
+
+    /// 每个请求附带的 Origin
+    pub fn origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// 添加默认请求头，同名时覆盖内置的请求头
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// 单个请求的超时时间，默认 10 秒
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 通过代理发送所有请求，如 `http://127.0.0.1:7890`
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// 是否请求压缩的响应（gzip、brotli）并自动解压，默认启用（需 `compression` 特性）
+    ///
+    /// 浏览器中由 fetch 自动协商压缩，不需要此设置。
+    #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// 获取任务列表时是否发送条件请求，默认启用
+    ///
+    /// 服务器返回 `ETag` 或 `Last-Modified` 时，下次请求同一列表附带 `If-None-Match` 或
+    /// `If-Modified-Since`，返回 304 时直接使用上次的结果，不再解析，见
+    /// [`HttpClient::fetch_audit_task_list`]。服务器不支持时没有影响；录制或回放夹具时不发送。
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.conditional_requests = enabled;
+        self
+    }
+
+    /// 连接池与 TCP 保活设置，见 [`PoolOptions`]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool(mut self, pool: PoolOptions) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// 不经过 DNS，将主机名直接解析为指定的 IP；同一主机名多次指定时依次尝试各个地址
+    ///
+    /// 端口仍取自请求地址。通过代理发送时由代理解析主机名，此设置不生效。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(mut self, host: impl Into<String>, ip: IpAddr) -> Self {
+        self.resolve.push((host.into(), ip));
+        self
+    }
+
+    /// 启用认领审计日志，见 [`HttpClient::with_audit_log`]
+    pub fn audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// 录制或回放接口请求，见 [`FixtureMode`]
+    ///
+    /// 录制的文件只包含路径、请求体和响应体，不含 Cookie，可以直接作为回归测试的数据；
+    /// 图片下载不经过夹具。
+    pub fn fixtures(mut self, mode: FixtureMode) -> Self {
+        self.fixtures = Some(mode);
+        self
+    }
+
+    /// 将每个接口的原始响应体写入目录，文件名包含时间戳和接口名
+    pub fn dump_responses(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dump_responses = Some(dir.into());
+        self
+    }
+
+    /// 添加请求中间件，先添加的在外层，最先看到请求、最后看到响应
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// 为每个请求附加动态令牌（如 CSRF 令牌），自动获取、缓存和刷新，见 [`Token`]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn token(mut self, token: Token) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    /// 添加备用服务器地址（其他网关或镜像），主地址连接失败时按添加顺序切换
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fallback_url(mut self, url: impl Into<String>) -> Self {
+        self.fallback_urls.push(url.into());
+        self
+    }
+
+    /// 连接失败的地址暂停使用多久，之后重新优先使用主地址，默认 30 秒
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn failover_cooldown(mut self, cooldown: Duration) -> Self {
+        self.failover_cooldown = cooldown;
+        self
+    }
+
+    /// 构建HTTP客户端，请求头非法、代理或备用地址无效、夹具目录无法读取或 Cookie 中没有令牌字段时返回错误
+    pub fn build(self) -> Result<HttpClient, BeduError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+        let mut cookie = header_value("Cookie", &self.cookie)?;
+        cookie.set_sensitive(true);
+
+        if let Some(referer) = &self.referer {
+            headers.insert(REFERER, header_value("Referer", referer)?);
+        }
+        if let Some(origin) = &self.origin {
+            headers.insert(ORIGIN, header_value("Origin", origin)?);
+        }
+        for (name, value) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| BeduError::Config(tr!(invalid_header_name, name, e)))?;
+            headers.insert(header_name, header_value(name, value)?);
+        }
+
+        // 按会话轮换时在此选定，按请求轮换时由 HttpClient::request 逐个设置
+        let user_agent = match &self.user_agents {
+            Some(pool) => pool.pick().to_string(),
+            None => self.user_agent,
+        };
+        let user_agents = self
+            .user_agents
+            .filter(|pool| pool.rotation() == UserAgentRotation::PerRequest);
+
+        let builder = Client::builder()
+            .user_agent(user_agent)
+            .default_headers(headers);
+        // 浏览器中由 fetch 负责超时和代理
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut builder = builder
+            .timeout(self.timeout)
+            .pool_idle_timeout(self.pool.idle_timeout)
+            .pool_max_idle_per_host(self.pool.max_idle_per_host.unwrap_or(usize::MAX))
+            .tcp_keepalive(self.pool.tcp_keepalive);
+        #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+        {
+            builder = builder.gzip(self.compression).brotli(self.compression);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        // 端口会被替换为请求地址中的端口
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut overrides: HashMap<&str, Vec<SocketAddr>> = HashMap::new();
+            for (host, ip) in &self.resolve {
+                overrides
+                    .entry(host)
+                    .or_default()
+                    .push(SocketAddr::new(*ip, 0));
+            }
+            for (host, addrs) in overrides {
+                builder = builder.resolve_to_addrs(host, &addrs);
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let endpoints = (!self.fallback_urls.is_empty())
+            .then(|| Endpoints::new(&self.base_url, &self.fallback_urls, self.failover_cooldown))
+            .transpose()?;
+
+        Ok(HttpClient {
+            client: builder.build()?,
+            base_url: self.base_url,
+            cookie,
+            audit_log: self.audit_log.map(AuditLog::new),
+            user_agents,
+            fixtures: self
+                .fixtures
+                .as_ref()
+                .map(Fixtures::open)
+                .transpose()
+                .map_err(|e| BeduError::Config(e.to_string()))?,
+            response_dump: self.dump_responses.map(ResponseDump::new),
+            #[cfg(not(target_arch = "wasm32"))]
+            middlewares: self.middlewares,
+            #[cfg(not(target_arch = "wasm32"))]
+            tokens: self
+                .tokens
+                .into_iter()
+                .map(|token| TokenState::new(token, &self.cookie))
+                .collect::<Result<_, _>>()?,
+            #[cfg(not(target_arch = "wasm32"))]
+            endpoints,
+            conditional_requests: self.conditional_requests && self.fixtures.is_none(),
+            list_cache: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+/// 将配置值转换为请求头的值
+fn header_value(name: &str, value: &str) -> Result<HeaderValue, BeduError> {
+    HeaderValue::from_str(value).map_err(|e| BeduError::Config(tr!(invalid_header_value, name, e)))
 }
 
 impl HttpClient {
     /// 创建新的HTTP客户端实例
+    ///
+    /// # Panics
+    ///
+    /// 底层客户端构建失败时 panic，库中使用请改用 [`HttpClient::try_new`]。
     pub fn new(base_url: String, cookie: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .build()
-            .expect("Failed to build HTTP client");
+        Self::try_new(base_url, cookie).expect("Failed to build HTTP client")
+    }
 
-        Self {
-            client,
-            base_url,
-            cookie,
+    /// 创建新的HTTP客户端实例，构建失败（如代理或 TLS 配置错误）时返回错误
+    pub fn try_new(base_url: String, cookie: String) -> Result<Self, BeduError> {
+        Self::builder(base_url, cookie).build()
+    }
+
+    /// 创建HTTP客户端构建器，可自定义请求头、User-Agent、超时和代理
+    pub fn builder(base_url: impl Into<String>, cookie: impl Into<String>) -> HttpClientBuilder {
+        HttpClientBuilder {
+            base_url: base_url.into(),
+            cookie: cookie.into(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            user_agents: None,
+            referer: None,
+            origin: None,
+            headers: Vec::new(),
+            timeout: Duration::from_secs(10),
+            proxy: None,
+            audit_log: None,
+            fixtures: None,
+            dump_responses: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            middlewares: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            tokens: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            fallback_urls: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            failover_cooldown: DEFAULT_FAILOVER_COOLDOWN,
+            #[cfg(not(target_arch = "wasm32"))]
+            resolve: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            pool: PoolOptions::default(),
+            #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+            compression: true,
+            conditional_requests: true,
+        }
+    }
+
+    /// 启用认领审计日志，每次认领请求及完整响应追加写入 JSONL 文件
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.audit_log = Some(AuditLog::new(path));
+        self
+    }
+
+    /// 创建携带 Cookie 的请求，按请求轮换 User-Agent 时为每个请求重新选取
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self
+            .external_request(method, url)
+            .header(COOKIE, self.cookie.clone());
+        // 浏览器禁止脚本设置 Cookie 请求头，改为携带浏览器中已登录的 Cookie
+        #[cfg(target_arch = "wasm32")]
+        let request = request.fetch_credentials_include();
+        request
+    }
+
+    /// 创建发往上传地址等其他服务器的请求，不携带 Cookie，发送时也不附加动态令牌
+    fn external_request(&self, method: Method, url: &str) -> RequestBuilder {
+        let mut request = self.client.request(method, url);
+        if let Some(pool) = &self.user_agents {
+            request = request.header(USER_AGENT, pool.pick());
+        }
+        match current_correlation_id() {
+            Some(id) => request.header(CORRELATION_HEADER, id),
+            None => request,
+        }
+    }
+
+    /// 附加动态令牌后发送请求，服务器返回 401 或 403 时刷新令牌重试一次
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send(&self, mut request: Request) -> Result<Response> {
+        // 令牌可能取自 Cookie，不携带 Cookie 的请求同样不附加
+        if self.tokens.is_empty() || !request.headers().contains_key(COOKIE) {
+            return self.dispatch(request).await;
+        }
+
+        self.apply_tokens(&mut request, false).await?;
+        // 流式请求体无法复制，不重试
+        let retry = request
+            .try_clone()
+            .filter(|_| self.tokens.iter().any(TokenState::refreshable));
+        let response = self.dispatch(request).await?;
+        match retry {
+            Some(mut retry)
+                if matches!(
+                    response.status(),
+                    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+                ) =>
+            {
+                debug!(status = %response.status(), "{}", tr!(token_refreshing, response.status()));
+                self.apply_tokens(&mut retry, true).await?;
+                self.dispatch(retry).await
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// 为请求附加动态令牌，`refresh` 为 true 时重新获取可刷新的令牌
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn apply_tokens(&self, request: &mut Request, refresh: bool) -> Result<()> {
+        for state in &self.tokens {
+            let value = state
+                .get(refresh, async |token| self.fetch_token(token).await)
+                .await?;
+            request.headers_mut().insert(state.header.clone(), value);
+        }
+        Ok(())
+    }
+
+    /// 请求令牌来源的地址并取出令牌，该请求不附加令牌
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn fetch_token(&self, token: &Token) -> Result<String> {
+        let url = token.source().url().unwrap_or_default();
+        let url = if url.starts_with('/') {
+            format!("{}{}", self.base_url, url)
+        } else {
+            url.to_string()
+        };
+        let request = self
+            .request(Method::GET, &url)
+            .header(ACCEPT, "*/*")
+            .build()?;
+        let body = self
+            .dispatch(request)
+            .await?
+            .error_for_status()
+            .map_err(|e| anyhow!(tr!(fetch_token_failed, token.header(), e)))?
+            .text()
+            .await?;
+        let value = token
+            .source()
+            .extract(&body)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| anyhow!(tr!(token_not_found, token.header(), url)))?;
+        debug!(header = token.header(), %url, "{}", tr!(token_fetched, token.header(), url));
+        Ok(value)
+    }
+
+    /// 经过中间件链发送请求；配置了备用地址时，连接失败后换下一个地址重试
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn dispatch(&self, request: Request) -> Result<Response> {
+        let next = Next::new(&self.client, &self.middlewares);
+        let Some(endpoints) = &self.endpoints else {
+            return next.run(request).await;
+        };
+        // 上传地址等其他服务器的请求不切换
+        let url = request.url().to_string();
+        let Some(path) = url.strip_prefix(endpoints.primary()) else {
+            return next.run(request).await;
+        };
+
+        let mut request = Some(request);
+        let mut last_error = None;
+        for index in endpoints.order() {
+            // 流式请求体无法复制，只尝试一次
+            let Some(mut current) = request.take() else {
+                break;
+            };
+            request = current.try_clone();
+            let endpoint = endpoints.url(index);
+            *current.url_mut() = reqwest::Url::parse(&format!("{}{}", endpoint, path))?;
+            match next.run(current).await {
+                Ok(response) => {
+                    if endpoints.mark_up(index) {
+                        info!(url = endpoint, "{}", tr!(endpoint_recovered, endpoint));
+                    }
+                    return Ok(response);
+                }
+                Err(e) if is_connect_error(&e) => {
+                    endpoints.mark_down(index);
+                    warn!(url = endpoint, error = %e, "{}", tr!(endpoint_unreachable, endpoint, e));
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
         }
+        Err(last_error.unwrap_or_else(|| anyhow!(tr!(invalid_server_url, url))))
+    }
+
+    /// 主地址和备用地址的健康状况，未配置备用地址时只有主地址
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn endpoint_status(&self) -> Vec<EndpointStatus> {
+        match &self.endpoints {
+            Some(endpoints) => endpoints.status(),
+            None => vec![EndpointStatus {
+                url: self.base_url.clone(),
+                healthy: true,
+                failures: 0,
+            }],
+        }
+    }
+
+    /// 丢弃缓存的动态令牌，下次请求时重新获取
+    ///
+    /// 服务器以 HTTP 401 或 403 拒绝时会自动刷新；接口改用业务错误码表示令牌失效时，
+    /// 调用方可在收到该错误码后调用本方法。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn invalidate_tokens(&self) {
+        for state in &self.tokens {
+            state.invalidate().await;
+        }
+    }
+
+    /// 通过浏览器 fetch 发送请求，wasm 下不支持中间件
+    #[cfg(target_arch = "wasm32")]
+    async fn send(&self, request: Request) -> Result<Response> {
+        Ok(self.client.execute(request).await?)
+    }
+
+    /// 发送请求并读取状态码和响应体，配置了夹具时录制或回放，配置了转储时写入原始响应
+    async fn execute(&self, request: RequestBuilder) -> Result<(u16, String)> {
+        let (status, _, body) = self.execute_with_headers(request).await?;
+        Ok((status, body))
+    }
+
+    /// 同 [`Self::execute`]，同时返回响应头；回放夹具时响应头为空
+    async fn execute_with_headers(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<(u16, HeaderMap, String)> {
+        let request = request.build()?;
+        if self.fixtures.is_none() && self.response_dump.is_none() {
+            let response = self.send(request).await?;
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            return Ok((status, headers, response.text().await?));
+        }
+
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let body: Option<Value> = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice(bytes).ok());
+
+        if let Some(fixtures) = &self.fixtures
+            && let Some(exchange) = fixtures.replay(request.method(), &path, body.as_ref())
+        {
+            let exchange = exchange?;
+            debug!(%path, "{}", tr!(fixture_replayed, path));
+            return Ok((exchange.status, HeaderMap::new(), exchange.response));
+        }
+
+        let method = request.method().to_string();
+        let response = self.send(request).await?;
+        let headers = response.headers().clone();
+        let exchange = Exchange {
+            method,
+            path,
+            request: body,
+            status: response.status().as_u16(),
+            response: response.text().await?,
+        };
+
+        if let Some(dump) = &self.response_dump {
+            match dump.write(&exchange.path, exchange.status, &exchange.response) {
+                Ok(file) => {
+                    debug!(file = %file.display(), "{}", tr!(response_dumped, file.display()))
+                }
+                Err(e) => warn!(error = %e, "{}", tr!(dump_response_failed, e)),
+            }
+        }
+        if let Some(fixtures) = &self.fixtures
+            && let Err(e) = fixtures.record(&exchange)
+        {
+            warn!(error = %e, "{}", tr!(record_fixture_failed, e));
+        }
+
+        Ok((exchange.status, headers, exchange.response))
     }
 
     /// 获取审核任务列表
@@ -35,6 +676,19 @@ impl HttpClient {
         &self,
         options: &HashMap<String, Value>,
     ) -> Result<TaskListResponse> {
+        Ok(self.fetch_audit_task_list(options).await?.response)
+    }
+
+    /// 获取审核任务列表，并告知任务池自上次获取以来是否有变化
+    ///
+    /// 服务器支持 `ETag` 或 `Last-Modified` 时发送条件请求，返回 304 时不再解析，直接返回上次
+    /// 的结果并将 `modified` 设为 false，轮询时可据此跳过筛选。见
+    /// [`HttpClientBuilder::conditional_requests`]。
+    #[instrument(name = "bedu.list_tasks", skip_all)]
+    pub async fn fetch_audit_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<TaskListFetch> {
         let task_type = options
             .get("taskType")
             .and_then(|v| v.as_str())
@@ -55,30 +709,129 @@ impl HttpClient {
             self.base_url, task_type, pn, rn, clue_id, clue_type, step, subject
         );
 
-        debug!("请求任务列表: {}", url);
+        debug!(%url, "{}", tr!(request_task_list, url));
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Cookie", &self.cookie)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+        let cached = self
+            .conditional_requests
+            .then(|| self.list_cache().remove(&url))
+            .flatten();
+        let mut request = self.request(Method::GET, &url);
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let (status, headers, body) = self.execute_with_headers(request).await?;
+        if status == 304
+            && let Some(cached) = cached
+        {
+            debug!("{}", tr!(task_list_not_modified));
+            let response = cached.response.clone();
+            self.list_cache().insert(url, cached);
+            return Ok(TaskListFetch {
+                response,
+                modified: false,
+            });
+        }
+        debug!("{}", tr!(task_list_response, body));
+
+        let parsed: TaskListResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_task_list_failed, e)))?;
+
+        let etag = headers.get(ETAG).cloned();
+        let last_modified = headers.get(LAST_MODIFIED).cloned();
+        if self.conditional_requests
+            && parsed.errno == 0
+            && parsed.raw.is_none()
+            && (etag.is_some() || last_modified.is_some())
+        {
+            self.list_cache().insert(
+                url,
+                CachedList {
+                    etag,
+                    last_modified,
+                    response: parsed.clone(),
+                },
+            );
+        }
+
+        Ok(TaskListFetch {
+            response: parsed,
+            modified: true,
+        })
+    }
+
+    fn list_cache(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedList>> {
+        self.list_cache
+            .lock()
+            .expect("task list cache mutex poisoned")
+    }
+
+    /// 获取我的任务列表（已认领的任务及其状态）
+    ///
+    /// 支持的选项：`taskType`、`pn`、`rn`，以及可选的 `state` 状态筛选。
+    #[instrument(name = "bedu.my_tasks", skip_all)]
+    pub async fn get_my_task_list(
+        &self,
+        options: &HashMap<String, Value>,
+    ) -> Result<MyTaskListResponse> {
+        let task_type = options
+            .get("taskType")
+            .and_then(|v| v.as_str())
+            .unwrap_or("audittask");
+
+        let pn = options.get("pn").and_then(|v| v.as_i64()).unwrap_or(1);
+        let rn = options.get("rn").and_then(|v| v.as_i64()).unwrap_or(20);
 
-        let body = response.text().await?;
-        debug!("任务列表响应: {}", body);
+        let mut url = format!(
+            "{}/edushop/question/{}/mylist?pn={}&rn={}",
+            self.base_url, task_type, pn, rn
+        );
+        if let Some(state) = options.get("state").and_then(|v| v.as_i64()) {
+            url.push_str(&format!("&state={}", state));
+        }
 
-        let parsed: TaskListResponse = serde_json::from_str(&body)
-            .map_err(|e| anyhow!("解析任务列表响应失败: {}, body: {}", e, body))?;
+        debug!(%url, "{}", tr!(request_my_tasks, url));
+
+        let (_, body) = self.execute(self.request(Method::GET, &url)).await?;
+        debug!("{}", tr!(my_tasks_response, body));
+
+        let parsed: MyTaskListResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_my_tasks_failed, e)))?;
 
         Ok(parsed)
     }
 
     /// 认领审核任务
+    #[instrument(name = "bedu.claim", skip(self), fields(count = task_ids.len()))]
     pub async fn claim_audit_task(
         &self,
         task_ids: Vec<String>,
         task_type: &str,
+    ) -> Result<ClaimResponse> {
+        self.commit_tasks("claim", task_ids, task_type).await
+    }
+
+    /// 释放已认领的任务，将其退回任务池
+    #[instrument(name = "bedu.release", skip(self), fields(count = task_ids.len()))]
+    pub async fn release_task(
+        &self,
+        task_ids: Vec<String>,
+        task_type: &str,
+    ) -> Result<ClaimResponse> {
+        self.commit_tasks("release", task_ids, task_type).await
+    }
+
+    /// 向任务提交接口发送认领或释放请求，生产任务按线索ID提交
+    async fn commit_tasks(
+        &self,
+        action: &str,
+        task_ids: Vec<String>,
+        task_type: &str,
     ) -> Result<ClaimResponse> {
         let commit_type = if task_type == "producetask" {
             "producetaskcommit"
@@ -86,52 +839,228 @@ impl HttpClient {
             "audittaskcommit"
         };
 
-        let url = format!("{}/edushop/question/{}/claim", self.base_url, commit_type);
+        let url = format!(
+            "{}/edushop/question/{}/{}",
+            self.base_url, commit_type, action
+        );
+        let label = if action == "claim" {
+            tr!(claim_label)
+        } else {
+            tr!(release_label)
+        };
 
         let request_body = if task_type == "producetask" {
-            let clue_ids: Result<Vec<u64>, _> = task_ids.iter().map(|s| s.parse()).collect();
+            let clue_ids: Result<Vec<i64>, _> = task_ids.iter().map(|s| s.parse()).collect();
             json!({ "clueIDs": clue_ids? })
         } else {
-            let task_ids_parsed: Result<Vec<u64>, _> = task_ids.iter().map(|s| s.parse()).collect();
+            let task_ids_parsed: Result<Vec<i64>, _> = task_ids.iter().map(|s| s.parse()).collect();
             json!({ "taskIDs": task_ids_parsed? })
         };
 
-        debug!("认领请求: {} -> {}", url, request_body);
+        debug!(%url, body = %request_body, "{}", tr!(task_action_request, label, url, request_body));
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Cookie", &self.cookie)
-            .header("Content-Type", "application/json")
-            .header("Accept", "application/json")
-            .json(&request_body)
-            .send()
+        let (status, body) = self
+            .execute(self.request(Method::POST, &url).json(&request_body))
             .await?;
+        debug!("{}", tr!(task_action_response, label, body));
 
-        let body = response.text().await?;
-        debug!("认领响应: {}", body);
+        if let Some(audit_log) = &self.audit_log
+            && let Err(e) = audit_log.append_claim(&url, task_type, &request_body, status, &body)
+        {
+            warn!(
+                error = %e,
+                path = %audit_log.path().display(),
+                "{}",
+                tr!(write_audit_log_failed, e)
+            );
+        }
 
-        let parsed: ClaimResponse = serde_json::from_str(&body)
-            .map_err(|e| anyhow!("解析认领响应失败: {}, body: {}", e, body))?;
+        let parsed: ClaimResponse = ApiResponse::parse(&body)
+            .map_err(|e| anyhow!(tr!(parse_task_action_failed, label, e)))?;
+
+        Ok(parsed)
+    }
+
+    /// 提交审核结论，`approved` 为 false 时需附带驳回原因
+    #[instrument(name = "bedu.submit_audit", skip(self, reason))]
+    pub async fn submit_audit_result(
+        &self,
+        task_id: i64,
+        approved: bool,
+        reason: &str,
+    ) -> Result<ClaimResponse> {
+        let url = format!("{}/edushop/question/audittaskcommit/submit", self.base_url);
+        let request_body = json!({
+            "taskID": task_id,
+            "result": if approved { 1 } else { 2 },
+            "reason": reason,
+        });
+
+        debug!(%url, body = %request_body, "{}", tr!(submit_verdict_request, url, request_body));
+
+        let (_, body) = self
+            .execute(self.request(Method::POST, &url).json(&request_body))
+            .await?;
+        debug!("{}", tr!(verdict_response, body));
+
+        let parsed: ClaimResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_verdict_failed, e)))?;
+
+        Ok(parsed)
+    }
+
+    /// 提交生产任务的答案
+    #[instrument(name = "bedu.submit_answer", skip(self, content))]
+    pub async fn submit_produce_answer(
+        &self,
+        clue_id: i64,
+        content: &QuestionContent,
+    ) -> Result<ProduceAnswerResponse> {
+        let url = format!(
+            "{}/edushop/question/producetaskcommit/submit",
+            self.base_url
+        );
+        let request = ProduceAnswerRequest {
+            clue_id,
+            content: content.clone(),
+        };
+
+        debug!(%url, clue_id, "{}", tr!(submit_answer_request, url));
+
+        let (_, body) = self
+            .execute(self.request(Method::POST, &url).json(&request))
+            .await?;
+        debug!("{}", tr!(answer_response, body));
+
+        let parsed: ProduceAnswerResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_answer_failed, e)))?;
+
+        Ok(parsed)
+    }
+
+    /// 上传图片，返回上传后的访问地址
+    ///
+    /// 先获取上传凭证，再以 multipart 表单提交到凭证中的上传地址；上传地址可能属于其他服务器，
+    /// 提交时不携带 Cookie。
+    #[instrument(name = "bedu.upload_image", skip(self, bytes), fields(size = bytes.len()))]
+    pub async fn upload_image(&self, bytes: Vec<u8>, filename: &str) -> Result<String> {
+        let token_url = format!("{}/edushop/upload/token", self.base_url);
+
+        let (_, body) = self.execute(self.request(Method::GET, &token_url)).await?;
+        debug!("{}", tr!(upload_token_response, body));
+
+        let token: UploadTokenResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_upload_token_failed, e)))?;
+        let token = token
+            .into_result()
+            .map_err(|e| anyhow!(tr!(fetch_upload_token_failed, e)))?;
+
+        let part = Part::bytes(bytes)
+            .file_name(filename.to_string())
+            .mime_str(image_mime(filename))?;
+        let form = Form::new().text("token", token.token).part("file", part);
+
+        debug!(url = %token.upload_url, filename, "{}", tr!(uploading_image, filename));
+
+        let (_, body) = self
+            .execute(
+                self.external_request(Method::POST, &token.upload_url)
+                    .multipart(form),
+            )
+            .await?;
+        debug!("{}", tr!(upload_response, body));
+
+        let parsed: UploadResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_upload_failed, e)))?;
+        let data = parsed
+            .into_result()
+            .map_err(|e| anyhow!(tr!(upload_image_failed, e)))?;
+
+        Ok(data.url)
+    }
+
+    /// 下载图片等附件，返回原始字节
+    ///
+    /// 图片地址取自任务内容，可能属于任意第三方服务器，下载时不携带 Cookie。
+    #[instrument(name = "bedu.download", skip(self))]
+    pub async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        let request = self
+            .external_request(Method::GET, url)
+            .header(ACCEPT, "*/*")
+            .build()?;
+        let response = self.send(request).await?.error_for_status()?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// 获取线索详情，包含题干、选项、答案、解析和图片
+    #[instrument(name = "bedu.clue_detail", skip(self))]
+    pub async fn get_clue_detail(&self, clue_id: i64) -> Result<ClueDetailResponse> {
+        let url = format!(
+            "{}/edushop/question/clue/detail?clueID={}",
+            self.base_url, clue_id
+        );
+
+        debug!(%url, "{}", tr!(request_clue_detail, url));
+
+        let (_, body) = self.execute(self.request(Method::GET, &url)).await?;
+        debug!("{}", tr!(clue_detail_response, body));
+
+        let parsed: ClueDetailResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_clue_detail_failed, e)))?;
+
+        Ok(parsed)
+    }
+
+    /// 获取筛选标签（学科、学段、线索类型等可选项）
+    #[instrument(name = "bedu.labels", skip(self))]
+    pub async fn get_labels(&self, task_type: &str) -> Result<LabelResponse> {
+        let url = format!("{}/edushop/question/{}/label", self.base_url, task_type);
+
+        debug!(%url, "{}", tr!(request_labels, url));
+
+        let (_, body) = self.execute(self.request(Method::GET, &url)).await?;
+        debug!("{}", tr!(labels_response, body));
+
+        let parsed: LabelResponse =
+            ApiResponse::parse(&body).map_err(|e| anyhow!(tr!(parse_labels_failed, e)))?;
 
         Ok(parsed)
     }
 
     /// 获取用户信息
+    #[instrument(name = "bedu.user_info", skip_all)]
     pub async fn get_user_info(&self) -> Result<UserInfoResponse> {
         let url = format!("{}/edushop/user/common/info", self.base_url);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Cookie", &self.cookie)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
-
-        let body = response.text().await?;
-        let parsed: UserInfoResponse = serde_json::from_str(&body)?;
+        let (_, body) = self.execute(self.request(Method::GET, &url)).await?;
+        let parsed: UserInfoResponse = ApiResponse::parse(&body)?;
 
         Ok(parsed)
     }
 }
+
+/// 是否为连接失败（请求未发出），只有这种错误才切换到备用地址
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(reqwest::Error::is_connect)
+}
+
+/// 根据文件扩展名推断图片的 MIME 类型
+fn image_mime(filename: &str) -> &'static str {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
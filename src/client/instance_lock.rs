@@ -0,0 +1,83 @@
+//! 同一账号的单实例锁
+//!
+//! 两个认领器用同一账号同时运行会重复认领、互相消耗每日额度，启动时按账号加文件锁互斥。
+//! 锁由操作系统在进程退出时释放，进程崩溃也不会留下失效的锁。
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// 账号锁，持有期间同一账号的其他认领器无法获取，值被释放时解锁
+#[derive(Debug)]
+pub struct InstanceLock {
+    /// 文件锁随文件关闭释放
+    _file: File,
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    /// 获取 Cookie 对应账号的锁，已有其他进程持有时返回错误
+    pub fn acquire(cookie: &str) -> Result<Self> {
+        Self::acquire_at(lock_path(cookie))
+    }
+
+    /// 获取指定锁文件上的锁，成功后在文件中写入当前进程ID
+    pub fn acquire_at(path: PathBuf) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| anyhow!(tr!(open_lock_failed, path.display(), e)))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut pid = String::new();
+                let _ = file.read_to_string(&mut pid);
+                let pid = Some(pid.trim())
+                    .filter(|pid| !pid.is_empty())
+                    .unwrap_or("?");
+                return Err(anyhow!(tr!(instance_running, pid, path.display())));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(anyhow!(tr!(lock_failed, path.display(), e)));
+            }
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Self { _file: file, path })
+    }
+
+    /// 锁文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// 账号对应的锁文件路径，位于 `$XDG_RUNTIME_DIR`（未设置时为临时目录）下
+///
+/// Cookie 中有 `BDUSS` 时按其值区分账号，其余字段变化不影响；否则按整个 Cookie 区分。
+/// 文件名只包含哈希值，不会泄露 Cookie。
+pub fn lock_path(cookie: &str) -> PathBuf {
+    let account = cookie
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == "BDUSS")
+        .map_or(cookie.trim(), |(_, value)| value.trim());
+    let hash: String = Sha256::digest(account.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(format!("bedu-claim-{}.lock", hash))
+}
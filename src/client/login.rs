@@ -0,0 +1,290 @@
+//! 百度账号扫码登录（需 `qr-login` 特性）
+//!
+//! 流程与百度通行证网页上的扫码登录相同：
+//!
+//! 1. 请求 `/v2/api/getqrcode` 取得二维码签名 `sign`，二维码内容为带有该签名的 wappass 链接；
+//! 2. 长轮询 `/channel/unicast`，手机百度 App 扫码后返回“已扫码”，确认登录后返回临时凭据；
+//! 3. 用临时凭据请求 `/v3/login/main/qrbdusslogin`，通行证通过 `Set-Cookie` 下发 `BDUSS` 等 Cookie。
+//!
+//! 所有请求共用一个 Cookie 容器，登录完成后从中取出会发送给百度教育服务器的 Cookie。
+
+use crate::client::cookie::Cookie;
+use crate::error::BeduError;
+use crate::tr;
+use anyhow::{Result, anyhow};
+use rand::Rng;
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::Instant;
+use tracing::debug;
+
+/// 百度通行证地址
+pub const PASSPORT_URL: &str = "https://passport.baidu.com";
+
+/// 登录产品线标识
+const TPL: &str = "mn";
+
+/// 长轮询在服务端最多挂起约 30 秒，请求超时需要比它长
+const POLL_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// 扫码状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QrStatus {
+    /// 等待扫码
+    Waiting,
+    /// 已扫码，等待在手机上确认
+    Scanned,
+    /// 已确认登录，附带换取 Cookie 的临时凭据
+    Confirmed(String),
+    /// 在手机上取消了登录
+    Cancelled,
+}
+
+/// 一次扫码登录会话
+///
+/// ```rust,no_run
+/// use bedu_claim::client::login::QrLogin;
+/// use std::time::Duration;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let login = QrLogin::start().await?;
+/// println!("用百度 App 扫描: {}", login.qr_content());
+/// let cookie = login
+///     .wait(Duration::from_secs(180), |status| println!("{:?}", status))
+///     .await?;
+/// println!("Cookie: {}", cookie);
+/// # Ok(())
+/// # }
+/// ```
+pub struct QrLogin {
+    client: Client,
+    jar: Arc<Jar>,
+    passport_url: String,
+    server_url: Url,
+    gid: String,
+    sign: String,
+    image_url: String,
+}
+
+#[derive(Deserialize)]
+struct QrCodeResponse {
+    errno: i32,
+    #[serde(default)]
+    sign: String,
+    #[serde(default)]
+    imgurl: String,
+}
+
+#[derive(Deserialize)]
+struct UnicastResponse {
+    errno: i32,
+    #[serde(default)]
+    channel_v: String,
+}
+
+#[derive(Deserialize)]
+struct ChannelValue {
+    status: i32,
+    #[serde(default)]
+    v: String,
+}
+
+impl QrLogin {
+    /// 在百度通行证申请登录二维码，登录后取出发送给百度教育的 Cookie
+    pub async fn start() -> Result<Self> {
+        Self::start_with(PASSPORT_URL, "https://easylearn.baidu.com").await
+    }
+
+    /// 指定通行证地址和服务器地址，登录后取出浏览器会发送给 `server_url` 的 Cookie
+    pub async fn start_with(passport_url: &str, server_url: &str) -> Result<Self> {
+        let server_url =
+            Url::parse(server_url).map_err(|_| anyhow!(tr!(invalid_server_url, server_url)))?;
+        let jar = Arc::new(Jar::default());
+        let client = Client::builder()
+            .cookie_provider(jar.clone())
+            .timeout(POLL_TIMEOUT)
+            .build()?;
+        let passport_url = passport_url.trim_end_matches('/').to_string();
+        let gid = random_gid();
+
+        let url = format!("{}/v2/api/getqrcode", passport_url);
+        let body = client
+            .get(&url)
+            .query(&[
+                ("lp", "pc"),
+                ("qrloginfrom", "pc"),
+                ("gid", &gid),
+                ("apiver", "v3"),
+                ("tpl", TPL),
+                ("tt", &millis().to_string()),
+            ])
+            .send()
+            .await?
+            .text()
+            .await?;
+        debug!(body = %body, "{}", tr!(qr_code_response, body));
+        let response: QrCodeResponse = parse_jsonp(&body)?;
+        if response.errno != 0 || response.sign.is_empty() {
+            return Err(BeduError::Api {
+                errno: response.errno,
+                errmsg: tr!(qr_code_failed),
+                log_id: None,
+            }
+            .into());
+        }
+
+        Ok(Self {
+            client,
+            jar,
+            passport_url,
+            server_url,
+            gid,
+            sign: response.sign,
+            image_url: response.imgurl,
+        })
+    }
+
+    /// 二维码的内容，用百度 App 扫描；可自行渲染成图片或终端字符画
+    pub fn qr_content(&self) -> String {
+        format!(
+            "https://wappass.baidu.com/wp/?qrlogin&t={}&error=0&sign={}&cmd=login&lp=pc&tpl={}&adapter=3&qrloginfrom=pc",
+            millis() / 1000,
+            self.sign,
+            TPL
+        )
+    }
+
+    /// 通行证生成的二维码图片地址，无法在终端显示二维码时可在浏览器中打开
+    pub fn image_url(&self) -> String {
+        if self.image_url.starts_with("http") {
+            self.image_url.clone()
+        } else {
+            format!("https://{}", self.image_url)
+        }
+    }
+
+    /// 长轮询一次扫码状态，服务端在状态变化或约 30 秒后返回
+    pub async fn poll(&self) -> Result<QrStatus> {
+        let url = format!("{}/channel/unicast", self.passport_url);
+        let body = self
+            .client
+            .get(&url)
+            .query(&[
+                ("channel_id", self.sign.as_str()),
+                ("gid", &self.gid),
+                ("tpl", TPL),
+                ("apiver", "v3"),
+                ("tt", &millis().to_string()),
+            ])
+            .send()
+            .await?
+            .text()
+            .await?;
+        debug!(body = %body, "{}", tr!(qr_poll_response, body));
+        let response: UnicastResponse = parse_jsonp(&body)?;
+        // errno 非 0 表示这段时间内没有状态变化
+        if response.errno != 0 || response.channel_v.is_empty() {
+            return Ok(QrStatus::Waiting);
+        }
+
+        let value: ChannelValue = parse_jsonp(&response.channel_v)?;
+        Ok(match value.status {
+            0 => QrStatus::Confirmed(value.v),
+            1 => QrStatus::Scanned,
+            2 => QrStatus::Cancelled,
+            _ => QrStatus::Waiting,
+        })
+    }
+
+    /// 用确认登录时得到的临时凭据换取 Cookie
+    pub async fn finish(&self, token: &str) -> Result<Cookie> {
+        let url = format!("{}/v3/login/main/qrbdusslogin", self.passport_url);
+        let now = millis();
+        let body = self
+            .client
+            .get(&url)
+            .query(&[
+                ("v", now.to_string().as_str()),
+                ("bduss", token),
+                ("loginVersion", "v4"),
+                ("qrcode", "1"),
+                ("tpl", TPL),
+                ("apiver", "v3"),
+                ("tt", &now.to_string()),
+                ("time", &(now / 1000).to_string()),
+                ("alg", "v3"),
+            ])
+            .send()
+            .await?
+            .text()
+            .await?;
+        debug!(body = %body, "{}", tr!(qr_login_response, body));
+
+        let header = self
+            .jar
+            .cookies(&self.server_url)
+            .and_then(|value| value.to_str().ok().map(str::to_string))
+            .unwrap_or_default();
+        Ok(Cookie::parse(&header)?)
+    }
+
+    /// 轮询直到确认登录并换取 Cookie，每次状态变化时回调 `on_status`；
+    /// 超过 `timeout` 仍未确认（二维码已失效）或在手机上取消时返回错误
+    pub async fn wait(
+        &self,
+        timeout: Duration,
+        mut on_status: impl FnMut(&QrStatus),
+    ) -> Result<Cookie> {
+        let deadline = Instant::now() + timeout;
+        let mut last = QrStatus::Waiting;
+        loop {
+            let status = tokio::time::timeout_at(deadline, self.poll())
+                .await
+                .map_err(|_| anyhow!(tr!(qr_login_expired)))??;
+            if status != last {
+                on_status(&status);
+                last = status.clone();
+            }
+            match status {
+                QrStatus::Confirmed(token) => return self.finish(&token).await,
+                QrStatus::Cancelled => return Err(anyhow!(tr!(qr_login_cancelled))),
+                QrStatus::Waiting | QrStatus::Scanned => {}
+            }
+        }
+    }
+}
+
+/// 通行证接口有时返回 JSONP，取出其中的 JSON 对象
+fn parse_jsonp<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, BeduError> {
+    let json = match (body.find('{'), body.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &body[start..=end],
+        _ => body,
+    };
+    serde_json::from_str(json).map_err(|e| BeduError::Decode {
+        message: e.to_string(),
+        body: body.to_string(),
+    })
+}
+
+/// 通行证用于关联同一次登录各个请求的随机ID，格式与网页生成的相同
+fn random_gid() -> String {
+    let mut rng = rand::thread_rng();
+    "xxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx"
+        .chars()
+        .map(|c| match c {
+            'x' => char::from_digit(rng.gen_range(0..16), 16).unwrap_or('0'),
+            'y' => char::from_digit(rng.gen_range(8..12), 16).unwrap_or('8'),
+            c => c,
+        })
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use reqwest::{Client, Request, Response};
+use std::sync::Arc;
+
+/// 请求中间件，可在请求发出前后插入日志、修改请求头、限速或统计指标
+///
+/// 与 tower 的 layer 类似，每个中间件拿到请求后决定是否以及如何交给下一层：
+///
+/// ```rust
+/// use anyhow::Result;
+/// use bedu_claim::client::{Middleware, Next};
+/// use futures::future::BoxFuture;
+/// use reqwest::{Request, Response};
+///
+/// struct Timing;
+///
+/// impl Middleware for Timing {
+///     fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+///         Box::pin(async move {
+///             let started = std::time::Instant::now();
+///             let url = request.url().clone();
+///             let response = next.run(request).await?;
+///             println!("{} {} {:?}", url, response.status(), started.elapsed());
+///             Ok(response)
+///         })
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    /// 处理请求，调用 `next.run(request)` 交给下一层
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>>;
+}
+
+/// 中间件链中剩余的部分，最后一层实际发送请求
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    client: &'a Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a Client, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    /// 将请求交给下一层中间件，没有剩余中间件时发送请求
+    pub fn run(self, request: Request) -> BoxFuture<'a, Result<Response>> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    client: self.client,
+                    middlewares: rest,
+                },
+            ),
+            None => Box::pin(async move { Ok(self.client.execute(request).await?) }),
+        }
+    }
+}
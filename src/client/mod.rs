@@ -1,5 +1,103 @@
+pub mod audit;
+#[cfg(feature = "claimer")]
+pub mod auditor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bedu_api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+#[cfg(feature = "claimer")]
 pub mod claimer;
+pub mod cookie;
+pub mod correlation;
+mod dump;
+#[cfg(feature = "claimer")]
+pub mod events;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod failover;
+pub mod fixture;
 pub mod http;
+#[cfg(feature = "claimer")]
+pub mod instance_lock;
+#[cfg(feature = "qr-login")]
+pub mod login;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod middleware;
+#[cfg(feature = "claimer")]
+pub mod notify;
+#[cfg(feature = "claimer")]
+pub mod ocr;
+#[cfg(feature = "claimer")]
+pub mod pool;
+#[cfg(feature = "claimer")]
+pub mod producer;
+#[cfg(feature = "claimer")]
+pub mod rate_limit;
+#[cfg(feature = "claimer")]
+pub mod schedule;
+#[cfg(feature = "claimer")]
+pub mod state;
+#[cfg(feature = "claimer")]
+pub mod stats;
+#[cfg(feature = "claimer")]
+mod throttle;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod token;
+pub mod user_agent;
 
-pub use claimer::{AutoClaimConfig, AutoClaimer};
-pub use http::HttpClient;
+#[cfg(feature = "claimer")]
+pub use auditor::{AuditDecision, AutoAuditor, AutoAuditorConfig, Judge, Verdict};
+#[cfg(not(target_arch = "wasm32"))]
+pub use bedu_api::BeduApi;
+#[cfg(feature = "blocking")]
+pub use blocking::{AutoClaimerBlocking, HttpClientBlocking};
+#[cfg(not(target_arch = "wasm32"))]
+pub use cache::{CacheTtl, CachedApi};
+#[cfg(feature = "claimer")]
+pub use claimer::{
+    AutoClaimConfig, AutoClaimer, ClaimFailure, ClaimOutcome, ClaimSettings, ClaimSummary,
+    ClaimedTask, ClaimerStatus, DrainedTask,
+};
+pub use cookie::Cookie;
+pub use correlation::{current_correlation_id, with_correlation_id};
+#[cfg(feature = "claimer")]
+pub use events::ClaimEvent;
+#[cfg(not(target_arch = "wasm32"))]
+pub use failover::EndpointStatus;
+pub use fixture::FixtureMode;
+#[cfg(not(target_arch = "wasm32"))]
+pub use http::PoolOptions;
+pub use http::{HttpClient, HttpClientBuilder, TaskListFetch};
+#[cfg(feature = "claimer")]
+pub use instance_lock::InstanceLock;
+#[cfg(not(target_arch = "wasm32"))]
+pub use middleware::{Middleware, Next};
+#[cfg(feature = "desktop-notify")]
+pub use notify::DesktopNotifier;
+#[cfg(feature = "email")]
+pub use notify::EmailNotifier;
+#[cfg(feature = "mqtt")]
+pub use notify::MqttNotifier;
+#[cfg(feature = "claimer")]
+pub use notify::{
+    BarkNotifier, DingTalkNotifier, Notifier, ServerChanNotifier, SoundNotifier, WeComNotifier,
+    WebhookNotifier,
+};
+#[cfg(feature = "claimer")]
+pub use ocr::{ImageTextExtractor, NoExtractor};
+#[cfg(feature = "claimer")]
+pub use pool::{ClaimerPool, MemberState, MemberStatus, PoolStatus};
+#[cfg(feature = "claimer")]
+pub use producer::{AnswerGenerator, AutoProducer, AutoProducerConfig, ProduceRecord};
+#[cfg(feature = "claimer")]
+pub use rate_limit::RateLimiter;
+#[cfg(feature = "claimer")]
+pub use schedule::{ActiveHours, CronSchedule, WorkCalendar};
+#[cfg(feature = "claimer")]
+pub use state::ClaimState;
+#[cfg(feature = "claimer")]
+pub use stats::ClaimStats;
+#[cfg(not(target_arch = "wasm32"))]
+pub use token::{Token, TokenSource};
+pub use user_agent::{UserAgentPool, UserAgentRotation};
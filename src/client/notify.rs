@@ -0,0 +1,754 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+#[cfg(feature = "email")]
+use lettre::message::{Mailbox, header::ContentType};
+#[cfg(feature = "email")]
+use lettre::transport::smtp::authentication::Credentials;
+#[cfg(feature = "email")]
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use reqwest::{Client, Url};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+#[cfg(feature = "email")]
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::client::events::ClaimEvent;
+
+/// 通知渠道，接收值得提醒的认领事件：发现新任务、认领成功、达到认领限制和 Cookie 过期
+///
+/// ```rust
+/// use anyhow::Result;
+/// use bedu_claim::client::ClaimEvent;
+/// use bedu_claim::client::notify::Notifier;
+/// use futures::future::BoxFuture;
+///
+/// struct Stdout;
+///
+/// impl Notifier for Stdout {
+///     fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+///         Box::pin(async move {
+///             if let ClaimEvent::TasksSpotted { tasks, .. } = event {
+///                 for task in tasks {
+///                     println!("新任务 {}: {}", task.task_id, task.brief);
+///                 }
+///             }
+///             Ok(())
+///         })
+///     }
+/// }
+/// ```
+pub trait Notifier: Send + Sync {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>>;
+
+    /// 是否接收该事件，默认接收 [`is_notable`] 的事件
+    fn wants(&self, event: &ClaimEvent) -> bool {
+        is_notable(event)
+    }
+}
+
+/// 是否为需要通知的事件
+pub fn is_notable(event: &ClaimEvent) -> bool {
+    describe(event).is_some()
+}
+
+/// 通知的标题和正文，不需要通知的事件返回 None
+pub fn describe(event: &ClaimEvent) -> Option<(String, String)> {
+    match event {
+        ClaimEvent::TasksSpotted { tasks, .. } => Some((
+            tr!(new_tasks_title, tasks.len()),
+            tasks
+                .iter()
+                .map(|task| format!("{} {}", task.task_id, task.brief))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+        ClaimEvent::Claimed {
+            task_ids, total, ..
+        } => Some((
+            tr!(claimed_title, task_ids.len()),
+            tr!(claimed_body, task_ids.join(", "), total),
+        )),
+        ClaimEvent::LimitReached { total, limit } => Some((
+            tr!(limit_reached_title),
+            tr!(limit_reached_body, total, limit),
+        )),
+        ClaimEvent::CookieExpired { message } => {
+            Some((tr!(cookie_expired_title), tr!(cookie_expired_body, message)))
+        }
+        _ => None,
+    }
+}
+
+/// 将需要通知的事件逐个交给通知渠道，直到事件通道关闭；发送失败只记录日志
+pub async fn dispatch<N: Notifier>(mut receiver: broadcast::Receiver<ClaimEvent>, notifier: N) {
+    loop {
+        match receiver.recv().await {
+            Ok(event) if notifier.wants(&event) => {
+                if let Err(e) = notifier.notify(&event).await {
+                    warn!(error = %e, "{}", tr!(notify_failed, e));
+                }
+            }
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// 系统桌面通知（需启用 `desktop-notify` 特性）
+#[cfg(feature = "desktop-notify")]
+#[derive(Debug, Default)]
+pub struct DesktopNotifier;
+
+#[cfg(feature = "desktop-notify")]
+impl Notifier for DesktopNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some((summary, body)) = describe(event) else {
+                return Ok(());
+            };
+            // 部分平台的通知接口是阻塞调用，放到阻塞线程池中执行
+            tokio::task::spawn_blocking(move || {
+                notify_rust::Notification::new()
+                    .appname("bedu-claim")
+                    .summary(&summary)
+                    .body(&body)
+                    .show()
+                    .map(|_| ())
+            })
+            .await??;
+            Ok(())
+        })
+    }
+}
+
+/// SMTP 邮件通知（需启用 `email` 特性），只发送运行结束汇总和致命错误
+///
+/// 适合没有接入聊天工具的无人值守服务器：自动认领结束、Cookie 过期或出错退出时发送一封邮件，
+/// 不会为每次认领成功发信。
+///
+/// ```rust,no_run
+/// use bedu_claim::client::notify::{EmailNotifier, SmtpConfig, SmtpTls};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let notifier = EmailNotifier::new(SmtpConfig {
+///     host: "smtp.qq.com".to_string(),
+///     tls: SmtpTls::Tls,
+///     username: Some("bot@qq.com".to_string()),
+///     password: Some("授权码".to_string()),
+///     from: "bot@qq.com".to_string(),
+///     to: vec!["me@example.com".to_string()],
+///     ..Default::default()
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "email")]
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+/// SMTP 连接的加密方式
+#[cfg(feature = "email")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// 不加密，仅用于本机或内网中继
+    None,
+    /// 明文连接后通过 STARTTLS 升级，默认端口 587
+    StartTls,
+    /// 直接使用 TLS 连接，默认端口 465
+    #[default]
+    Tls,
+}
+
+#[cfg(feature = "email")]
+impl FromStr for SmtpTls {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "starttls" => Ok(Self::StartTls),
+            "tls" | "ssl" => Ok(Self::Tls),
+            _ => Err(anyhow!(tr!(invalid_smtp_tls, s))),
+        }
+    }
+}
+
+/// SMTP 服务器和收发件人
+#[cfg(feature = "email")]
+#[derive(Debug, Clone, Default)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// 端口，为 None 时按加密方式使用默认端口
+    pub port: Option<u16>,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 发件人，如 `bedu-claim <bot@example.com>`
+    pub from: String,
+    /// 收件人
+    pub to: Vec<String>,
+}
+
+#[cfg(feature = "email")]
+impl EmailNotifier {
+    /// 按配置创建通知，地址格式错误或没有收件人时返回错误
+    pub fn new(config: SmtpConfig) -> Result<Self> {
+        let parse_mailbox = |address: &str| -> Result<Mailbox> {
+            address
+                .trim()
+                .parse()
+                .map_err(|e| anyhow!(tr!(invalid_mail_address, address, e)))
+        };
+
+        let from = parse_mailbox(&config.from)?;
+        let to = config
+            .to
+            .iter()
+            .map(|address| parse_mailbox(address))
+            .collect::<Result<Vec<_>>>()?;
+        if to.is_empty() {
+            return Err(anyhow!(tr!(empty_mail_recipients)));
+        }
+
+        let mut builder = match config.tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| anyhow!(tr!(smtp_config_error, e)))?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| anyhow!(tr!(smtp_config_error, e)))?,
+        };
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let Some(username) = config.username {
+            builder = builder.credentials(Credentials::new(
+                username,
+                config.password.unwrap_or_default(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+
+    /// 邮件的主题和正文，不需要发信的事件返回 None
+    fn compose(event: &ClaimEvent) -> Option<(String, String)> {
+        match event {
+            ClaimEvent::Finished { summary } => Some((
+                tr!(finished_title, summary.total_claims),
+                tr!(
+                    finished_body,
+                    summary.total_claims,
+                    summary.attempts,
+                    summary.failures.len(),
+                    summary.duration.as_secs_f64(),
+                    summary.claimed_task_ids.join(", ")
+                ),
+            )),
+            ClaimEvent::Aborted { message } => {
+                Some((tr!(aborted_title), tr!(aborted_body, message)))
+            }
+            ClaimEvent::CookieExpired { .. } => describe(event),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "email")]
+impl Notifier for EmailNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some((subject, body)) = Self::compose(event) else {
+                return Ok(());
+            };
+
+            let mut builder = Message::builder()
+                .from(self.from.clone())
+                .subject(format!("[bedu-claim] {}", subject))
+                .header(ContentType::TEXT_PLAIN);
+            for to in &self.to {
+                builder = builder.to(to.clone());
+            }
+            let message = builder
+                .body(body)
+                .map_err(|e| anyhow!(tr!(build_mail_failed, e)))?;
+
+            self.transport
+                .send(message)
+                .await
+                .map_err(|e| anyhow!(tr!(send_mail_failed, e)))?;
+            Ok(())
+        })
+    }
+
+    fn wants(&self, event: &ClaimEvent) -> bool {
+        Self::compose(event).is_some()
+    }
+}
+
+/// HTTP 通知请求的超时时间，通知地址无响应时不会一直卡住通知任务
+const HTTP_NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook、机器人和推送服务共用的 HTTP 客户端配置
+///
+/// # Panics
+///
+/// 与 [`Client::new`] 相同，TLS 后端初始化失败时 panic。
+fn http_client() -> Client {
+    Client::builder()
+        .timeout(HTTP_NOTIFY_TIMEOUT)
+        .build()
+        .expect("Failed to build notification HTTP client")
+}
+
+/// Webhook 通知，以 POST 请求发送事件的 JSON（与 NDJSON 输出的每行相同）
+pub struct WebhookNotifier {
+    client: Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            url: url.into(),
+        }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .body(event.to_json_line()?)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(anyhow!(tr!(webhook_status, response.status())));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// 钉钉群机器人通知，支持加签
+///
+/// 机器人安全设置选择「加签」时需通过 [`DingTalkNotifier::secret`] 提供密钥，
+/// 每次发送时按钉钉的规则计算签名并附加到地址上。
+pub struct DingTalkNotifier {
+    client: Client,
+    url: String,
+    secret: Option<String>,
+}
+
+impl DingTalkNotifier {
+    /// `url` 为机器人的 Webhook 地址（含 access_token）
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            url: url.into(),
+            secret: None,
+        }
+    }
+
+    /// 设置加签密钥（`SEC` 开头）
+    pub fn secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// 带签名的发送地址：`timestamp` 为毫秒时间戳，`sign` 为 HMAC-SHA256 的 Base64
+    fn signed_url(&self) -> Result<Url> {
+        let mut url = Url::parse(&self.url).map_err(|e| anyhow!(tr!(invalid_dingtalk_url, e)))?;
+        if let Some(secret) = &self.secret {
+            let timestamp = chrono::Utc::now().timestamp_millis();
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .map_err(|e| anyhow!(tr!(invalid_sign_secret, e)))?;
+            mac.update(format!("{}\n{}", timestamp, secret).as_bytes());
+            let sign = STANDARD.encode(mac.finalize().into_bytes());
+            url.query_pairs_mut()
+                .append_pair("timestamp", &timestamp.to_string())
+                .append_pair("sign", &sign);
+        }
+        Ok(url)
+    }
+}
+
+impl Notifier for DingTalkNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(content) = robot_text(event) else {
+                return Ok(());
+            };
+            post_robot(&self.client, self.signed_url()?, content).await
+        })
+    }
+}
+
+/// 企业微信群机器人通知
+///
+/// 企业微信群机器人没有加签机制，Webhook 地址中的 key 即为凭证，注意不要泄露。
+pub struct WeComNotifier {
+    client: Client,
+    url: String,
+}
+
+impl WeComNotifier {
+    /// `url` 为机器人的 Webhook 地址（含 key）
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            url: url.into(),
+        }
+    }
+}
+
+impl Notifier for WeComNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(content) = robot_text(event) else {
+                return Ok(());
+            };
+            let url = Url::parse(&self.url).map_err(|e| anyhow!(tr!(invalid_wecom_url, e)))?;
+            post_robot(&self.client, url, content).await
+        })
+    }
+}
+
+/// 群机器人的文本消息内容：标题和正文各占一段
+fn robot_text(event: &ClaimEvent) -> Option<String> {
+    describe(event).map(|(summary, body)| format!("【bedu-claim】{}\n{}", summary, body))
+}
+
+/// 以文本消息发送到群机器人，钉钉和企业微信的消息格式与返回格式相同
+async fn post_robot(client: &Client, url: Url, content: String) -> Result<()> {
+    let response: Value = client
+        .post(url)
+        .json(&json!({
+            "msgtype": "text",
+            "text": { "content": content },
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    match response.get("errcode").and_then(Value::as_i64) {
+        Some(0) => Ok(()),
+        _ => Err(anyhow!(tr!(bot_error, response))),
+    }
+}
+
+/// 手机推送只发送认领成功和 Cookie 过期，避免频繁打扰
+fn is_push_worthy(event: &ClaimEvent) -> bool {
+    matches!(
+        event,
+        ClaimEvent::Claimed { .. } | ClaimEvent::CookieExpired { .. }
+    )
+}
+
+/// Bark（iOS）推送，只发送认领成功和 Cookie 过期
+pub struct BarkNotifier {
+    client: Client,
+    server: String,
+    key: String,
+}
+
+impl BarkNotifier {
+    /// `key` 为 Bark App 中显示的设备密钥
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            server: "https://api.day.app".to_string(),
+            key: key.into(),
+        }
+    }
+
+    /// 使用自建的 Bark 服务器，默认 `https://api.day.app`
+    pub fn server(mut self, server: impl Into<String>) -> Self {
+        self.server = server.into();
+        self
+    }
+}
+
+impl Notifier for BarkNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some((title, body)) = describe(event) else {
+                return Ok(());
+            };
+            let response: Value = self
+                .client
+                .post(format!(
+                    "{}/{}",
+                    self.server.trim_end_matches('/'),
+                    self.key
+                ))
+                .json(&json!({
+                    "title": title,
+                    "body": body,
+                    "group": "bedu-claim",
+                }))
+                .send()
+                .await?
+                .json()
+                .await?;
+
+            match response.get("code").and_then(Value::as_i64) {
+                Some(200) => Ok(()),
+                _ => Err(anyhow!(tr!(bark_error, response))),
+            }
+        })
+    }
+
+    fn wants(&self, event: &ClaimEvent) -> bool {
+        is_push_worthy(event)
+    }
+}
+
+/// Server酱（微信）推送，只发送认领成功和 Cookie 过期
+pub struct ServerChanNotifier {
+    client: Client,
+    send_key: String,
+}
+
+impl ServerChanNotifier {
+    /// `send_key` 为 Server酱 的 SendKey
+    pub fn new(send_key: impl Into<String>) -> Self {
+        Self {
+            client: http_client(),
+            send_key: send_key.into(),
+        }
+    }
+}
+
+impl Notifier for ServerChanNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some((title, body)) = describe(event) else {
+                return Ok(());
+            };
+            let response: Value = self
+                .client
+                .post(format!("https://sctapi.ftqq.com/{}.send", self.send_key))
+                .form(&[("title", title), ("desp", body)])
+                .send()
+                .await?
+                .json()
+                .await?;
+
+            match response.get("code").and_then(Value::as_i64) {
+                Some(0) => Ok(()),
+                _ => Err(anyhow!(tr!(serverchan_error, response))),
+            }
+        })
+    }
+
+    fn wants(&self, event: &ClaimEvent) -> bool {
+        is_push_worthy(event)
+    }
+}
+
+/// 提示音：认领成功或任务池由空变为有任务时响铃或播放声音文件
+///
+/// 声音文件通过系统播放器播放：macOS 使用 `afplay`，Windows 使用 PowerShell，
+/// 其他系统依次尝试 `paplay` 和 `aplay`。
+pub struct SoundNotifier {
+    sound_file: Option<PathBuf>,
+    /// 上一次获取到的任务池是否为空，初始视为空
+    pool_empty: Mutex<bool>,
+}
+
+impl SoundNotifier {
+    /// 在终端响铃（向 stderr 输出 BEL 字符）
+    pub fn bell() -> Self {
+        Self {
+            sound_file: None,
+            pool_empty: Mutex::new(true),
+        }
+    }
+
+    /// 播放声音文件
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            sound_file: Some(path.into()),
+            pool_empty: Mutex::new(true),
+        }
+    }
+
+    fn play(path: &Path) -> std::io::Result<ExitStatus> {
+        if cfg!(target_os = "macos") {
+            Command::new("afplay").arg(path).status()
+        } else if cfg!(windows) {
+            // 路径经环境变量传入，不拼接进命令，含引号的路径不会破坏或注入命令
+            Command::new("powershell")
+                .arg("-NoProfile")
+                .arg("-Command")
+                .arg("(New-Object Media.SoundPlayer $env:BEDU_SOUND_FILE).PlaySync()")
+                .env("BEDU_SOUND_FILE", path)
+                .status()
+        } else {
+            match Command::new("paplay").arg(path).status() {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    Command::new("aplay").arg("-q").arg(path).status()
+                }
+                result => result,
+            }
+        }
+    }
+}
+
+impl Notifier for SoundNotifier {
+    fn notify<'a>(&'a self, _event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(path) = self.sound_file.clone() else {
+                let mut stderr = std::io::stderr();
+                stderr.write_all(b"\x07")?;
+                stderr.flush()?;
+                return Ok(());
+            };
+
+            let status = tokio::task::spawn_blocking(move || Self::play(&path))
+                .await?
+                .map_err(|e| anyhow!(tr!(player_start_failed, e)))?;
+            if !status.success() {
+                return Err(anyhow!(tr!(play_sound_failed, status)));
+            }
+            Ok(())
+        })
+    }
+
+    fn wants(&self, event: &ClaimEvent) -> bool {
+        match event {
+            ClaimEvent::Claimed { .. } => true,
+            ClaimEvent::PoolFetched { pool_size, .. } => {
+                let mut pool_empty = self
+                    .pool_empty
+                    .lock()
+                    .expect("sound notifier mutex poisoned");
+                let became_available = *pool_empty && *pool_size > 0;
+                *pool_empty = *pool_size == 0;
+                became_available
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 将全部认领事件以 JSON（与 NDJSON 输出的每行相同）发布到 MQTT 主题（需启用 `mqtt` 特性）
+///
+/// 供 Node-RED、Home Assistant 等订阅，按 `event` 字段区分事件类型。
+/// 只支持不加密的 `mqtt://` 连接，断线后自动重连；自动认领结束时等待剩余消息发出后断开。
+#[cfg(feature = "mqtt")]
+pub struct MqttNotifier {
+    client: rumqttc::AsyncClient,
+    topic: String,
+    event_loop: tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+#[cfg(feature = "mqtt")]
+impl MqttNotifier {
+    /// 连接 `mqtt://[用户名:密码@]主机[:端口]`，事件发布到 `topic`
+    ///
+    /// 需要在 tokio 运行时中调用，连接在后台建立。
+    pub fn connect(url: &str, topic: impl Into<String>) -> Result<Self> {
+        let url = Url::parse(url).map_err(|e| anyhow!(tr!(invalid_mqtt_url, url, e)))?;
+        if !matches!(url.scheme(), "mqtt" | "tcp") {
+            return Err(anyhow!(tr!(unsupported_mqtt_scheme, url.scheme())));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!(tr!(mqtt_missing_host)))?;
+
+        let mut options = rumqttc::MqttOptions::new(
+            format!("bedu-claim-{}", std::process::id()),
+            host,
+            url.port().unwrap_or(1883),
+        );
+        options.set_keep_alive(Duration::from_secs(30));
+        if !url.username().is_empty() {
+            options.set_credentials(url.username(), url.password().unwrap_or_default());
+        }
+
+        let (client, mut event_loop) = rumqttc::AsyncClient::new(options, 64);
+        let handle = tokio::spawn(async move {
+            loop {
+                match event_loop.poll().await {
+                    Ok(rumqttc::Event::Outgoing(rumqttc::Outgoing::Disconnect)) => break,
+                    Ok(_) => {}
+                    Err(rumqttc::ConnectionError::RequestsDone) => break,
+                    Err(e) => {
+                        warn!(error = %e, "{}", tr!(mqtt_connection_error, e));
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            topic: topic.into(),
+            event_loop: tokio::sync::Mutex::new(Some(handle)),
+        })
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl Notifier for MqttNotifier {
+    fn notify<'a>(&'a self, event: &'a ClaimEvent) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.client
+                .publish(
+                    &self.topic,
+                    rumqttc::QoS::AtLeastOnce,
+                    false,
+                    event.to_json_line()?,
+                )
+                .await
+                .map_err(|e| anyhow!(tr!(mqtt_publish_failed, e)))?;
+
+            // 结束事件之后不会再有新事件，断开连接并等待已排队的消息发出
+            if matches!(
+                event,
+                ClaimEvent::Finished { .. } | ClaimEvent::Aborted { .. }
+            ) && let Some(handle) = self.event_loop.lock().await.take()
+            {
+                self.client
+                    .disconnect()
+                    .await
+                    .map_err(|e| anyhow!(tr!(mqtt_disconnect_failed, e)))?;
+                if tokio::time::timeout(Duration::from_secs(5), handle)
+                    .await
+                    .is_err()
+                {
+                    return Err(anyhow!(tr!(mqtt_flush_timeout)));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn wants(&self, _event: &ClaimEvent) -> bool {
+        true
+    }
+}
@@ -0,0 +1,48 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use std::future::Future;
+use tracing::debug;
+
+use crate::api::QuestionContent;
+use crate::client::bedu_api::BeduApi;
+
+/// 图片文字识别器，由使用方接入具体的 OCR 服务
+///
+/// 实现时可以直接写 `async fn extract(...)`。
+pub trait ImageTextExtractor: Send + Sync {
+    /// 识别图片中的文字
+    fn extract(&self, image: &[u8]) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// 未配置识别器时使用的占位类型
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoExtractor;
+
+impl ImageTextExtractor for NoExtractor {
+    async fn extract(&self, _image: &[u8]) -> Result<String> {
+        Err(anyhow!(tr!(ocr_not_configured)))
+    }
+}
+
+/// 题目只有图片时，下载图片并识别文字填入题干，返回是否做了识别
+pub async fn fill_image_text<A: BeduApi, E: ImageTextExtractor>(
+    client: &A,
+    extractor: &E,
+    content: &mut QuestionContent,
+) -> Result<bool> {
+    if !content.is_image_only() {
+        return Ok(false);
+    }
+
+    let mut texts = Vec::with_capacity(content.images.len());
+    for url in &content.images {
+        let image = client.download_image(url).await?;
+        let text = extractor.extract(&image).await?;
+        debug!(%url, chars = text.chars().count(), "{}", tr!(ocr_finished, url));
+        texts.push(text);
+    }
+
+    content.stem = texts.join("\n");
+
+    Ok(true)
+}
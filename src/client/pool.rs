@@ -0,0 +1,252 @@
+use crate::tr;
+use anyhow::anyhow;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+use crate::client::HttpClient;
+use crate::client::bedu_api::BeduApi;
+use crate::client::claimer::{AutoClaimer, ClaimSummary};
+
+/// 池中认领器的运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberState {
+    /// 尚未启动
+    Pending,
+    Running,
+    /// 出错退出，等待重启
+    Restarting,
+    /// 达到认领限制正常结束
+    Finished,
+    /// 重启次数用尽，不再重启
+    Failed,
+}
+
+/// 单个认领器的状态
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberStatus {
+    pub name: String,
+    pub state: MemberState,
+    /// 已重启的次数
+    pub restarts: u32,
+    /// 累计认领成功数（跨重启）
+    pub claimed: i32,
+    /// 最近一次出错退出的原因
+    pub last_error: Option<String>,
+    /// 正常结束时的运行汇总
+    pub summary: Option<ClaimSummary>,
+}
+
+/// 认领器池的整体状态
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStatus {
+    pub members: Vec<MemberStatus>,
+    /// 所有认领器的认领成功数之和
+    pub total_claimed: i32,
+    /// 正在运行或等待重启的认领器数量
+    pub active: usize,
+}
+
+struct Member<A> {
+    claimer: Arc<AutoClaimer<A>>,
+    status: Arc<Mutex<MemberStatus>>,
+}
+
+/// 重启策略：等待时间从 `initial` 开始每次翻倍，最长 `max`
+#[derive(Debug, Clone, Copy)]
+struct RestartPolicy {
+    initial: Duration,
+    max: Duration,
+    max_restarts: Option<u32>,
+}
+
+impl RestartPolicy {
+    fn delay(&self, restarts: u32) -> Duration {
+        self.initial
+            .saturating_mul(2u32.saturating_pow(restarts))
+            .min(self.max)
+    }
+}
+
+/// 认领器池，监督多个认领器并在出错退出时自动重启
+///
+/// 每个认领器的每次运行都在独立的 tokio 任务中进行，返回错误或 panic 都视为崩溃，
+/// 按指数退避等待后重启同一个认领器实例，认领计数和去重信息随之保留。
+/// 适合长时间运行、多套配置（学科、任务类型）同时认领的部署。
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{AutoClaimConfig, AutoClaimer, ClaimerPool};
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let mut pool = ClaimerPool::new().max_restarts(20);
+/// for subject_id in [1, 2, 3] {
+///     let claimer = AutoClaimer::new(AutoClaimConfig {
+///         subject_id,
+///         ..Default::default()
+///     })?;
+///     pool.add(format!("subject-{}", subject_id), claimer);
+/// }
+///
+/// for member in pool.run().await {
+///     println!("{}: {:?}，认领 {} 个", member.name, member.state, member.claimed);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClaimerPool<A = HttpClient> {
+    members: Vec<Member<A>>,
+    policy: RestartPolicy,
+}
+
+impl<A> Default for ClaimerPool<A> {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            policy: RestartPolicy {
+                initial: Duration::from_secs(1),
+                max: Duration::from_secs(300),
+                max_restarts: None,
+            },
+        }
+    }
+}
+
+impl<A: BeduApi + 'static> ClaimerPool<A> {
+    /// 创建空的认领器池，默认无限重启，退避从 1 秒开始、最长 5 分钟
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置重启前的退避时间：首次等待 `initial`，之后每次翻倍，最长 `max`
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.policy.initial = initial;
+        self.policy.max = max;
+        self
+    }
+
+    /// 设置每个认领器的最大重启次数，用尽后标记为失败
+    pub fn max_restarts(mut self, max_restarts: u32) -> Self {
+        self.policy.max_restarts = Some(max_restarts);
+        self
+    }
+
+    /// 加入一个认领器，返回其共享句柄，可用于订阅事件或查询统计
+    pub fn add(&mut self, name: impl Into<String>, claimer: AutoClaimer<A>) -> Arc<AutoClaimer<A>> {
+        let claimer = Arc::new(claimer);
+        self.members.push(Member {
+            claimer: claimer.clone(),
+            status: Arc::new(Mutex::new(MemberStatus {
+                name: name.into(),
+                state: MemberState::Pending,
+                restarts: 0,
+                claimed: 0,
+                last_error: None,
+                summary: None,
+            })),
+        });
+        claimer
+    }
+
+    /// 当前所有认领器的状态，运行期间可在其他任务中调用
+    pub async fn status(&self) -> PoolStatus {
+        let mut members = Vec::with_capacity(self.members.len());
+        for member in &self.members {
+            let mut status = member.status.lock().await.clone();
+            status.claimed = member.claimer.get_successful_claims().await;
+            members.push(status);
+        }
+
+        PoolStatus {
+            total_claimed: members.iter().map(|member| member.claimed).sum(),
+            active: members
+                .iter()
+                .filter(|member| {
+                    matches!(
+                        member.state,
+                        MemberState::Pending | MemberState::Running | MemberState::Restarting
+                    )
+                })
+                .count(),
+            members,
+        }
+    }
+
+    /// 启动全部认领器并监督运行，直到每个认领器正常结束或重启次数用尽，返回最终状态
+    pub async fn run(&self) -> Vec<MemberStatus> {
+        futures::future::join_all(
+            self.members.iter().map(|member| {
+                supervise(member.claimer.clone(), member.status.clone(), self.policy)
+            }),
+        )
+        .await;
+
+        self.status().await.members
+    }
+}
+
+/// 运行单个认领器，出错或 panic 时按退避策略重启
+async fn supervise<A: BeduApi + 'static>(
+    claimer: Arc<AutoClaimer<A>>,
+    status: Arc<Mutex<MemberStatus>>,
+    policy: RestartPolicy,
+) {
+    loop {
+        status.lock().await.state = MemberState::Running;
+
+        let task = claimer.clone();
+        let result = tokio::spawn(async move { task.start().await })
+            .await
+            .map_err(|e| anyhow!(tr!(claimer_crashed, e)))
+            .and_then(|result| result);
+
+        let mut current = status.lock().await;
+        let name = current.name.clone();
+        match result {
+            Ok(summary) => {
+                info!(
+                    member = %name,
+                    claimed = summary.total_claims,
+                    "{}",
+                    tr!(pool_member_finished, name, summary.total_claims)
+                );
+                current.state = MemberState::Finished;
+                current.summary = Some(summary);
+                return;
+            }
+            Err(e) => {
+                current.last_error = Some(e.to_string());
+                if policy
+                    .max_restarts
+                    .is_some_and(|max_restarts| current.restarts >= max_restarts)
+                {
+                    error!(
+                        member = %name,
+                        restarts = current.restarts,
+                        error = %e,
+                        "{}",
+                        tr!(pool_member_gave_up, name, current.restarts, e)
+                    );
+                    current.state = MemberState::Failed;
+                    return;
+                }
+
+                let delay = policy.delay(current.restarts);
+                current.restarts += 1;
+                current.state = MemberState::Restarting;
+                warn!(
+                    member = %name,
+                    restarts = current.restarts,
+                    error = %e,
+                    "{}",
+                    tr!(pool_member_restarting, name, e, delay.as_secs_f64(), current.restarts)
+                );
+                drop(current);
+                sleep(delay).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::api::QuestionContent;
+use crate::client::bedu_api::BeduApi;
+use crate::client::ocr::{ImageTextExtractor, NoExtractor, fill_image_text};
+use crate::client::throttle::Throttle;
+use crate::client::{ClaimEvent, ClaimedTask, HttpClient};
+
+/// 答案生成器，由使用方接入具体的模型（OpenAI、通义千问、本地模型等）
+///
+/// 实现时可以直接写 `async fn generate(...)`。
+pub trait AnswerGenerator: Send + Sync {
+    /// 根据题目内容生成答案草稿
+    fn generate(&self, content: &QuestionContent) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// 自动生产配置
+#[derive(Debug, Clone)]
+pub struct AutoProducerConfig {
+    /// 两次提交之间的最小间隔
+    pub submit_interval: Duration,
+    /// 只生成草稿不提交
+    pub dry_run: bool,
+}
+
+impl Default for AutoProducerConfig {
+    fn default() -> Self {
+        Self {
+            submit_interval: Duration::from_secs(5),
+            dry_run: false,
+        }
+    }
+}
+
+/// 单个线索的生产记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ProduceRecord {
+    pub clue_id: i64,
+    /// 生成的答案草稿，获取详情或生成出错时为 None
+    pub draft: Option<String>,
+    /// 是否已成功提交到服务器
+    pub submitted: bool,
+    pub error: Option<String>,
+    pub produced_at: DateTime<Local>,
+}
+
+/// 自动生产器：获取认领线索的题目内容，交给生成器起草答案后提交
+pub struct AutoProducer<G, E = NoExtractor, A = HttpClient> {
+    client: Arc<A>,
+    generator: G,
+    extractor: Option<E>,
+    config: AutoProducerConfig,
+    throttle: Throttle,
+}
+
+impl<G: AnswerGenerator, A: BeduApi> AutoProducer<G, NoExtractor, A> {
+    /// 创建新的自动生产器实例
+    pub fn new(client: Arc<A>, generator: G, config: AutoProducerConfig) -> Self {
+        Self {
+            client,
+            generator,
+            extractor: None,
+            throttle: Throttle::new(config.submit_interval),
+            config,
+        }
+    }
+
+    /// 设置图片文字识别器，纯图片题目在生成答案前先识别出题干文字
+    pub fn with_image_extractor<E: ImageTextExtractor>(
+        self,
+        extractor: E,
+    ) -> AutoProducer<G, E, A> {
+        AutoProducer {
+            client: self.client,
+            generator: self.generator,
+            extractor: Some(extractor),
+            config: self.config,
+            throttle: self.throttle,
+        }
+    }
+}
+
+impl<G: AnswerGenerator, E: ImageTextExtractor, A: BeduApi> AutoProducer<G, E, A> {
+    /// 为单个线索生成并提交答案，出错时记录在返回的生产记录中
+    pub async fn produce(&self, task: &ClaimedTask) -> ProduceRecord {
+        let clue_id = task.task.clue_id;
+        let mut record = ProduceRecord {
+            clue_id,
+            draft: None,
+            submitted: false,
+            error: None,
+            produced_at: Local::now(),
+        };
+
+        match self.draft(clue_id).await {
+            Ok((mut content, draft)) => {
+                record.draft = Some(draft.clone());
+                content.answer = draft;
+                match self.submit(clue_id, &content).await {
+                    Ok(submitted) => record.submitted = submitted,
+                    Err(e) => record.error = Some(e.to_string()),
+                }
+            }
+            Err(e) => record.error = Some(e.to_string()),
+        }
+        record.produced_at = Local::now();
+
+        match &record.error {
+            None => info!(
+                clue_id,
+                submitted = record.submitted,
+                "{}",
+                tr!(answer_generated, clue_id, record.submitted)
+            ),
+            Some(error) => warn!(
+                clue_id,
+                error = %error,
+                "{}",
+                tr!(produce_failed, clue_id, error)
+            ),
+        }
+
+        record
+    }
+
+    /// 依次处理事件流中认领成功的生产任务，直到事件通道关闭
+    pub async fn run(&self, mut receiver: broadcast::Receiver<ClaimEvent>) -> Vec<ProduceRecord> {
+        let mut records = Vec::new();
+        loop {
+            match receiver.recv().await {
+                Ok(ClaimEvent::TaskClaimed { task }) => {
+                    if task.task_type == "producetask" {
+                        records.push(self.produce(&task).await);
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "{}", tr!(producer_lagged, skipped));
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+
+        records
+    }
+
+    /// 获取题目内容并生成答案草稿
+    async fn draft(&self, clue_id: i64) -> Result<(QuestionContent, String)> {
+        let content = self
+            .client
+            .get_clue_detail(clue_id)
+            .await?
+            .into_result()
+            .map_err(|e| anyhow!(tr!(fetch_clue_detail_failed, e)))?
+            .content;
+
+        // 识别出的文字只用于生成答案，提交时保留原题内容
+        let mut prompt = content.clone();
+        if let Some(extractor) = &self.extractor {
+            fill_image_text(self.client.as_ref(), extractor, &mut prompt).await?;
+        }
+
+        let draft = self.generator.generate(&prompt).await?;
+        if draft.trim().is_empty() {
+            return Err(anyhow!(tr!(empty_answer)));
+        }
+
+        Ok((content, draft))
+    }
+
+    /// 按最小间隔限速提交答案，演练模式下不提交
+    async fn submit(&self, clue_id: i64, content: &QuestionContent) -> Result<bool> {
+        if self.config.dry_run {
+            return Ok(false);
+        }
+
+        let _permit = self.throttle.acquire().await;
+        let response = self.client.submit_produce_answer(clue_id, content).await?;
+        if response.errno != 0 {
+            return Err(anyhow!(tr!(
+                submit_answer_failed,
+                response.errno,
+                response.errmsg
+            )));
+        }
+
+        Ok(true)
+    }
+}
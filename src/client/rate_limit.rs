@@ -0,0 +1,80 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use reqwest::{Request, Response};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{Instant, sleep_until};
+
+use crate::client::middleware::{Middleware, Next};
+
+/// 可在多个客户端、认领器之间共享的请求限速器
+///
+/// 克隆得到的句柄共用同一个配额：同一账号或服务器上运行多个 [`AutoClaimer`](crate::client::AutoClaimer)
+/// 时，把同一个限速器传给它们，合计请求速率就不会超过上限。请求按固定间隔均匀放行，不允许突发。
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{AutoClaimConfig, AutoClaimer, RateLimiter};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), bedu_claim::BeduError> {
+/// // 两个认领器合计每秒最多 5 个请求
+/// let limiter = RateLimiter::new(5, Duration::from_secs(1));
+/// let math = AutoClaimer::new(AutoClaimConfig {
+///     subject_id: 2,
+///     rate_limiter: Some(limiter.clone()),
+///     ..Default::default()
+/// })?;
+/// let chinese = AutoClaimer::new(AutoClaimConfig {
+///     subject_id: 1,
+///     rate_limiter: Some(limiter),
+///     ..Default::default()
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    /// 下一个请求最早可以发出的时间
+    next: Arc<Mutex<Instant>>,
+}
+
+impl RateLimiter {
+    /// 每 `per` 时长内最多放行 `max_requests` 个请求
+    ///
+    /// # Panics
+    ///
+    /// `max_requests` 为 0 时 panic。
+    pub fn new(max_requests: u32, per: Duration) -> Self {
+        assert!(max_requests > 0, "max_requests must be positive");
+        Self {
+            interval: per / max_requests,
+            next: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// 相邻两个请求之间的间隔
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// 预约下一个发送时间并等待到该时间
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().expect("rate limiter mutex poisoned");
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+impl Middleware for RateLimiter {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            self.acquire().await;
+            next.run(request).await
+        })
+    }
+}
@@ -0,0 +1,325 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, FixedOffset, NaiveDate, NaiveTime, Weekday,
+};
+use croner::Cron;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 一个工作时段，结束时间不晚于开始时间时表示跨越午夜
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Window {
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// 工作时段，如 `09:00-12:00,14:00-18:00`，时段外认领器暂停等待
+///
+/// 结束时间早于开始时间的时段跨越午夜（如 `22:00-02:00`），`24:00` 表示当天结束。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveHours {
+    windows: Vec<Window>,
+}
+
+impl ActiveHours {
+    /// 指定时间是否在任一工作时段内
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.windows.iter().any(|window| window.contains(time))
+    }
+
+    /// `now` 之后最近一个工作时段的开始时间，`now` 已在时段内时返回 `now`
+    pub fn next_start(&self, now: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        if self.contains(now.time()) {
+            return now;
+        }
+
+        let today = now.date_naive();
+        self.windows
+            .iter()
+            .filter_map(|window| {
+                let date = if window.start > now.time() {
+                    today
+                } else {
+                    today.succ_opt()?
+                };
+                date.and_time(window.start)
+                    .and_local_timezone(*now.offset())
+                    .single()
+            })
+            .min()
+            .unwrap_or(now + ChronoDuration::days(1))
+    }
+}
+
+impl FromStr for ActiveHours {
+    type Err = anyhow::Error;
+
+    /// 解析以逗号分隔的 `HH:MM-HH:MM` 时段
+    fn from_str(s: &str) -> Result<Self> {
+        let parse_time = |value: &str| -> Result<NaiveTime> {
+            let value = value.trim();
+            if value == "24:00" {
+                return Ok(NaiveTime::MIN);
+            }
+            NaiveTime::parse_from_str(value, "%H:%M").map_err(|_| anyhow!(tr!(invalid_time, value)))
+        };
+
+        let windows = s
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(|part| {
+                let (start, end) = part
+                    .split_once('-')
+                    .ok_or_else(|| anyhow!(tr!(invalid_active_hours, part.trim())))?;
+                Ok(Window {
+                    start: parse_time(start)?,
+                    end: parse_time(end)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if windows.is_empty() {
+            return Err(anyhow!(tr!(empty_active_hours)));
+        }
+
+        Ok(Self { windows })
+    }
+}
+
+impl fmt::Display for ActiveHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, window) in self.windows.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(
+                f,
+                "{}-{}",
+                window.start.format("%H:%M"),
+                window.end.format("%H:%M")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 定时认领：按 cron 表达式触发，每次触发后持续认领一段时间
+///
+/// 任务按固定时间发布时，只在发布后集中轮询，避免其余时间空转。
+/// 表达式为标准五段格式（分 时 日 月 周），如 `0,30 * * * *` 表示每小时整点和半点。
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    cron: Cron,
+    burst: Duration,
+}
+
+impl CronSchedule {
+    /// 解析 cron 表达式，每次触发后持续认领 `burst` 时长
+    pub fn new(expression: &str, burst: Duration) -> Result<Self> {
+        let cron = expression
+            .trim()
+            .parse()
+            .map_err(|e| anyhow!(tr!(invalid_cron, expression, e)))?;
+        if burst.is_zero() {
+            return Err(anyhow!(tr!(cron_burst_not_positive)));
+        }
+        Ok(Self { cron, burst })
+    }
+
+    /// 每次触发后持续认领的时长
+    pub fn burst(&self) -> Duration {
+        self.burst
+    }
+
+    /// 指定时间是否处在某次触发后的认领时段内
+    pub fn contains(&self, now: DateTime<FixedOffset>) -> bool {
+        self.cron
+            .find_previous_occurrence(&now, true)
+            .is_ok_and(|triggered_at| now < triggered_at + self.burst)
+    }
+
+    /// `now` 之后的下一次触发时间，`now` 已在认领时段内时返回 `now`
+    pub fn next_start(&self, now: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        if self.contains(now) {
+            return now;
+        }
+        self.cron
+            .find_next_occurrence(&now, false)
+            .unwrap_or(now + ChronoDuration::days(1))
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(cron_schedule, self.cron, self.burst.as_secs()))
+    }
+}
+
+/// 工作日历：限定每周的工作日，并排除节假日，非工作日全天暂停认领
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkCalendar {
+    /// 每周的工作日，为 None 时每天都是工作日
+    weekdays: Option<HashSet<Weekday>>,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl WorkCalendar {
+    /// 创建不限制任何日期的日历
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每周的工作日
+    pub fn weekdays(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        self.weekdays = Some(weekdays.into_iter().collect());
+        self
+    }
+
+    /// 添加节假日
+    pub fn holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    /// 从文件加载节假日并添加到日历
+    ///
+    /// 支持 iCal（`.ics`）文件和每行一个 `YYYY-MM-DD` 日期的文本文件（`#` 开头为注释）。
+    /// iCal 文件中全天事件按 `DTSTART` 到 `DTEND`（不含）计入，其他事件只计入开始当天。
+    pub fn load_holidays(self, path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!(tr!(read_holidays_failed, path.display(), e)))?;
+        let holidays = if content.trim_start().starts_with("BEGIN:VCALENDAR") {
+            parse_ical_dates(&content)
+        } else {
+            parse_date_lines(&content)
+        }
+        .map_err(|e| anyhow!(tr!(parse_holidays_failed, path.display(), e)))?;
+        Ok(self.holidays(holidays))
+    }
+
+    /// 指定日期是否为工作日
+    pub fn is_workday(&self, date: NaiveDate) -> bool {
+        !self.holidays.contains(&date)
+            && self
+                .weekdays
+                .as_ref()
+                .is_none_or(|weekdays| weekdays.contains(&date.weekday()))
+    }
+
+    /// `now` 之后最近一个工作日的开始时间，今天是工作日时返回 `now`，一年内没有工作日时返回 None
+    pub fn next_workday_start(&self, now: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+        if self.is_workday(now.date_naive()) {
+            return Some(now);
+        }
+
+        now.date_naive()
+            .iter_days()
+            .skip(1)
+            .take(366)
+            .find(|date| self.is_workday(*date))
+            .and_then(|date| {
+                date.and_time(NaiveTime::MIN)
+                    .and_local_timezone(*now.offset())
+                    .single()
+            })
+    }
+}
+
+/// 解析工作日，如 `mon-fri`、`mon,wed,fri` 或 `1-5`（1 为周一）
+pub fn parse_weekdays(value: &str) -> Result<HashSet<Weekday>> {
+    let parse_day = |day: &str| -> Result<Weekday> {
+        let day = day.trim();
+        match day.parse::<u8>() {
+            Ok(n @ 1..=7) => Ok(Weekday::try_from(n - 1).expect("weekday index is in range")),
+            Ok(_) => Err(anyhow!(tr!(invalid_weekday_number, day))),
+            Err(_) => day
+                .parse::<Weekday>()
+                .map_err(|_| anyhow!(tr!(invalid_weekday, day))),
+        }
+    };
+
+    let mut weekdays = HashSet::new();
+    for part in value.split(',').filter(|part| !part.trim().is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (mut day, end) = (parse_day(start)?, parse_day(end)?);
+                // 支持跨周末的范围，如 fri-mon
+                weekdays.insert(day);
+                while day != end {
+                    day = day.succ();
+                    weekdays.insert(day);
+                }
+            }
+            None => {
+                weekdays.insert(parse_day(part)?);
+            }
+        }
+    }
+
+    if weekdays.is_empty() {
+        return Err(anyhow!(tr!(empty_weekdays)));
+    }
+    Ok(weekdays)
+}
+
+fn parse_date_lines(content: &str) -> Result<Vec<NaiveDate>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            NaiveDate::parse_from_str(line, "%Y-%m-%d")
+                .map_err(|_| anyhow!(tr!(invalid_date_format, line)))
+        })
+        .collect()
+}
+
+/// 从 iCal 内容中取出事件覆盖的日期
+fn parse_ical_dates(content: &str) -> Result<Vec<NaiveDate>> {
+    // `DTSTART;VALUE=DATE:20261001` 或 `DTSTART:20261001T090000Z`
+    let parse_property = |line: &str| -> Result<(NaiveDate, bool)> {
+        let value = line.rsplit(':').next().unwrap_or_default().trim();
+        let date = value.get(..8).unwrap_or(value);
+        let date = NaiveDate::parse_from_str(date, "%Y%m%d")
+            .map_err(|_| anyhow!(tr!(invalid_date, value)))?;
+        Ok((date, !value.contains('T')))
+    };
+
+    let mut dates = Vec::new();
+    let (mut start, mut end) = (None, None);
+    for line in content.lines().map(str::trim_end) {
+        if line == "BEGIN:VEVENT" {
+            (start, end) = (None, None);
+        } else if line.starts_with("DTSTART") {
+            start = Some(parse_property(line)?);
+        } else if line.starts_with("DTEND") {
+            end = Some(parse_property(line)?);
+        } else if line == "END:VEVENT"
+            && let Some((start, all_day)) = start
+        {
+            match end {
+                // 全天事件的结束日期不含在内
+                Some((end, _)) if all_day && end > start => {
+                    dates.extend(start.iter_days().take_while(|date| *date < end));
+                }
+                _ => dates.push(start),
+            }
+        }
+    }
+    Ok(dates)
+}
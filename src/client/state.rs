@@ -0,0 +1,75 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// 可跨进程重启保留的认领状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClaimState {
+    #[serde(default)]
+    pub successful_claims: i32,
+    #[serde(default)]
+    pub attempt_count: i32,
+    /// 已认领成功的任务ID，用于去重
+    #[serde(default, deserialize_with = "id_set")]
+    pub claimed_ids: BTreeSet<i64>,
+    /// 每日认领成功数（本地日期）
+    #[serde(default)]
+    pub daily_claims: BTreeMap<NaiveDate, i32>,
+}
+
+impl ClaimState {
+    /// 从文件加载状态，文件不存在时返回空状态
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| anyhow!(tr!(parse_state_failed, path.display(), e))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(anyhow!(tr!(read_state_failed, path.display(), e))),
+        }
+    }
+
+    /// 保存状态，先写临时文件再替换，避免中途退出导致文件损坏
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    /// 记录认领成功的任务
+    pub fn record_claims(&mut self, date: NaiveDate, task_ids: &[String], count: i32) {
+        self.claimed_ids
+            .extend(task_ids.iter().filter_map(|id| id.parse::<i64>().ok()));
+        *self.daily_claims.entry(date).or_insert(0) += count;
+    }
+
+    /// 指定日期的认领成功数
+    pub fn claims_on(&self, date: NaiveDate) -> i32 {
+        self.daily_claims.get(&date).copied().unwrap_or(0)
+    }
+}
+
+/// 旧版本状态文件中的ID以字符串保存，读取时兼容数字和字符串两种格式
+fn id_set<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeSet<i64>, D::Error> {
+    let values = Vec::<Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::Number(n) => n.as_i64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        })
+        .collect())
+}
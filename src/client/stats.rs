@@ -0,0 +1,503 @@
+use crate::tr;
+use chrono::{DateTime, FixedOffset, Timelike};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// 认领统计快照
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClaimStats {
+    pub attempts: i32,
+    pub total_claims: i32,
+    /// 发起认领请求的任务总数
+    pub requested_tasks: i32,
+    /// 认领成功率（成功数 / 请求认领的任务数）
+    pub success_rate: f64,
+    pub list_requests: u32,
+    pub avg_list_latency: Duration,
+    pub claim_requests: u32,
+    pub avg_claim_latency: Duration,
+    /// 任务列表请求的延迟分布
+    pub list_latency: LatencyStats,
+    /// 认领请求的延迟分布
+    pub claim_latency: LatencyStats,
+    /// 用户信息请求（校验 Cookie）的延迟分布
+    pub user_info_latency: LatencyStats,
+    /// 按从获取任务列表到认领完成的耗时分段统计的认领成功率，与 [`LISTED_TO_CLAIMED_BUCKETS`] 对应，
+    /// 最后多一段超过所有上界的
+    pub success_by_latency: Vec<LatencySuccess>,
+    /// 当前连续空池次数
+    pub empty_pool_streak: u32,
+    /// 最长连续空池次数
+    pub longest_empty_pool_streak: u32,
+    /// 按错误码统计的认领失败次数
+    pub failures_by_errno: BTreeMap<i32, u32>,
+    /// 网络或解析等非接口错误次数
+    pub request_errors: u32,
+    /// 按学科统计的认领成功数
+    pub claims_by_subject: BTreeMap<String, i32>,
+    /// 按小时（认领器配置时区的 0-23 时）统计的认领成功数
+    pub claims_by_hour: BTreeMap<u32, i32>,
+}
+
+/// 延迟直方图各桶的上界，超过最后一个上界的请求计入溢出桶
+pub const LATENCY_BUCKETS: [Duration; 12] = [
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(300),
+    Duration::from_millis(500),
+    Duration::from_millis(750),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// 一类请求的延迟分布，分位数由直方图估算，精度为桶的宽度
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub sum: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// 各桶的请求数（不累计），与 [`LATENCY_BUCKETS`] 对应，最后多一个溢出桶
+    pub buckets: Vec<u64>,
+}
+
+/// 从获取任务列表到认领完成的耗时分段上界
+pub const LISTED_TO_CLAIMED_BUCKETS: [Duration; 6] = [
+    Duration::from_millis(200),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// 一段耗时内请求认领的任务数和认领成功数
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LatencySuccess {
+    /// 该段的上界，超过所有上界的一段为 None
+    pub up_to: Option<Duration>,
+    pub requested: u32,
+    pub succeeded: u32,
+}
+
+impl LatencySuccess {
+    /// 认领成功率，没有请求时为 0
+    pub fn success_rate(&self) -> f64 {
+        if self.requested > 0 {
+            self.succeeded as f64 / self.requested as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// 形如 `<=500ms` 或 `>10s` 的分段名称
+    pub fn label(&self) -> String {
+        match self.up_to {
+            Some(up_to) => format!("<={}", format_duration(up_to)),
+            None => format!(
+                ">{}",
+                format_duration(LISTED_TO_CLAIMED_BUCKETS[LISTED_TO_CLAIMED_BUCKETS.len() - 1])
+            ),
+        }
+    }
+}
+
+/// 延迟直方图
+#[derive(Debug, Default)]
+pub(crate) struct LatencyHistogram {
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    count: u64,
+    sum: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn record(&mut self, latency: Duration) {
+        let bucket = LATENCY_BUCKETS.partition_point(|bound| *bound < latency);
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += latency;
+        self.max = self.max.max(latency);
+    }
+
+    /// 估算分位数：在所在的桶内按线性分布插值，不超过最大值
+    fn quantile(&self, q: f64) -> Duration {
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            if seen + count < rank {
+                seen += count;
+                continue;
+            }
+            let Some(upper) = LATENCY_BUCKETS.get(index) else {
+                return self.max;
+            };
+            let lower = index
+                .checked_sub(1)
+                .map_or(Duration::ZERO, |i| LATENCY_BUCKETS[i]);
+            let position = (rank - seen) as f64 / *count as f64;
+            return (lower + (*upper - lower).mul_f64(position)).min(self.max);
+        }
+        Duration::ZERO
+    }
+
+    pub(crate) fn stats(&self) -> LatencyStats {
+        LatencyStats {
+            count: self.count,
+            sum: self.sum,
+            p50: self.quantile(0.50),
+            p95: self.quantile(0.95),
+            p99: self.quantile(0.99),
+            max: self.max,
+            buckets: self.buckets.to_vec(),
+        }
+    }
+}
+
+impl LatencyStats {
+    /// 形如 `120/300/800ms` 的 p50/p95/p99
+    pub fn percentiles(&self) -> String {
+        format!(
+            "{}/{}/{}ms",
+            self.p50.as_millis(),
+            self.p95.as_millis(),
+            self.p99.as_millis()
+        )
+    }
+}
+
+/// 统计累加器，由认领器内部维护
+#[derive(Debug, Default)]
+pub(crate) struct StatsRecorder {
+    requested_tasks: i32,
+    list_requests: u32,
+    list_latency_total: Duration,
+    claim_requests: u32,
+    claim_latency_total: Duration,
+    list_latency: LatencyHistogram,
+    claim_latency: LatencyHistogram,
+    user_info_latency: LatencyHistogram,
+    /// 各耗时分段的（请求认领数，认领成功数）
+    success_by_latency: [(u32, u32); LISTED_TO_CLAIMED_BUCKETS.len() + 1],
+    empty_pool_streak: u32,
+    longest_empty_pool_streak: u32,
+    failures_by_errno: BTreeMap<i32, u32>,
+    request_errors: u32,
+    claims_by_subject: BTreeMap<String, i32>,
+    claims_by_hour: BTreeMap<u32, i32>,
+}
+
+impl StatsRecorder {
+    /// 记录一次任务列表请求
+    pub(crate) fn record_list(&mut self, latency: Duration, pool_size: usize) {
+        self.list_requests += 1;
+        self.list_latency_total += latency;
+        self.list_latency.record(latency);
+
+        if pool_size == 0 {
+            self.empty_pool_streak += 1;
+            self.longest_empty_pool_streak =
+                self.longest_empty_pool_streak.max(self.empty_pool_streak);
+        } else {
+            self.empty_pool_streak = 0;
+        }
+    }
+
+    /// 记录一次认领请求
+    pub(crate) fn record_claim(&mut self, latency: Duration, requested: usize, errno: i32) {
+        self.claim_requests += 1;
+        self.claim_latency_total += latency;
+        self.claim_latency.record(latency);
+        self.requested_tasks += requested as i32;
+
+        if errno != 0 {
+            *self.failures_by_errno.entry(errno).or_insert(0) += 1;
+        }
+    }
+
+    /// 记录按学科的认领成功数
+    pub(crate) fn record_subject_claims(&mut self, subject: &str, count: i32) {
+        *self
+            .claims_by_subject
+            .entry(subject.to_string())
+            .or_insert(0) += count;
+    }
+
+    /// 按认领时间所在的小时（认领器配置的时区）记录认领成功数
+    pub(crate) fn record_claimed(&mut self, count: i32, claimed_at: DateTime<FixedOffset>) {
+        *self.claims_by_hour.entry(claimed_at.hour()).or_insert(0) += count;
+    }
+
+    /// 记录一次用户信息请求
+    pub(crate) fn record_user_info(&mut self, latency: Duration) {
+        self.user_info_latency.record(latency);
+    }
+
+    /// 记录一个任务从获取列表到认领完成的耗时和是否认领成功
+    pub(crate) fn record_listed_to_claimed(&mut self, elapsed: Duration, succeeded: bool) {
+        let bucket = LISTED_TO_CLAIMED_BUCKETS.partition_point(|bound| *bound < elapsed);
+        let (requested, claimed) = &mut self.success_by_latency[bucket];
+        *requested += 1;
+        *claimed += succeeded as u32;
+    }
+
+    /// 记录一次请求错误
+    pub(crate) fn record_request_error(&mut self) {
+        self.request_errors += 1;
+    }
+
+    /// 生成统计快照
+    pub(crate) fn snapshot(&self, attempts: i32, total_claims: i32) -> ClaimStats {
+        let success_rate = if self.requested_tasks > 0 {
+            total_claims as f64 / self.requested_tasks as f64
+        } else {
+            0.0
+        };
+
+        ClaimStats {
+            attempts,
+            total_claims,
+            requested_tasks: self.requested_tasks,
+            success_rate,
+            list_requests: self.list_requests,
+            avg_list_latency: average(self.list_latency_total, self.list_requests),
+            claim_requests: self.claim_requests,
+            avg_claim_latency: average(self.claim_latency_total, self.claim_requests),
+            list_latency: self.list_latency.stats(),
+            claim_latency: self.claim_latency.stats(),
+            user_info_latency: self.user_info_latency.stats(),
+            success_by_latency: self
+                .success_by_latency
+                .iter()
+                .enumerate()
+                .map(|(index, (requested, succeeded))| LatencySuccess {
+                    up_to: LISTED_TO_CLAIMED_BUCKETS.get(index).copied(),
+                    requested: *requested,
+                    succeeded: *succeeded,
+                })
+                .collect(),
+            empty_pool_streak: self.empty_pool_streak,
+            longest_empty_pool_streak: self.longest_empty_pool_streak,
+            failures_by_errno: self.failures_by_errno.clone(),
+            request_errors: self.request_errors,
+            claims_by_subject: self.claims_by_subject.clone(),
+            claims_by_hour: self.claims_by_hour.clone(),
+        }
+    }
+}
+
+impl ClaimStats {
+    /// 认领最多的小时及其认领数
+    pub fn busiest_hour(&self) -> Option<(u32, i32)> {
+        self.claims_by_hour
+            .iter()
+            .max_by_key(|(hour, count)| (**count, std::cmp::Reverse(**hour)))
+            .map(|(hour, count)| (*hour, *count))
+    }
+
+    /// 渲染为 Prometheus 文本格式，包括各类请求的延迟直方图和认领计数
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "# HELP bedu_claim_request_duration_seconds Request latency by endpoint."
+        );
+        let _ = writeln!(text, "# TYPE bedu_claim_request_duration_seconds histogram");
+        for (endpoint, latency) in [
+            ("list", &self.list_latency),
+            ("claim", &self.claim_latency),
+            ("user_info", &self.user_info_latency),
+        ] {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&latency.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    text,
+                    "bedu_claim_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"{}\"}} {}",
+                    endpoint,
+                    bound.as_secs_f64(),
+                    cumulative
+                );
+            }
+            let _ = writeln!(
+                text,
+                "bedu_claim_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"+Inf\"}} {}",
+                endpoint, latency.count
+            );
+            let _ = writeln!(
+                text,
+                "bedu_claim_request_duration_seconds_sum{{endpoint=\"{}\"}} {}",
+                endpoint,
+                latency.sum.as_secs_f64()
+            );
+            let _ = writeln!(
+                text,
+                "bedu_claim_request_duration_seconds_count{{endpoint=\"{}\"}} {}",
+                endpoint, latency.count
+            );
+        }
+
+        for (name, help, value) in [
+            ("attempts", "Claim attempts.", self.attempts as i64),
+            ("claims", "Tasks claimed.", self.total_claims as i64),
+            (
+                "requested_tasks",
+                "Tasks requested in claim requests.",
+                self.requested_tasks as i64,
+            ),
+            (
+                "request_errors",
+                "Network or decode errors.",
+                self.request_errors as i64,
+            ),
+        ] {
+            let _ = writeln!(text, "# HELP bedu_claim_{}_total {}", name, help);
+            let _ = writeln!(text, "# TYPE bedu_claim_{}_total counter", name);
+            let _ = writeln!(text, "bedu_claim_{}_total {}", name, value);
+        }
+        text
+    }
+
+    /// 渲染为运行结束时打印的汇总表格
+    pub fn summary_table(&self) -> String {
+        let mut rows: Vec<(String, String)> = vec![
+            (tr!(attempts_label), self.attempts.to_string()),
+            (
+                tr!(claimed_label),
+                format!("{}/{}", self.total_claims, self.requested_tasks),
+            ),
+            (
+                tr!(success_rate_label),
+                format!("{:.1}%", self.success_rate * 100.0),
+            ),
+            (
+                tr!(avg_list_latency_label),
+                format!("{}ms", self.avg_list_latency.as_millis()),
+            ),
+            (
+                tr!(avg_claim_latency_label),
+                format!("{}ms", self.avg_claim_latency.as_millis()),
+            ),
+            (
+                tr!(list_latency_percentiles_label),
+                self.list_latency.percentiles(),
+            ),
+            (
+                tr!(claim_latency_percentiles_label),
+                self.claim_latency.percentiles(),
+            ),
+            (
+                tr!(longest_empty_pool_label),
+                self.longest_empty_pool_streak.to_string(),
+            ),
+            (
+                tr!(busiest_hour_label),
+                match self.busiest_hour() {
+                    Some((hour, count)) => format!("{:02}:00-{:02}:59 ({})", hour, hour, count),
+                    None => "-".to_string(),
+                },
+            ),
+        ];
+
+        for (subject, count) in &self.claims_by_subject {
+            rows.push((tr!(subject_row, subject), count.to_string()));
+        }
+        for bucket in self.success_by_latency.iter().filter(|b| b.requested > 0) {
+            rows.push((
+                tr!(latency_success_row, bucket.label()),
+                format!(
+                    "{}/{} ({:.1}%)",
+                    bucket.succeeded,
+                    bucket.requested,
+                    bucket.success_rate() * 100.0
+                ),
+            ));
+        }
+        for (errno, count) in &self.failures_by_errno {
+            rows.push((tr!(errno_row, errno), count.to_string()));
+        }
+        if self.request_errors > 0 {
+            rows.push((tr!(request_errors_label), self.request_errors.to_string()));
+        }
+
+        let rows: Vec<Vec<String>> = rows.into_iter().map(|(k, v)| vec![k, v]).collect();
+        render_table(&rows, false)
+    }
+}
+
+/// 渲染文本表格，`header` 为 true 时第一行作为表头
+pub fn render_table(rows: &[Vec<String>], header: bool) -> String {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| display_width(cell))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = format!(
+        "+{}+",
+        widths
+            .iter()
+            .map(|w| "-".repeat(w + 2))
+            .collect::<Vec<_>>()
+            .join("+")
+    );
+
+    let mut table = String::new();
+    let _ = writeln!(table, "{}", border);
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map(String::as_str).unwrap_or("");
+                format!(" {}{} ", cell, " ".repeat(width - display_width(cell)))
+            })
+            .collect();
+        let _ = writeln!(table, "|{}|", cells.join("|"));
+
+        if header && index == 0 {
+            let _ = writeln!(table, "{}", border);
+        }
+    }
+    let _ = write!(table, "{}", border);
+
+    table
+}
+
+/// 终端显示宽度，中日韩字符按两列计算
+pub fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| if (c as u32) >= 0x1100 { 2 } else { 1 })
+        .sum()
+}
+
+/// 整秒显示为 `2s`，否则显示为 `500ms`
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+fn average(total: Duration, count: u32) -> Duration {
+    if count == 0 {
+        Duration::ZERO
+    } else {
+        total / count
+    }
+}
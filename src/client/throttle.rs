@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::sleep;
+
+/// 保证相邻两次操作之间至少间隔指定时长
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+/// 持有期间其他调用方需等待，释放时记录本次操作时间
+pub(crate) struct ThrottlePermit<'a> {
+    last: MutexGuard<'a, Option<Instant>>,
+}
+
+impl Throttle {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// 等待到距上次操作满足最小间隔
+    pub(crate) async fn acquire(&self) -> ThrottlePermit<'_> {
+        let last = self.last.lock().await;
+        if let Some(previous) = *last {
+            let elapsed = previous.elapsed();
+            if elapsed < self.interval {
+                sleep(self.interval - elapsed).await;
+            }
+        }
+
+        ThrottlePermit { last }
+    }
+}
+
+impl Drop for ThrottlePermit<'_> {
+    fn drop(&mut self) {
+        *self.last = Some(Instant::now());
+    }
+}
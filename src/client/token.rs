@@ -0,0 +1,250 @@
+//! 动态令牌：部分接口要求请求头中携带 CSRF 令牌等动态值
+//!
+//! 令牌可以取自 Cookie 中的某个字段，也可以请求一个页面或接口后从响应中取出。[`HttpClient`]
+//! 在发送请求前附加令牌，取得的令牌缓存到过期为止；服务器返回 401 或 403 时重新获取令牌并重试
+//! 一次，用户无需手动复制请求头。
+//!
+//! [`HttpClient`]: crate::client::HttpClient
+
+use crate::error::BeduError;
+use crate::tr;
+use reqwest::header::{HeaderName, HeaderValue};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 取得的令牌默认缓存 10 分钟
+pub const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(600);
+
+/// 页面令牌规格中表示令牌位置的占位符
+const PLACEHOLDER: &str = "{}";
+
+/// 令牌的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Cookie 中该字段的值
+    Cookie(String),
+    /// 请求该地址，按 JSON Pointer（如 `/data/csrfToken`）取出响应中的值
+    Json { url: String, pointer: String },
+    /// 请求该地址，取出页面中位于 `prefix` 与 `suffix` 之间的文本
+    Page {
+        url: String,
+        prefix: String,
+        suffix: String,
+    },
+}
+
+impl TokenSource {
+    /// 需要请求的地址，取自 Cookie 时返回 None
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Self::Cookie(_) => None,
+            Self::Json { url, .. } | Self::Page { url, .. } => Some(url),
+        }
+    }
+
+    /// 从响应体中取出令牌
+    pub fn extract(&self, body: &str) -> Option<String> {
+        match self {
+            Self::Cookie(_) => None,
+            Self::Json { pointer, .. } => {
+                match serde_json::from_str::<Value>(body).ok()?.pointer(pointer)? {
+                    Value::String(token) => Some(token.clone()),
+                    Value::Number(token) => Some(token.to_string()),
+                    _ => None,
+                }
+            }
+            Self::Page { prefix, suffix, .. } => {
+                let (_, rest) = body.split_once(prefix.as_str())?;
+                let (token, _) = rest.split_once(suffix.as_str())?;
+                Some(token.to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cookie(name) => write!(f, "cookie:{}", name),
+            Self::Json { url, pointer } => write!(f, "json:{}#{}", url, pointer),
+            Self::Page {
+                url,
+                prefix,
+                suffix,
+            } => write!(f, "page:{}#{}{}{}", url, prefix, PLACEHOLDER, suffix),
+        }
+    }
+}
+
+/// 每个请求附带的动态令牌
+///
+/// 可以在代码中构造，也可以从 `<请求头>=<来源>` 形式的字符串解析，来源有三种写法：
+///
+/// - `cookie:<字段名>`：Cookie 中该字段的值
+/// - `json:<地址>#<JSON Pointer>`：请求该地址，取出 JSON 响应中的值
+/// - `page:<地址>#<前缀>{}<后缀>`：请求该地址，取出页面中 `{}` 位置的文本
+///
+/// 地址以 `/` 开头时相对于服务器基础URL。
+///
+/// ```
+/// use bedu_claim::client::{Token, TokenSource};
+///
+/// let token: Token = "X-Csrf-Token=cookie:csrftoken".parse()?;
+/// assert_eq!(token.source(), &TokenSource::Cookie("csrftoken".to_string()));
+///
+/// let token: Token = r#"X-Csrf-Token=page:/edushop/#csrfToken: "{}""#.parse()?;
+/// assert_eq!(
+///     token.source().extract(r#"<script>window.csrfToken: "abc123";</script>"#),
+///     Some("abc123".to_string())
+/// );
+/// # Ok::<(), bedu_claim::BeduError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    header: String,
+    source: TokenSource,
+    ttl: Duration,
+}
+
+impl Token {
+    /// 将 `source` 取得的令牌放在请求头 `header` 中
+    pub fn new(header: impl Into<String>, source: TokenSource) -> Self {
+        Self {
+            header: header.into(),
+            source,
+            ttl: DEFAULT_TOKEN_TTL,
+        }
+    }
+
+    /// 取得的令牌缓存多久后重新获取，默认 [`DEFAULT_TOKEN_TTL`]；取自 Cookie 的令牌不会过期
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 请求头名称
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// 令牌的来源
+    pub fn source(&self) -> &TokenSource {
+        &self.source
+    }
+}
+
+impl FromStr for Token {
+    type Err = BeduError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BeduError::Config(tr!(invalid_token_spec, s));
+        let (header, source) = s.split_once('=').ok_or_else(invalid)?;
+        let (kind, rest) = source.split_once(':').ok_or_else(invalid)?;
+        let source = match kind.trim() {
+            "cookie" if !rest.trim().is_empty() => TokenSource::Cookie(rest.trim().to_string()),
+            "json" => {
+                let (url, pointer) = rest.split_once('#').ok_or_else(invalid)?;
+                TokenSource::Json {
+                    url: url.trim().to_string(),
+                    pointer: pointer.trim().to_string(),
+                }
+            }
+            "page" => {
+                let (url, pattern) = rest.split_once('#').ok_or_else(invalid)?;
+                let (prefix, suffix) = pattern.split_once(PLACEHOLDER).ok_or_else(invalid)?;
+                if prefix.is_empty() || suffix.is_empty() {
+                    return Err(invalid());
+                }
+                TokenSource::Page {
+                    url: url.trim().to_string(),
+                    prefix: prefix.to_string(),
+                    suffix: suffix.to_string(),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        if source.url().is_some_and(str::is_empty) {
+            return Err(invalid());
+        }
+        Ok(Self::new(header.trim(), source))
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.header, self.source)
+    }
+}
+
+/// 客户端中的令牌及其缓存
+pub(crate) struct TokenState {
+    pub(crate) token: Token,
+    pub(crate) header: HeaderName,
+    /// 缓存的令牌和取得的时间；获取期间持有锁，并发请求只获取一次
+    cached: Mutex<Option<(HeaderValue, Instant)>>,
+}
+
+impl TokenState {
+    /// 检查请求头名称，取自 Cookie 的令牌在此从 `cookie` 中取出
+    pub(crate) fn new(token: Token, cookie: &str) -> Result<Self, BeduError> {
+        let header = HeaderName::from_bytes(token.header.as_bytes())
+            .map_err(|e| BeduError::Config(tr!(invalid_header_name, token.header, e)))?;
+        let cached = match &token.source {
+            TokenSource::Cookie(name) => {
+                let value = cookie
+                    .split(';')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(key, _)| key.trim() == name)
+                    .map(|(_, value)| value.trim())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| BeduError::Config(tr!(token_cookie_missing, name)))?;
+                Some((sensitive_value(&token.header, value)?, Instant::now()))
+            }
+            _ => None,
+        };
+        Ok(Self {
+            token,
+            header,
+            cached: Mutex::new(cached),
+        })
+    }
+
+    /// 令牌需要从服务器获取，失效后可以刷新
+    pub(crate) fn refreshable(&self) -> bool {
+        self.token.source.url().is_some()
+    }
+
+    /// 返回缓存的令牌，未缓存、已过期或 `refresh` 时调用 `fetch` 重新获取
+    pub(crate) async fn get<F>(&self, refresh: bool, fetch: F) -> anyhow::Result<HeaderValue>
+    where
+        F: AsyncFnOnce(&Token) -> anyhow::Result<String>,
+    {
+        let mut cached = self.cached.lock().await;
+        if let Some((value, fetched_at)) = cached.as_ref()
+            && (!self.refreshable() || (!refresh && fetched_at.elapsed() < self.token.ttl))
+        {
+            return Ok(value.clone());
+        }
+        let value = sensitive_value(&self.token.header, &fetch(&self.token).await?)?;
+        *cached = Some((value.clone(), Instant::now()));
+        Ok(value)
+    }
+
+    /// 丢弃缓存的令牌，下次请求时重新获取
+    pub(crate) async fn invalidate(&self) {
+        if self.refreshable() {
+            *self.cached.lock().await = None;
+        }
+    }
+}
+
+/// 令牌与 Cookie 一样不应出现在日志中
+fn sensitive_value(name: &str, value: &str) -> Result<HeaderValue, BeduError> {
+    let mut value = HeaderValue::from_str(value)
+        .map_err(|e| BeduError::Config(tr!(invalid_header_value, name, e)))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
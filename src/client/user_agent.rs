@@ -0,0 +1,83 @@
+use crate::tr;
+use anyhow::{Result, anyhow};
+use rand::seq::SliceRandom;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// User-Agent 轮换方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UserAgentRotation {
+    /// 创建客户端时随机选定一个，整个会话保持不变
+    #[default]
+    PerSession,
+    /// 每个请求随机选取
+    PerRequest,
+}
+
+impl FromStr for UserAgentRotation {
+    type Err = anyhow::Error;
+
+    /// 解析 `session` 或 `request`
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "session" | "per-session" => Ok(Self::PerSession),
+            "request" | "per-request" => Ok(Self::PerRequest),
+            _ => Err(anyhow!(tr!(invalid_ua_rotation, s))),
+        }
+    }
+}
+
+/// User-Agent 池，长时间运行时轮换使用，降低被识别的概率
+#[derive(Debug, Clone)]
+pub struct UserAgentPool {
+    agents: Vec<String>,
+    rotation: UserAgentRotation,
+}
+
+impl UserAgentPool {
+    /// 从 User-Agent 列表创建，空白项会被忽略
+    pub fn new(agents: Vec<String>, rotation: UserAgentRotation) -> Result<Self> {
+        let agents: Vec<String> = agents
+            .into_iter()
+            .map(|agent| agent.trim().to_string())
+            .filter(|agent| !agent.is_empty())
+            .collect();
+        if agents.is_empty() {
+            return Err(anyhow!(tr!(empty_ua_pool)));
+        }
+
+        Ok(Self { agents, rotation })
+    }
+
+    /// 从文件加载，每行一个 User-Agent，`#` 开头的行视为注释
+    pub fn from_file(path: &Path, rotation: UserAgentRotation) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!(tr!(read_ua_file_failed, path.display(), e)))?;
+        let agents = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        Self::new(agents, rotation).map_err(|e| anyhow!(tr!(invalid_ua_file, path.display(), e)))
+    }
+
+    /// 轮换方式
+    pub fn rotation(&self) -> UserAgentRotation {
+        self.rotation
+    }
+
+    /// 池中的全部 User-Agent
+    pub fn agents(&self) -> &[String] {
+        &self.agents
+    }
+
+    /// 随机选取一个 User-Agent
+    pub fn pick(&self) -> &str {
+        self.agents
+            .choose(&mut rand::thread_rng())
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
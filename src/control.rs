@@ -0,0 +1,166 @@
+//! 内嵌的 HTTP 控制接口（需启用 `control-server` 特性）
+//!
+//! 无人值守部署时通过 HTTP 远程查看和管理认领器，除 `/metrics` 外所有接口返回 JSON：
+//!
+//! | 方法 | 路径 | 说明 |
+//! |------|------|------|
+//! | GET | `/` | 网页仪表盘：实时统计和可筛选的已认领任务表 |
+//! | GET | `/status` | 运行状态和统计 |
+//! | POST | `/pause` | 暂停认领 |
+//! | POST | `/resume` | 恢复认领 |
+//! | PUT | `/limit` | 修改认领限制，请求体 `{"limit": 20}` |
+//! | GET | `/claims?count=20` | 最近认领成功的任务（新的在前），默认 20 条 |
+//! | POST | `/reload` | 请求重新加载配置，由订阅 [`ClaimEvent::ReloadRequested`](crate::client::ClaimEvent::ReloadRequested) 的一方执行 |
+//! | GET | `/healthz` | 存活探针：Cookie 有效且接口可访问时返回 200，否则返回 503 |
+//! | GET | `/metrics` | Prometheus 指标：列表、认领、用户信息请求的延迟直方图和认领计数 |
+//!
+//! 接口没有鉴权，请只监听本机地址或放在反向代理之后。
+
+use crate::tr;
+use anyhow::Result;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use crate::client::bedu_api::BeduApi;
+use crate::client::{AutoClaimer, ClaimedTask, ClaimerStatus};
+
+type SharedClaimer<A> = State<Arc<AutoClaimer<A>>>;
+
+/// 网页仪表盘，定时轮询 `/status` 和 `/claims`
+const DASHBOARD: &str = include_str!("dashboard.html");
+
+/// 控制接口的路由，可挂载到已有的 axum 应用中
+pub fn router<A: BeduApi + 'static>(claimer: Arc<AutoClaimer<A>>) -> Router {
+    Router::new()
+        .route("/", get(dashboard))
+        .route("/status", get(status::<A>))
+        .route("/pause", post(pause::<A>))
+        .route("/resume", post(resume::<A>))
+        .route("/limit", put(set_limit::<A>))
+        .route("/claims", get(claims::<A>))
+        .route("/reload", post(reload::<A>))
+        .route("/healthz", get(healthz::<A>))
+        .route("/metrics", get(metrics::<A>))
+        .with_state(claimer)
+}
+
+/// 在 `listener` 上提供控制接口，直到 `shutdown` 完成
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{AutoClaimConfig, AutoClaimer};
+/// use std::sync::Arc;
+/// use tokio::net::TcpListener;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let claimer = Arc::new(AutoClaimer::new(AutoClaimConfig::default())?);
+/// let listener = TcpListener::bind("127.0.0.1:8787").await?;
+/// let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+/// let server = tokio::spawn(bedu_claim::control::serve(listener, claimer.clone(), async {
+///     let _ = stopped.await;
+/// }));
+///
+/// let summary = claimer.start().await;
+/// let _ = stop.send(());
+/// server.await??;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve<A: BeduApi + 'static>(
+    listener: TcpListener,
+    claimer: Arc<AutoClaimer<A>>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    axum::serve(listener, router(claimer))
+        .with_graceful_shutdown(shutdown)
+        .await?;
+    Ok(())
+}
+
+async fn dashboard() -> Html<&'static str> {
+    Html(DASHBOARD)
+}
+
+async fn status<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Json<ClaimerStatus> {
+    Json(claimer.status().await)
+}
+
+async fn pause<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Json<ClaimerStatus> {
+    claimer.pause();
+    Json(claimer.status().await)
+}
+
+async fn resume<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Json<ClaimerStatus> {
+    claimer.resume();
+    Json(claimer.status().await)
+}
+
+#[derive(Deserialize)]
+struct LimitRequest {
+    limit: i32,
+}
+
+async fn set_limit<A: BeduApi>(
+    State(claimer): SharedClaimer<A>,
+    Json(request): Json<LimitRequest>,
+) -> Response {
+    if request.limit <= 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": tr!(claim_limit_not_positive) })),
+        )
+            .into_response();
+    }
+    claimer.set_claim_limit(request.limit).await;
+    Json(claimer.status().await).into_response()
+}
+
+/// 只发出重新加载请求，配置在订阅方异步应用，因此返回 202
+async fn reload<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Response {
+    claimer.request_reload();
+    (StatusCode::ACCEPTED, Json(claimer.status().await)).into_response()
+}
+
+#[derive(Deserialize)]
+struct ClaimsQuery {
+    count: Option<usize>,
+}
+
+async fn claims<A: BeduApi>(
+    State(claimer): SharedClaimer<A>,
+    Query(query): Query<ClaimsQuery>,
+) -> Json<Vec<ClaimedTask>> {
+    let mut tasks = claimer.get_claimed_tasks().await;
+    let count = query.count.unwrap_or(20);
+    tasks.drain(..tasks.len().saturating_sub(count));
+    tasks.reverse();
+    Json(tasks)
+}
+
+/// 用当前 Cookie 请求用户信息，供 Docker/Kubernetes 存活探针使用；探针请求不计入延迟统计
+async fn healthz<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Response {
+    match claimer.check_user().await {
+        Ok(user) => Json(json!({ "status": "ok", "user": user })).into_response(),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "unhealthy", "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+async fn metrics<A: BeduApi>(State(claimer): SharedClaimer<A>) -> Response {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        claimer.get_stats().await.to_prometheus(),
+    )
+        .into_response()
+}
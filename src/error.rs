@@ -0,0 +1,67 @@
+//! 库的错误类型
+
+use crate::tr;
+use thiserror::Error;
+
+/// 未登录（Cookie 无效或已过期）时接口返回的错误码
+pub const NOT_LOGGED_IN_ERRNO: i32 = 110000;
+
+/// 调用百度教育接口时的错误
+#[derive(Debug, Error)]
+pub enum BeduError {
+    /// 接口返回非 0 的 `errno`
+    #[error("{}", tr!(api_error, .errno, .errmsg, log_id_suffix(.log_id)))]
+    Api {
+        errno: i32,
+        errmsg: String,
+        /// 服务器返回的 logid/traceid，便于向服务端排查
+        log_id: Option<String>,
+    },
+    /// 接口返回成功但缺少 `data`
+    #[error("{}", tr!(missing_data))]
+    MissingData,
+    /// 响应体无法按预期结构解析
+    #[error("{}", tr!(decode_error, .message, .body))]
+    Decode { message: String, body: String },
+    /// 客户端配置无效，如请求头包含非法字符
+    #[error("{}", tr!(config_error, .0))]
+    Config(String),
+    /// 构建客户端或发送请求失败（代理、TLS 配置错误等）
+    #[cfg(feature = "client")]
+    #[error("{}", tr!(http_error, .0))]
+    Http(#[from] reqwest::Error),
+}
+
+fn log_id_suffix(log_id: &Option<String>) -> String {
+    log_id
+        .as_ref()
+        .map(|id| tr!(log_id_suffix, id))
+        .unwrap_or_default()
+}
+
+impl BeduError {
+    /// 接口返回的错误码
+    pub fn errno(&self) -> Option<i32> {
+        match self {
+            BeduError::Api { errno, .. } => Some(*errno),
+            BeduError::MissingData | BeduError::Decode { .. } | BeduError::Config(_) => None,
+            #[cfg(feature = "client")]
+            BeduError::Http(_) => None,
+        }
+    }
+
+    /// 是否为未登录错误，通常表示 Cookie 已过期
+    pub fn is_not_logged_in(&self) -> bool {
+        self.errno() == Some(NOT_LOGGED_IN_ERRNO)
+    }
+
+    /// 服务器返回的 logid/traceid
+    pub fn log_id(&self) -> Option<&str> {
+        match self {
+            BeduError::Api { log_id, .. } => log_id.as_deref(),
+            BeduError::MissingData | BeduError::Decode { .. } | BeduError::Config(_) => None,
+            #[cfg(feature = "client")]
+            BeduError::Http(_) => None,
+        }
+    }
+}
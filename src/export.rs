@@ -0,0 +1,113 @@
+//! 认领记录导出（CSV / JSON）
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::client::ClaimedTask;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// 根据文件扩展名推断导出格式
+    pub fn from_path(path: &Path) -> Result<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| anyhow!(tr!(export_format_unknown, path.display())))?
+            .parse()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!(tr!(export_format_unsupported, s))),
+        }
+    }
+}
+
+/// 导出的一行认领记录
+#[derive(Debug, Serialize)]
+struct ExportRow<'a> {
+    task_id: i64,
+    clue_id: i64,
+    task_type: &'a str,
+    brief: &'a str,
+    subject_id: i32,
+    subject_name: &'a str,
+    step_id: i32,
+    step_name: &'a str,
+    clue_type_name: &'a str,
+    attempt: i32,
+    claimed_at: String,
+}
+
+impl<'a> From<&'a ClaimedTask> for ExportRow<'a> {
+    fn from(claim: &'a ClaimedTask) -> Self {
+        Self {
+            task_id: claim.task.task_id,
+            clue_id: claim.task.clue_id,
+            task_type: &claim.task_type,
+            brief: &claim.task.brief,
+            subject_id: claim.task.subject,
+            subject_name: &claim.task.subject_name,
+            step_id: claim.task.step,
+            step_name: &claim.task.step_name,
+            clue_type_name: &claim.task.clue_type_name,
+            attempt: claim.attempt,
+            claimed_at: claim.claimed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+/// 将认领记录按指定格式写出
+pub fn write_claims<W: Write>(
+    claims: &[ClaimedTask],
+    format: ExportFormat,
+    writer: W,
+) -> Result<()> {
+    let rows: Vec<ExportRow> = claims.iter().map(ExportRow::from).collect();
+
+    match format {
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for row in &rows {
+                csv_writer.serialize(row)?;
+            }
+            csv_writer.flush()?;
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(writer, &rows)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 将认领记录导出到文件，格式由扩展名决定（.csv / .json）
+pub fn export_claims(claims: &[ClaimedTask], path: &Path) -> Result<()> {
+    let format = ExportFormat::from_path(path)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    // 写入 BOM，便于 Excel 正确识别中文
+    if format == ExportFormat::Csv {
+        writer.write_all("\u{feff}".as_bytes())?;
+    }
+    write_claims(claims, format, &mut writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+//! gRPC 控制与事件服务（需启用 `grpc` 特性）
+//!
+//! 集中调度多个认领实例时，调度端通过 gRPC 启动、停止认领循环，修改认领限制或暂停认领，
+//! 并以服务端流订阅认领事件。服务定义见仓库中的 `proto/bedu_claim.proto`，
+//! 调度端可据此生成任意语言的客户端。
+//!
+//! 接口没有鉴权，请只监听内网地址。
+
+use crate::tr;
+use anyhow::Result;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, broadcast, watch};
+use tokio::task::JoinHandle;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::client::bedu_api::BeduApi;
+use crate::client::{AutoClaimer, ClaimSummary, HttpClient};
+
+/// gRPC 消息与生成的服务代码，字段与 `proto/bedu_claim.proto` 一一对应
+pub mod proto {
+    #[derive(Clone, Copy, PartialEq, prost::Message)]
+    pub struct StartRequest {}
+
+    #[derive(Clone, Copy, PartialEq, prost::Message)]
+    pub struct StopRequest {}
+
+    #[derive(Clone, Copy, PartialEq, prost::Message)]
+    pub struct StatusRequest {}
+
+    #[derive(Clone, Copy, PartialEq, prost::Message)]
+    pub struct ConfigureRequest {
+        /// 新的认领限制，必须大于0
+        #[prost(int32, optional, tag = "1")]
+        pub claim_limit: Option<i32>,
+        /// true 暂停认领，false 恢复认领
+        #[prost(bool, optional, tag = "2")]
+        pub paused: Option<bool>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct WatchEventsRequest {
+        /// 只推送这些类型的事件（如 `claimed`），为空时推送全部事件
+        #[prost(string, repeated, tag = "1")]
+        pub events: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ClaimerStatus {
+        /// 认领循环是否在运行
+        #[prost(bool, tag = "1")]
+        pub running: bool,
+        /// 累计认领成功数
+        #[prost(int32, tag = "2")]
+        pub claimed: i32,
+        #[prost(int32, tag = "3")]
+        pub claim_limit: i32,
+        /// 今天已认领成功数
+        #[prost(int32, tag = "4")]
+        pub claimed_today: i32,
+        #[prost(int32, tag = "5")]
+        pub attempts: i32,
+        #[prost(bool, tag = "6")]
+        pub paused: bool,
+        /// 是否因待完成任务过多而休眠
+        #[prost(bool, tag = "7")]
+        pub dormant: bool,
+        #[prost(double, tag = "8")]
+        pub success_rate: f64,
+        /// 上一次认领循环出错退出的原因
+        #[prost(string, optional, tag = "9")]
+        pub last_error: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ClaimEvent {
+        /// 事件类型，与 NDJSON 输出的 `event` 字段相同
+        #[prost(string, tag = "1")]
+        pub event: String,
+        /// 完整事件，与 NDJSON 输出的一行相同
+        #[prost(string, tag = "2")]
+        pub json: String,
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/bedu_claim.v1.ClaimerControl.rs"));
+}
+
+pub use proto::claimer_control_server::{ClaimerControl, ClaimerControlServer};
+
+#[derive(Default)]
+struct RunState {
+    handle: Option<JoinHandle<Result<ClaimSummary>>>,
+    last_error: Option<String>,
+}
+
+impl RunState {
+    /// 回收已经结束的认领循环，记录出错原因
+    async fn reap(&mut self) {
+        let Some(handle) = self.handle.take_if(|handle| handle.is_finished()) else {
+            return;
+        };
+        self.last_error = match handle.await {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(e) => Some(tr!(claimer_crashed, e)),
+        };
+    }
+}
+
+/// 管理一个认领器的 gRPC 服务，认领循环在独立的 tokio 任务中运行
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{AutoClaimConfig, AutoClaimer};
+/// use bedu_claim::grpc::ClaimerService;
+/// use std::sync::Arc;
+/// use tokio::net::TcpListener;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let claimer = Arc::new(AutoClaimer::new(AutoClaimConfig::default())?);
+/// let service = Arc::new(ClaimerService::new(claimer));
+/// service.start().await;
+///
+/// let listener = TcpListener::bind("0.0.0.0:50051").await?;
+/// bedu_claim::grpc::serve(listener, service.clone(), async {
+///     let _ = tokio::signal::ctrl_c().await;
+/// })
+/// .await?;
+/// service.stop().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClaimerService<A = HttpClient> {
+    claimer: Arc<AutoClaimer<A>>,
+    run: Mutex<RunState>,
+    /// 服务关闭时通知事件流结束，否则优雅关闭会一直等待订阅者
+    closing: watch::Sender<bool>,
+}
+
+impl<A: BeduApi + 'static> ClaimerService<A> {
+    pub fn new(claimer: Arc<AutoClaimer<A>>) -> Self {
+        Self {
+            claimer,
+            run: Mutex::new(RunState::default()),
+            closing: watch::Sender::new(false),
+        }
+    }
+
+    /// 启动认领循环，已在运行时返回 false
+    pub async fn start(&self) -> bool {
+        let mut run = self.run.lock().await;
+        run.reap().await;
+        if run.handle.is_some() {
+            return false;
+        }
+
+        let claimer = self.claimer.clone();
+        run.handle = Some(tokio::spawn(async move { claimer.start().await }));
+        run.last_error = None;
+        info!("{}", tr!(claim_loop_started));
+        true
+    }
+
+    /// 停止认领循环，未在运行时返回 false
+    pub async fn stop(&self) -> bool {
+        let mut run = self.run.lock().await;
+        run.reap().await;
+        let Some(handle) = run.handle.take() else {
+            return false;
+        };
+
+        handle.abort();
+        let _ = handle.await;
+        if let Err(e) = self.claimer.save_state().await {
+            warn!(error = %e, "{}", tr!(save_state_failed, e));
+        }
+        info!("{}", tr!(claim_loop_stopped));
+        true
+    }
+
+    /// 认领循环是否在运行
+    pub async fn is_running(&self) -> bool {
+        let mut run = self.run.lock().await;
+        run.reap().await;
+        run.handle.is_some()
+    }
+
+    async fn status(&self) -> proto::ClaimerStatus {
+        let (running, last_error) = {
+            let mut run = self.run.lock().await;
+            run.reap().await;
+            (run.handle.is_some(), run.last_error.clone())
+        };
+        let status = self.claimer.status().await;
+        proto::ClaimerStatus {
+            running,
+            claimed: status.claimed,
+            claim_limit: status.claim_limit,
+            claimed_today: status.claimed_today,
+            attempts: status.attempts,
+            paused: status.paused,
+            dormant: status.dormant,
+            success_rate: status.stats.success_rate,
+            last_error,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<A: BeduApi + 'static> ClaimerControl for ClaimerService<A> {
+    async fn start(
+        &self,
+        _request: Request<proto::StartRequest>,
+    ) -> Result<Response<proto::ClaimerStatus>, Status> {
+        if !ClaimerService::start(self).await {
+            return Err(Status::failed_precondition(tr!(claimer_already_running)));
+        }
+        Ok(Response::new(self.status().await))
+    }
+
+    async fn stop(
+        &self,
+        _request: Request<proto::StopRequest>,
+    ) -> Result<Response<proto::ClaimerStatus>, Status> {
+        if !ClaimerService::stop(self).await {
+            return Err(Status::failed_precondition(tr!(claimer_not_running)));
+        }
+        Ok(Response::new(self.status().await))
+    }
+
+    async fn configure(
+        &self,
+        request: Request<proto::ConfigureRequest>,
+    ) -> Result<Response<proto::ClaimerStatus>, Status> {
+        let request = request.into_inner();
+        if let Some(limit) = request.claim_limit {
+            if limit <= 0 {
+                return Err(Status::invalid_argument(tr!(claim_limit_not_positive)));
+            }
+            self.claimer.set_claim_limit(limit).await;
+        }
+        match request.paused {
+            Some(true) => self.claimer.pause(),
+            Some(false) => self.claimer.resume(),
+            None => {}
+        }
+        Ok(Response::new(self.status().await))
+    }
+
+    async fn get_status(
+        &self,
+        _request: Request<proto::StatusRequest>,
+    ) -> Result<Response<proto::ClaimerStatus>, Status> {
+        Ok(Response::new(self.status().await))
+    }
+
+    type WatchEventsStream = BoxStream<'static, Result<proto::ClaimEvent, Status>>;
+
+    async fn watch_events(
+        &self,
+        request: Request<proto::WatchEventsRequest>,
+    ) -> Result<Response<Self::WatchEventsStream>, Status> {
+        let filter = request.into_inner().events;
+        let mut closing = self.closing.subscribe();
+        let events = futures::stream::unfold(self.claimer.subscribe(), |mut receiver| async {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "{}", tr!(grpc_events_lagged, skipped));
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .filter_map(move |event| {
+            let kind = serde_json::to_value(&event)
+                .ok()
+                .and_then(|value| value["event"].as_str().map(str::to_string))
+                .unwrap_or_default();
+            let wanted = filter.is_empty() || filter.contains(&kind);
+            let message = event
+                .to_json_line()
+                .ok()
+                .map(|json| proto::ClaimEvent { event: kind, json });
+            async move { message.filter(|_| wanted).map(Ok) }
+        })
+        .take_until(async move {
+            let _ = closing.wait_for(|closing| *closing).await;
+        });
+        Ok(Response::new(events.boxed()))
+    }
+}
+
+/// 在 `listener` 上提供 gRPC 服务，直到 `shutdown` 完成
+///
+/// 关闭时结束所有事件订阅，但不会停止认领循环，需要时再调用 [`ClaimerService::stop`]。
+pub async fn serve<A: BeduApi + 'static>(
+    listener: TcpListener,
+    service: Arc<ClaimerService<A>>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let closing = service.closing.clone();
+    Server::builder()
+        .add_service(ClaimerControlServer::from_arc(service))
+        .serve_with_incoming_shutdown(TcpIncoming::from(listener), async move {
+            shutdown.await;
+            closing.send_replace(true);
+        })
+        .await?;
+    Ok(())
+}
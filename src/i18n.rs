@@ -0,0 +1,597 @@
+//! 日志、错误信息和命令输出的中英文消息目录
+//!
+//! 默认输出中文，调用 [`set_lang`] 切换为英文（命令行为 `--lang en`）。每条消息在本文件末尾的目录中
+//! 以键名登记中英文格式串，调用处用 [`tr!`](crate::tr) 按当前语言格式化，格式串和参数在编译期检查：
+//!
+//! ```
+//! use bedu_claim::i18n::{Lang, set_lang};
+//!
+//! set_lang(Lang::En);
+//! assert_eq!(bedu_claim::tr!(limit_changed, 10, 20), "Claim limit changed from 10 to 20");
+//! ```
+//!
+//! 服务器返回的错误信息、任务简介等原始数据保持原样。
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    /// 中文
+    #[default]
+    Zh,
+    /// 英文
+    En,
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    /// 解析 `zh`/`en`，也接受 `zh-CN`、`en_US.UTF-8` 这类区域设置
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_ascii_lowercase();
+        if value == "zh" || value.starts_with("zh-") || value.starts_with("zh_") {
+            Ok(Lang::Zh)
+        } else if value == "en" || value.starts_with("en-") || value.starts_with("en_") {
+            Ok(Lang::En)
+        } else {
+            Err(format!("无效的语言: {}，可选 zh、en", s))
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        })
+    }
+}
+
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+/// 设置全局输出语言，之后格式化的消息都使用该语言
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+/// 当前输出语言
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 按当前语言格式化目录中的消息，返回 `String`
+///
+/// 第一个参数为消息键名，其余参数与格式串中的 `{}` 一一对应，中英文格式串的参数个数相同。
+#[macro_export]
+macro_rules! tr {
+    ($key:ident $(, $arg:expr)* $(,)?) => {
+        match $crate::i18n::lang() {
+            $crate::i18n::Lang::Zh => format!($crate::__messages!($key __zh) $(, $arg)*),
+            $crate::i18n::Lang::En => format!($crate::__messages!($key __en) $(, $arg)*),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __zh {
+    ($zh:literal, $en:literal) => {
+        $zh
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __en {
+    ($zh:literal, $en:literal) => {
+        $en
+    };
+}
+
+/// 消息目录：`(键名 $l:ident) => { $crate::$l!("中文", "English") };`，按模块分组
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __messages {
+    // 错误类型
+    (api_error $l:ident) => { $crate::$l!("接口返回错误，错误码: {}，错误信息: {}{}", "API error, errno: {}, errmsg: {}{}") };
+    (log_id_suffix $l:ident) => { $crate::$l!("，logid: {}", ", logid: {}") };
+    (missing_data $l:ident) => { $crate::$l!("接口响应缺少 data 字段", "API response is missing the data field") };
+    (decode_error $l:ident) => { $crate::$l!("解析接口响应失败: {}, body: {}", "Failed to parse API response: {}, body: {}") };
+    (config_error $l:ident) => { $crate::$l!("客户端配置错误: {}", "Client configuration error: {}") };
+    (http_error $l:ident) => { $crate::$l!("HTTP 请求失败: {}", "HTTP request failed: {}") };
+
+    // 通用
+    (save_state_failed $l:ident) => { $crate::$l!("保存状态文件失败: {}", "Failed to save state file: {}") };
+    (fetch_task_list_failed $l:ident) => { $crate::$l!("获取任务列表失败: {}", "Failed to fetch task list: {}") };
+    (fetch_labels_failed $l:ident) => { $crate::$l!("获取筛选标签失败: {}", "Failed to fetch filter labels: {}") };
+    (fetch_clue_detail_failed $l:ident) => { $crate::$l!("获取线索详情失败: {}", "Failed to fetch clue details: {}") };
+    (claimer_crashed $l:ident) => { $crate::$l!("认领器异常退出: {}", "Claimer exited abnormally: {}") };
+    (fetch_user_info_failed $l:ident) => { $crate::$l!("获取用户信息失败: {}", "Failed to fetch user info: {}") };
+    (claim_limit_not_positive $l:ident) => { $crate::$l!("认领限制必须大于0", "Claim limit must be greater than 0") };
+    (filter_id_not_found $l:ident) => { $crate::$l!("{}ID {} 不存在，可选值: {}", "{} ID {} does not exist, available values: {}") };
+    (filter_name_not_found $l:ident) => { $crate::$l!("{} \"{}\" 不存在，可选值: {}", "{} \"{}\" does not exist, available values: {}") };
+    (filter_name_suggest $l:ident) => { $crate::$l!("{} \"{}\" 不存在，是否想输入: {}？可选值: {}", "{} \"{}\" does not exist, did you mean: {}? Available values: {}") };
+
+    // 认领器
+    (limit_changed $l:ident) => { $crate::$l!("认领限制由 {} 修改为 {}", "Claim limit changed from {} to {}") };
+    (claiming_paused $l:ident) => { $crate::$l!("已手动暂停认领", "Claiming paused manually") };
+    (claiming_resumed $l:ident) => { $crate::$l!("已恢复认领", "Claiming resumed") };
+    (auto_claim_crashed $l:ident) => { $crate::$l!("自动认领异常结束: {}", "Auto-claim ended abnormally: {}") };
+    (state_restored $l:ident) => { $crate::$l!("已从状态文件恢复：认领数 {}，尝试次数 {}，已认领任务 {} 个", "Restored from state file: {} claimed, {} attempts, {} claimed tasks") };
+    (no_workday_within_year $l:ident) => { $crate::$l!("工作日历中未来一年内没有工作日，一天后再检查", "No workday in the work calendar within the next year, checking again in a day") };
+    (non_working_day $l:ident) => { $crate::$l!("{} 不是工作日，将于 {} 恢复认领", "{} is not a workday, claiming resumes at {}") };
+    (outside_active_hours $l:ident) => { $crate::$l!("当前不在工作时段 {} 内，将于 {} 恢复认领", "Outside active hours {}, claiming resumes at {}") };
+    (awaiting_cron $l:ident) => { $crate::$l!("等待定时认领 {}，下次触发时间 {}", "Waiting for scheduled claiming {}, next trigger at {}") };
+    (hourly_limit_reached $l:ident) => { $crate::$l!("最近一小时已认领 {} 个任务，达到每小时上限 {}，将于 {} 恢复认领", "Claimed {} tasks in the last hour, hourly limit {} reached, claiming resumes at {}") };
+    (daily_limit_reached $l:ident) => { $crate::$l!("今日已认领 {} 个任务，达到每日上限 {}，将于 {} 恢复认领", "Claimed {} tasks today, daily limit {} reached, claiming resumes at {}") };
+    (error_budget_exhausted $l:ident) => { $crate::$l!("连续 {} 次失败，已停止认领，最近一次错误: {}", "{} consecutive failures, claiming stopped, last error: {}") };
+    (claim_limit_reached_stopping $l:ident) => { $crate::$l!("已达到认领限制，停止自动认领", "Claim limit reached, stopping auto-claim") };
+    (cookie_maybe_expired $l:ident) => { $crate::$l!("接口返回未登录，Cookie 可能已过期: {}", "API reports not logged in, the cookie may have expired: {}") };
+    (user_validation_failed $l:ident) => { $crate::$l!("用户验证失败: {}", "User validation failed: {}") };
+    (cookie_validation_failed $l:ident) => { $crate::$l!("Cookie验证失败: {}", "Cookie validation failed: {}") };
+    (fetch_my_tasks_failed $l:ident) => { $crate::$l!("获取我的任务列表失败: {}", "Failed to fetch my task list: {}") };
+    (entering_dormancy $l:ident) => { $crate::$l!("进入休眠，每 {:.0} 秒检查一次待完成任务，清空后自动恢复认领", "Going dormant, checking pending tasks every {:.0} seconds and resuming once they are cleared") };
+    (pending_cleared $l:ident) => { $crate::$l!("待完成任务数 {}，恢复认领", "{} pending tasks, resuming claiming") };
+    (still_pending $l:ident) => { $crate::$l!("仍有 {} 个待完成任务，继续休眠", "{} tasks still pending, staying dormant") };
+    (pending_check_failed_resuming $l:ident) => { $crate::$l!("查询待完成任务数失败，尝试恢复认领: {}", "Failed to query pending tasks, trying to resume claiming: {}") };
+    (labels_unavailable_skip_check $l:ident) => { $crate::$l!("获取筛选标签失败，跳过ID校验: {}", "Failed to fetch filter labels, skipping ID validation: {}") };
+    (with_correlation_id $l:ident) => { $crate::$l!("{}（关联ID: {}）", "{} (correlation ID: {})") };
+    (claim_limit_already_reached $l:ident) => { $crate::$l!("认领限制已达到 ({}/{})", "Claim limit already reached ({}/{})") };
+    (claiming_tasks $l:ident) => { $crate::$l!("尝试认领 {} 个任务: {:?}", "Trying to claim {} tasks: {:?}") };
+    (new_tasks_spotted $l:ident) => { $crate::$l!("发现 {} 个新任务: {}", "Spotted {} new tasks: {}") };
+    (attempt_started $l:ident) => { $crate::$l!("认领尝试 #{} 开始，当前认领数：{}/{}", "Claim attempt #{} started, claimed so far: {}/{}") };
+    (tasks_fetched $l:ident) => { $crate::$l!("获取到 {} 个任务", "Fetched {} tasks") };
+    (pool_empty $l:ident) => { $crate::$l!("线索池中没任务", "No tasks in the clue pool") };
+    (no_matching_tasks $l:ident) => { $crate::$l!("没有符合条件的任务", "No tasks match the filters") };
+    (pending_limit_reached $l:ident) => { $crate::$l!("待完成任务数 {} 已达上限 {}，跳过本次认领", "{} pending tasks reached the limit {}, skipping this attempt") };
+    (pending_check_failed_continuing $l:ident) => { $crate::$l!("查询待完成任务数失败，继续认领: {}", "Failed to query pending tasks, continuing to claim: {}") };
+    (claim_succeeded $l:ident) => { $crate::$l!("认领成功：{} 个任务，TaskID: {:?}，总计：{}/{}", "Claimed {} tasks, TaskID: {:?}, total: {}/{}") };
+    (task_claim_failed $l:ident) => { $crate::$l!("任务 {} 认领失败: {}", "Failed to claim task {}: {}") };
+    (response_data $l:ident) => { $crate::$l!("响应数据: {:?}", "response data: {:?}") };
+    (response_data_null $l:ident) => { $crate::$l!("响应数据: null", "response data: null") };
+    (claim_failed $l:ident) => { $crate::$l!("认领失败 {}: {:?}，错误码: {}，错误信息: {}{}，{}", "Claim failed {}: {:?}, errno: {}, errmsg: {}{}, {}") };
+    (finish_pending_hint $l:ident) => { $crate::$l!("提示：请先完成待审核的任务后再尝试认领新任务", "Hint: finish your pending review tasks before claiming new ones") };
+    (auto_claim_starting $l:ident) => { $crate::$l!("开始自动认领任务...", "Starting auto-claim...") };
+    (claim_config $l:ident) => { $crate::$l!("配置: 任务类型={}, 认领限制={}, 轮询间隔={:.1}秒, 学科ID={}, 学段ID={}, 线索类型ID={}", "Config: task type={}, claim limit={}, interval={:.1}s, subject ID={}, step ID={}, clue type ID={}") };
+    (user_validated $l:ident) => { $crate::$l!("用户验证成功: {}", "User validated: {}") };
+    (auto_claim_finished $l:ident) => { $crate::$l!("自动认领完成，最终认领数：{}/{}，总尝试次数：{}，耗时：{:.1}秒", "Auto-claim finished, claimed: {}/{}, attempts: {}, elapsed: {:.1}s") };
+    (claim_error $l:ident) => { $crate::$l!("认领过程出错: {}", "Error while claiming: {}") };
+    (max_attempts_reached $l:ident) => { $crate::$l!("已达到最大尝试次数 {}，停止自动认领", "Reached the maximum of {} attempts, stopping auto-claim") };
+    (max_runtime_reached $l:ident) => { $crate::$l!("已达到最长运行时间 {:.0} 秒，停止自动认领", "Reached the maximum runtime of {:.0} seconds, stopping auto-claim") };
+    (worker_pool_mode $l:ident) => { $crate::$l!("工作池模式，{} 个认领工作者", "Worker pool mode with {} claim workers") };
+    (fetch_task_list_error $l:ident) => { $crate::$l!("获取任务列表出错: {}（关联ID: {}）", "Error fetching task list: {} (correlation ID: {})") };
+    (worker_claim_error $l:ident) => { $crate::$l!("工作者 #{} 认领出错: {}（关联ID: {}）", "Worker #{} failed to claim: {} (correlation ID: {})") };
+    (worker_claiming $l:ident) => { $crate::$l!("工作者 #{} 认领任务 {}", "Worker #{} claiming task {}") };
+
+    // 认领器池、自动审核与自动生产
+    (pool_member_finished $l:ident) => { $crate::$l!("认领器 {} 已完成，认领 {} 个任务", "Claimer {} finished with {} tasks claimed") };
+    (pool_member_gave_up $l:ident) => { $crate::$l!("认领器 {} 出错退出且已重启 {} 次，不再重启: {}", "Claimer {} failed after {} restarts, giving up: {}") };
+    (pool_member_restarting $l:ident) => { $crate::$l!("认领器 {} 出错退出: {}，{:.1} 秒后第 {} 次重启", "Claimer {} failed: {}, restarting in {:.1}s (restart #{})") };
+    (audit_verdict $l:ident) => { $crate::$l!("任务 {} 审核结论: {:?}", "Task {} audit verdict: {:?}") };
+    (audit_failed $l:ident) => { $crate::$l!("任务 {} 审核失败: {}", "Failed to audit task {}: {}") };
+    (auditor_lagged $l:ident) => { $crate::$l!("自动审核滞后，丢失 {} 条事件", "Auto-audit lagged behind, {} events dropped") };
+    (submit_verdict_failed $l:ident) => { $crate::$l!("提交审核结论失败，错误码: {}，错误信息: {}", "Failed to submit audit verdict, errno: {}, errmsg: {}") };
+    (answer_generated $l:ident) => { $crate::$l!("线索 {} 答案已生成，提交: {}", "Answer generated for clue {}, submitted: {}") };
+    (produce_failed $l:ident) => { $crate::$l!("线索 {} 生产失败: {}", "Failed to produce clue {}: {}") };
+    (producer_lagged $l:ident) => { $crate::$l!("自动生产滞后，丢失 {} 条事件", "Auto-produce lagged behind, {} events dropped") };
+    (empty_answer $l:ident) => { $crate::$l!("生成的答案为空", "The generated answer is empty") };
+    (submit_answer_failed $l:ident) => { $crate::$l!("提交答案失败，错误码: {}，错误信息: {}", "Failed to submit answer, errno: {}, errmsg: {}") };
+    (ocr_not_configured $l:ident) => { $crate::$l!("未配置图片文字识别器", "No image text recognizer configured") };
+    (ocr_finished $l:ident) => { $crate::$l!("图片识别完成: {}", "Image recognized: {}") };
+    (runtime_create_failed $l:ident) => { $crate::$l!("创建运行时失败: {}", "Failed to create runtime: {}") };
+
+    // HTTP 客户端
+    (invalid_header_name $l:ident) => { $crate::$l!("请求头名称 {} 无效: {}", "Invalid header name {}: {}") };
+    (invalid_header_value $l:ident) => { $crate::$l!("请求头 {} 的值无效: {}", "Invalid value for header {}: {}") };
+    (fixture_replayed $l:ident) => { $crate::$l!("回放夹具: {}", "Replaying fixture: {}") };
+    (response_dumped $l:ident) => { $crate::$l!("响应已转储: {}", "Response dumped: {}") };
+    (dump_response_failed $l:ident) => { $crate::$l!("转储响应失败: {}", "Failed to dump response: {}") };
+    (record_fixture_failed $l:ident) => { $crate::$l!("录制夹具失败: {}", "Failed to record fixture: {}") };
+    (request_task_list $l:ident) => { $crate::$l!("请求任务列表: {}", "Requesting task list: {}") };
+    (task_list_response $l:ident) => { $crate::$l!("任务列表响应: {}", "Task list response: {}") };
+    (parse_task_list_failed $l:ident) => { $crate::$l!("解析任务列表响应失败: {}", "Failed to parse task list response: {}") };
+    (request_my_tasks $l:ident) => { $crate::$l!("请求我的任务列表: {}", "Requesting my task list: {}") };
+    (my_tasks_response $l:ident) => { $crate::$l!("我的任务列表响应: {}", "My task list response: {}") };
+    (parse_my_tasks_failed $l:ident) => { $crate::$l!("解析我的任务列表响应失败: {}", "Failed to parse my task list response: {}") };
+    (claim_label $l:ident) => { $crate::$l!("认领", "claim") };
+    (release_label $l:ident) => { $crate::$l!("释放", "release") };
+    (task_action_request $l:ident) => { $crate::$l!("{}请求: {} -> {}", "{} request: {} -> {}") };
+    (task_action_response $l:ident) => { $crate::$l!("{}响应: {}", "{} response: {}") };
+    (write_audit_log_failed $l:ident) => { $crate::$l!("写入审计日志失败: {}", "Failed to write audit log: {}") };
+    (parse_task_action_failed $l:ident) => { $crate::$l!("解析{}响应失败: {}", "Failed to parse {} response: {}") };
+    (submit_verdict_request $l:ident) => { $crate::$l!("提交审核结论: {} -> {}", "Submitting audit verdict: {} -> {}") };
+    (verdict_response $l:ident) => { $crate::$l!("审核结论响应: {}", "Audit verdict response: {}") };
+    (parse_verdict_failed $l:ident) => { $crate::$l!("解析审核结论响应失败: {}", "Failed to parse audit verdict response: {}") };
+    (submit_answer_request $l:ident) => { $crate::$l!("提交生产任务答案: {}", "Submitting produce task answer: {}") };
+    (answer_response $l:ident) => { $crate::$l!("答案提交响应: {}", "Answer submission response: {}") };
+    (parse_answer_failed $l:ident) => { $crate::$l!("解析答案提交响应失败: {}", "Failed to parse answer submission response: {}") };
+    (upload_token_response $l:ident) => { $crate::$l!("上传凭证响应: {}", "Upload token response: {}") };
+    (parse_upload_token_failed $l:ident) => { $crate::$l!("解析上传凭证响应失败: {}", "Failed to parse upload token response: {}") };
+    (fetch_upload_token_failed $l:ident) => { $crate::$l!("获取上传凭证失败: {}", "Failed to fetch upload token: {}") };
+    (uploading_image $l:ident) => { $crate::$l!("上传图片: {}", "Uploading image: {}") };
+    (upload_response $l:ident) => { $crate::$l!("上传响应: {}", "Upload response: {}") };
+    (parse_upload_failed $l:ident) => { $crate::$l!("解析上传响应失败: {}", "Failed to parse upload response: {}") };
+    (upload_image_failed $l:ident) => { $crate::$l!("上传图片失败: {}", "Failed to upload image: {}") };
+    (request_clue_detail $l:ident) => { $crate::$l!("请求线索详情: {}", "Requesting clue details: {}") };
+    (clue_detail_response $l:ident) => { $crate::$l!("线索详情响应: {}", "Clue details response: {}") };
+    (parse_clue_detail_failed $l:ident) => { $crate::$l!("解析线索详情响应失败: {}", "Failed to parse clue details response: {}") };
+    (request_labels $l:ident) => { $crate::$l!("请求筛选标签: {}", "Requesting filter labels: {}") };
+    (labels_response $l:ident) => { $crate::$l!("筛选标签响应: {}", "Filter labels response: {}") };
+    (parse_labels_failed $l:ident) => { $crate::$l!("解析筛选标签响应失败: {}", "Failed to parse filter labels response: {}") };
+    (create_dump_dir_failed $l:ident) => { $crate::$l!("创建响应转储目录失败 {}: {}", "Failed to create response dump directory {}: {}") };
+    (write_dump_failed $l:ident) => { $crate::$l!("写入响应转储失败 {}: {}", "Failed to write response dump {}: {}") };
+    (create_fixture_dir_failed $l:ident) => { $crate::$l!("创建夹具目录失败 {}: {}", "Failed to create fixture directory {}: {}") };
+    (parse_fixture_failed $l:ident) => { $crate::$l!("解析夹具文件失败 {}: {}", "Failed to parse fixture file {}: {}") };
+    (fixture_not_found $l:ident) => { $crate::$l!("夹具中没有匹配的请求: {}", "No matching request in fixtures: {}") };
+    (write_fixture_failed $l:ident) => { $crate::$l!("写入夹具文件失败 {}: {}", "Failed to write fixture file {}: {}") };
+    (read_fixture_dir_failed $l:ident) => { $crate::$l!("读取夹具目录失败 {}: {}", "Failed to read fixture directory {}: {}") };
+    (invalid_ua_rotation $l:ident) => { $crate::$l!("无效的 User-Agent 轮换方式: {}，可选值: session, request", "Invalid User-Agent rotation: {}, expected session or request") };
+    (empty_ua_pool $l:ident) => { $crate::$l!("User-Agent 池不能为空", "The User-Agent pool must not be empty") };
+    (read_ua_file_failed $l:ident) => { $crate::$l!("读取 User-Agent 文件失败 {}: {}", "Failed to read User-Agent file {}: {}") };
+    (invalid_ua_file $l:ident) => { $crate::$l!("User-Agent 文件 {} 无效: {}", "Invalid User-Agent file {}: {}") };
+
+    // 状态、调度与统计
+    (parse_state_failed $l:ident) => { $crate::$l!("解析状态文件失败 {}: {}", "Failed to parse state file {}: {}") };
+    (read_state_failed $l:ident) => { $crate::$l!("读取状态文件失败 {}: {}", "Failed to read state file {}: {}") };
+    (invalid_time $l:ident) => { $crate::$l!("无效的时间: {}，格式为 HH:MM", "Invalid time: {}, expected HH:MM") };
+    (invalid_active_hours $l:ident) => { $crate::$l!("无效的工作时段: {}，示例: 09:00-12:00", "Invalid active hours: {}, e.g. 09:00-12:00") };
+    (empty_active_hours $l:ident) => { $crate::$l!("工作时段不能为空", "Active hours must not be empty") };
+    (invalid_cron $l:ident) => { $crate::$l!("无效的 cron 表达式 {}: {}", "Invalid cron expression {}: {}") };
+    (cron_burst_not_positive $l:ident) => { $crate::$l!("每次触发的认领时长必须大于0", "The claiming duration per trigger must be greater than 0") };
+    (cron_schedule $l:ident) => { $crate::$l!("{}（每次 {} 秒）", "{} ({} seconds each)") };
+    (read_holidays_failed $l:ident) => { $crate::$l!("读取节假日文件失败 {}: {}", "Failed to read holidays file {}: {}") };
+    (parse_holidays_failed $l:ident) => { $crate::$l!("解析节假日文件失败 {}: {}", "Failed to parse holidays file {}: {}") };
+    (invalid_weekday_number $l:ident) => { $crate::$l!("无效的星期: {}，数字应为 1-7", "Invalid weekday: {}, numbers must be 1-7") };
+    (invalid_weekday $l:ident) => { $crate::$l!("无效的星期: {}，示例: mon、tue 或 1-7", "Invalid weekday: {}, e.g. mon, tue or 1-7") };
+    (empty_weekdays $l:ident) => { $crate::$l!("工作日不能为空", "Workdays must not be empty") };
+    (invalid_date_format $l:ident) => { $crate::$l!("无效的日期: {}，格式为 YYYY-MM-DD", "Invalid date: {}, expected YYYY-MM-DD") };
+    (invalid_date $l:ident) => { $crate::$l!("无效的日期: {}", "Invalid date: {}") };
+    (attempts_label $l:ident) => { $crate::$l!("尝试次数", "Attempts") };
+    (claimed_label $l:ident) => { $crate::$l!("认领成功", "Claimed") };
+    (success_rate_label $l:ident) => { $crate::$l!("成功率", "Success rate") };
+    (avg_list_latency_label $l:ident) => { $crate::$l!("平均列表延迟", "Avg list latency") };
+    (avg_claim_latency_label $l:ident) => { $crate::$l!("平均认领延迟", "Avg claim latency") };
+    (longest_empty_pool_label $l:ident) => { $crate::$l!("最长空池次数", "Longest empty pool streak") };
+    (busiest_hour_label $l:ident) => { $crate::$l!("最忙时段", "Busiest hour") };
+    (subject_row $l:ident) => { $crate::$l!("学科: {}", "Subject: {}") };
+    (errno_row $l:ident) => { $crate::$l!("错误码 {}", "Errno {}") };
+    (request_errors_label $l:ident) => { $crate::$l!("请求错误", "Request errors") };
+    (export_format_unknown $l:ident) => { $crate::$l!("无法从文件名推断导出格式: {}", "Cannot infer export format from file name: {}") };
+    (export_format_unsupported $l:ident) => { $crate::$l!("不支持的导出格式: {}（支持 csv/json）", "Unsupported export format: {} (csv/json supported)") };
+    (invalid_log_rotation $l:ident) => { $crate::$l!("无效的日志轮转策略: {}", "Invalid log rotation: {}") };
+    (log_rotation_size_not_positive $l:ident) => { $crate::$l!("日志轮转大小必须大于0", "Log rotation size must be greater than 0") };
+    (invalid_log_path $l:ident) => { $crate::$l!("无效的日志文件路径: {}", "Invalid log file path: {}") };
+    (otel_shutdown_failed $l:ident) => { $crate::$l!("关闭 OpenTelemetry 导出器失败: {}", "Failed to shut down OpenTelemetry exporter: {}") };
+
+    // 通知
+    (new_tasks_title $l:ident) => { $crate::$l!("发现 {} 个新任务", "Spotted {} new tasks") };
+    (claimed_title $l:ident) => { $crate::$l!("认领成功 {} 个任务", "Claimed {} tasks") };
+    (claimed_body $l:ident) => { $crate::$l!("任务ID: {}，累计认领 {} 个", "Task IDs: {}, {} claimed in total") };
+    (limit_reached_title $l:ident) => { $crate::$l!("已达到认领限制", "Claim limit reached") };
+    (limit_reached_body $l:ident) => { $crate::$l!("共认领 {}/{} 个任务，自动认领已停止", "Claimed {}/{} tasks, auto-claim stopped") };
+    (cookie_expired_title $l:ident) => { $crate::$l!("Cookie 已过期", "Cookie expired") };
+    (cookie_expired_body $l:ident) => { $crate::$l!("请更新 Cookie 后重新运行: {}", "Please update the cookie and run again: {}") };
+    (notify_failed $l:ident) => { $crate::$l!("发送通知失败: {}", "Failed to send notification: {}") };
+    (invalid_smtp_tls $l:ident) => { $crate::$l!("无效的加密方式: {}，可选 none、starttls、tls", "Invalid encryption: {}, expected none, starttls or tls") };
+    (invalid_mail_address $l:ident) => { $crate::$l!("无效的邮件地址 {}: {}", "Invalid email address {}: {}") };
+    (empty_mail_recipients $l:ident) => { $crate::$l!("邮件收件人不能为空", "Email recipients must not be empty") };
+    (smtp_config_error $l:ident) => { $crate::$l!("SMTP 配置错误: {}", "SMTP configuration error: {}") };
+    (finished_title $l:ident) => { $crate::$l!("自动认领结束，共认领 {} 个任务", "Auto-claim finished, {} tasks claimed") };
+    (finished_body $l:ident) => { $crate::$l!("认领数: {}\n尝试次数: {}\n失败次数: {}\n耗时: {:.1} 秒\n任务ID: {}", "Claimed: {}\nAttempts: {}\nFailures: {}\nElapsed: {:.1}s\nTask IDs: {}") };
+    (aborted_title $l:ident) => { $crate::$l!("自动认领出错退出", "Auto-claim aborted with an error") };
+    (aborted_body $l:ident) => { $crate::$l!("错误: {}", "Error: {}") };
+    (build_mail_failed $l:ident) => { $crate::$l!("构建邮件失败: {}", "Failed to build email: {}") };
+    (send_mail_failed $l:ident) => { $crate::$l!("发送邮件失败: {}", "Failed to send email: {}") };
+    (webhook_status $l:ident) => { $crate::$l!("Webhook 返回状态码 {}", "Webhook returned status {}") };
+    (invalid_dingtalk_url $l:ident) => { $crate::$l!("无效的钉钉 Webhook 地址: {}", "Invalid DingTalk webhook URL: {}") };
+    (invalid_sign_secret $l:ident) => { $crate::$l!("无效的加签密钥: {}", "Invalid signing secret: {}") };
+    (invalid_wecom_url $l:ident) => { $crate::$l!("无效的企业微信 Webhook 地址: {}", "Invalid WeCom webhook URL: {}") };
+    (bot_error $l:ident) => { $crate::$l!("群机器人返回错误: {}", "Group bot returned an error: {}") };
+    (bark_error $l:ident) => { $crate::$l!("Bark 返回错误: {}", "Bark returned an error: {}") };
+    (serverchan_error $l:ident) => { $crate::$l!("Server酱返回错误: {}", "ServerChan returned an error: {}") };
+    (player_start_failed $l:ident) => { $crate::$l!("无法启动播放器: {}", "Failed to start audio player: {}") };
+    (play_sound_failed $l:ident) => { $crate::$l!("播放提示音失败: {}", "Failed to play notification sound: {}") };
+    (invalid_mqtt_url $l:ident) => { $crate::$l!("无效的 MQTT 地址 {}: {}", "Invalid MQTT URL {}: {}") };
+    (unsupported_mqtt_scheme $l:ident) => { $crate::$l!("不支持的 MQTT 协议: {}，只支持 mqtt://", "Unsupported MQTT scheme: {}, only mqtt:// is supported") };
+    (mqtt_missing_host $l:ident) => { $crate::$l!("MQTT 地址缺少主机名", "MQTT URL is missing a host") };
+    (mqtt_connection_error $l:ident) => { $crate::$l!("MQTT 连接出错: {}，稍后重连", "MQTT connection error: {}, reconnecting later") };
+    (mqtt_publish_failed $l:ident) => { $crate::$l!("发布 MQTT 消息失败: {}", "Failed to publish MQTT message: {}") };
+    (mqtt_disconnect_failed $l:ident) => { $crate::$l!("断开 MQTT 连接失败: {}", "Failed to disconnect from MQTT: {}") };
+    (mqtt_flush_timeout $l:ident) => { $crate::$l!("等待 MQTT 消息发出超时", "Timed out waiting for MQTT messages to be sent") };
+
+    // 存储、消息队列与远程控制
+    (store_claim_failed $l:ident) => { $crate::$l!("写入认领记录失败: {}", "Failed to write claim record: {}") };
+    (store_request_failed $l:ident) => { $crate::$l!("写入认领请求记录失败: {}", "Failed to write claim request record: {}") };
+    (store_lagged $l:ident) => { $crate::$l!("认领记录写入滞后，丢失 {} 条事件", "Claim record writer lagged behind, {} events dropped") };
+    (kafka_producer_failed $l:ident) => { $crate::$l!("创建 Kafka 生产者失败: {}", "Failed to create Kafka producer: {}") };
+    (kafka_delivery_failed $l:ident) => { $crate::$l!("任务 {} 投递失败: {}", "Failed to deliver task {}: {}") };
+    (kafka_publish_failed $l:ident) => { $crate::$l!("发布认领任务到 Kafka 失败: {}", "Failed to publish claimed task to Kafka: {}") };
+    (kafka_lagged $l:ident) => { $crate::$l!("Kafka 发布滞后，丢失 {} 条事件", "Kafka publisher lagged behind, {} events dropped") };
+    (invalid_queue_kind $l:ident) => { $crate::$l!("无效的队列类型: {}，可选 list、stream", "Invalid queue kind: {}, expected list or stream") };
+    (invalid_redis_url $l:ident) => { $crate::$l!("无效的 Redis 地址 {}: {}", "Invalid Redis URL {}: {}") };
+    (redis_connect_failed $l:ident) => { $crate::$l!("连接 Redis 失败: {}", "Failed to connect to Redis: {}") };
+    (redis_push_failed $l:ident) => { $crate::$l!("推送任务 {} 到 Redis 失败: {}", "Failed to push task {} to Redis: {}") };
+    (redis_lagged $l:ident) => { $crate::$l!("Redis 推送滞后，丢失 {} 条事件", "Redis pusher lagged behind, {} events dropped") };
+    (limit_command_usage $l:ident) => { $crate::$l!("limit 命令需要一个正整数，如 limit 20", "The limit command needs a positive integer, e.g. limit 20") };
+    (unknown_control_command $l:ident) => { $crate::$l!("未知的控制命令: {}", "Unknown control command: {}") };
+    (redis_control_read_failed $l:ident) => { $crate::$l!("读取 Redis 控制命令失败: {}，5 秒后重连", "Failed to read Redis control commands: {}, reconnecting in 5 seconds") };
+    (redis_control_started $l:ident) => { $crate::$l!("开始从 Redis 列表 {} 读取控制命令", "Reading control commands from Redis list {}") };
+    (control_command_received $l:ident) => { $crate::$l!("收到控制命令: {:?}", "Received control command: {:?}") };
+    (invalid_control_command_ignored $l:ident) => { $crate::$l!("忽略无效的控制命令: {}", "Ignoring invalid control command: {}") };
+    (claim_loop_started $l:ident) => { $crate::$l!("认领循环已启动", "Claim loop started") };
+    (claim_loop_stopped $l:ident) => { $crate::$l!("认领循环已停止", "Claim loop stopped") };
+    (claimer_already_running $l:ident) => { $crate::$l!("认领器已在运行", "The claimer is already running") };
+    (claimer_not_running $l:ident) => { $crate::$l!("认领器未在运行", "The claimer is not running") };
+    (grpc_events_lagged $l:ident) => { $crate::$l!("gRPC 事件订阅滞后，丢失 {} 条事件", "gRPC event subscriber lagged behind, {} events dropped") };
+    (control_socket_in_use $l:ident) => { $crate::$l!("控制套接字 {} 已被其他实例占用", "Control socket {} is in use by another instance") };
+    (control_socket_bind_failed $l:ident) => { $crate::$l!("绑定控制套接字 {} 失败: {}", "Failed to bind control socket {}: {}") };
+    (control_connection_dropped $l:ident) => { $crate::$l!("控制连接异常断开: {}", "Control connection dropped: {}") };
+    (control_accept_failed $l:ident) => { $crate::$l!("接受控制连接失败: {}", "Failed to accept control connection: {}") };
+    (jsonrpc_version_unsupported $l:ident) => { $crate::$l!("只支持 JSON-RPC 2.0", "Only JSON-RPC 2.0 is supported") };
+    (invalid_rpc_request $l:ident) => { $crate::$l!("无效的请求: {}", "Invalid request: {}") };
+    (reload_state_failed $l:ident) => { $crate::$l!("重新读取状态文件失败: {}", "Failed to reload state file: {}") };
+    (state_reloaded $l:ident) => { $crate::$l!("已通过控制套接字重新读取状态文件", "State file reloaded via the control socket") };
+    (unknown_rpc_method $l:ident) => { $crate::$l!("未知的方法: {}，可选 status、pause、resume、reload", "Unknown method: {}, expected status, pause, resume or reload") };
+    (control_socket_connect_failed $l:ident) => { $crate::$l!("连接控制套接字 {} 失败: {}，请确认认领器已用 --control-socket 启动", "Failed to connect to control socket {}: {}, make sure the claimer was started with --control-socket") };
+    (control_socket_closed $l:ident) => { $crate::$l!("控制套接字已关闭", "The control socket was closed") };
+    (invalid_rpc_response $l:ident) => { $crate::$l!("无效的响应: {}", "Invalid response: {}") };
+    (rpc_error $l:ident) => { $crate::$l!("{}（错误码 {}）", "{} (code {})") };
+    (rpc_response_empty $l:ident) => { $crate::$l!("响应中没有结果", "The response has no result") };
+
+    // 命令行
+    (empty_cookie $l:ident) => { $crate::$l!("Cookie不能为空", "The cookie must not be empty") };
+    (filter_not_provided $l:ident) => { $crate::$l!("服务器未提供筛选项 {}，请填写数字ID", "The server does not provide filter {}, please use a numeric ID") };
+    (invalid_task_type $l:ident) => { $crate::$l!("任务类型必须是 audittask 或 producetask", "Task type must be audittask or producetask") };
+    (invalid_duration $l:ident) => { $crate::$l!("无效的时长: {}，示例: 90s、30m、2h、1h30m", "Invalid duration: {}, e.g. 90s, 30m, 2h, 1h30m") };
+    (invalid_config_option $l:ident) => { $crate::$l!("无效的配置项: {}，格式为 KEY=VALUE", "Invalid option: {}, expected KEY=VALUE") };
+    (invalid_utc_offset $l:ident) => { $crate::$l!("无效的时区: {}，示例: +08:00、UTC", "Invalid time zone: {}, e.g. +08:00, UTC") };
+    (interval_too_small $l:ident) => { $crate::$l!("轮询间隔必须是不小于0.001秒（1毫秒）的有限数", "The polling interval must be a finite number of at least 0.001 seconds (1 ms)") };
+    (workers_too_few $l:ident) => { $crate::$l!("工作者数量至少为 1", "The number of workers must be at least 1") };
+    (daily_limit_too_small $l:ident) => { $crate::$l!("每日认领上限至少为 1", "The daily claim limit must be at least 1") };
+    (hourly_limit_too_small $l:ident) => { $crate::$l!("每小时认领上限至少为 1", "The hourly claim limit must be at least 1") };
+    (rps_too_small $l:ident) => { $crate::$l!("每秒请求数至少为 1", "Requests per second must be at least 1") };
+    (pending_recheck_invalid $l:ident) => { $crate::$l!("休眠复查间隔必须为 0（不休眠）或不小于0.001秒（1毫秒）的有限数", "The pending recheck interval must be 0 (no dormancy) or a finite number of at least 0.001 seconds (1 ms)") };
+    (control_server_bind_failed $l:ident) => { $crate::$l!("监听控制接口 {} 失败: {}", "Failed to listen for the control server on {}: {}") };
+    (control_server_started $l:ident) => { $crate::$l!("控制接口已启动，网页仪表盘: http://{}/", "Control server started, web dashboard: http://{}/") };
+    (control_socket_started $l:ident) => { $crate::$l!("控制套接字已启动: {}", "Control socket started: {}") };
+    (control_server_crashed $l:ident) => { $crate::$l!("控制接口异常退出: {}", "Control server exited abnormally: {}") };
+    (claims_exported $l:ident) => { $crate::$l!("已导出 {} 条认领记录到 {}", "Exported {} claim records to {}") };
+    (grpc_bind_failed $l:ident) => { $crate::$l!("监听 gRPC 服务 {} 失败: {}", "Failed to listen for the gRPC service on {}: {}") };
+    (grpc_started $l:ident) => { $crate::$l!("gRPC 控制服务已启动: {}，按 Ctrl-C 退出", "gRPC control service started: {}, press Ctrl-C to exit") };
+    (task_id_header $l:ident) => { $crate::$l!("任务ID", "Task ID") };
+    (clue_id_header $l:ident) => { $crate::$l!("线索ID", "Clue ID") };
+    (subject_header $l:ident) => { $crate::$l!("学科", "Subject") };
+    (step_header $l:ident) => { $crate::$l!("学段", "Step") };
+    (clue_type_header $l:ident) => { $crate::$l!("线索类型", "Clue type") };
+    (waiting_header $l:ident) => { $crate::$l!("等待时长", "Waiting") };
+    (brief_header $l:ident) => { $crate::$l!("简介", "Brief") };
+    (task_total $l:ident) => { $crate::$l!("任务总数: {}", "Total tasks: {}") };
+    (just_now $l:ident) => { $crate::$l!("刚刚", "just now") };
+    (minutes $l:ident) => { $crate::$l!("{}分钟", "{}m") };
+    (hours_minutes $l:ident) => { $crate::$l!("{}小时{}分钟", "{}h {}m") };
+    (days_hours $l:ident) => { $crate::$l!("{}天{}小时", "{}d {}h") };
+    (claim_command_failed $l:ident) => { $crate::$l!("认领失败 {:?}，错误码: {}，错误信息: {}", "Claim failed {:?}, errno: {}, errmsg: {}") };
+    (claim_command_succeeded $l:ident) => { $crate::$l!("认领成功: {:?}", "Claimed: {:?}") };
+    (claim_command_task_failed $l:ident) => { $crate::$l!("认领失败: {} ({})", "Claim failed: {} ({})") };
+    (release_failed $l:ident) => { $crate::$l!("释放失败 {:?}，错误码: {}，错误信息: {}", "Release failed {:?}, errno: {}, errmsg: {}") };
+    (released $l:ident) => { $crate::$l!("已释放: {:?}", "Released: {:?}") };
+    (invalid_task_id $l:ident) => { $crate::$l!("无效的任务ID: {}", "Invalid task ID: {}") };
+    (name_header $l:ident) => { $crate::$l!("名称", "Name") };
+    (date_header $l:ident) => { $crate::$l!("日期", "Date") };
+    (claims_header $l:ident) => { $crate::$l!("认领数", "Claims") };
+    (requested_header $l:ident) => { $crate::$l!("请求任务数", "Requested") };
+    (total_row $l:ident) => { $crate::$l!("合计", "Total") };
+    (daily_claims_table $l:ident) => { $crate::$l!("每日认领\n{}", "Daily claims\n{}") };
+    (subject_claims_table $l:ident) => { $crate::$l!("\n按学科\n{}", "\nBy subject\n{}") };
+    (user_name_line $l:ident) => { $crate::$l!("用户名: {}", "User name: {}") };
+    (roles_line $l:ident) => { $crate::$l!("角色: {}", "Roles: {}") };
+    (role_links_line $l:ident) => { $crate::$l!("角色链接:", "Role links:") };
+    (status_line $l:ident) => { $crate::$l!("状态: {}", "Status: {}") };
+    (claimed_line $l:ident) => { $crate::$l!("认领: {}/{}", "Claimed: {}/{}") };
+    (claimed_today_line $l:ident) => { $crate::$l!("今日认领: {}", "Claimed today: {}") };
+    (attempts_line $l:ident) => { $crate::$l!("尝试次数: {}", "Attempts: {}") };
+    (success_rate_line $l:ident) => { $crate::$l!("成功率: {:.1}%", "Success rate: {:.1}%") };
+    (failures_by_errno_line $l:ident) => { $crate::$l!("失败错误码:", "Failures by errno:") };
+    (paused_state $l:ident) => { $crate::$l!("已暂停", "paused") };
+    (dormant_state $l:ident) => { $crate::$l!("休眠中", "dormant") };
+    (claiming_state $l:ident) => { $crate::$l!("认领中", "claiming") };
+
+    // 进度条与终端仪表盘
+    (claim_ended $l:ident) => { $crate::$l!("认领结束", "Claiming finished") };
+    (claim_aborted $l:ident) => { $crate::$l!("认领出错退出: {}", "Claiming aborted: {}") };
+    (dormant_too_many_pending $l:ident) => { $crate::$l!("待完成任务过多，休眠中", "Too many pending tasks, dormant") };
+    (waiting_until $l:ident) => { $crate::$l!("等待至 {}", "Waiting until {}") };
+    (eta $l:ident) => { $crate::$l!("预计剩余 {}", "ETA {}") };
+    (eta_unknown $l:ident) => { $crate::$l!("预计剩余 --", "ETA --") };
+    (duration_seconds $l:ident) => { $crate::$l!("{}秒", "{}s") };
+    (duration_minutes $l:ident) => { $crate::$l!("{}分{:02}秒", "{}m{:02}s") };
+    (duration_hours $l:ident) => { $crate::$l!("{}小时{:02}分", "{}h{:02}m") };
+    (dashboard_finished $l:ident) => { $crate::$l!("认领已结束，按 q 退出", "Claiming finished, press q to quit") };
+    (dashboard_aborted $l:ident) => { $crate::$l!("认领出错退出: {}，按 q 退出", "Claiming aborted: {}, press q to quit") };
+    (finished_state $l:ident) => { $crate::$l!("已结束", "finished") };
+    (dashboard_uptime $l:ident) => { $crate::$l!("  运行 {:02}:{:02}:{:02}", "  running {:02}:{:02}:{:02}") };
+    (dashboard_progress_title $l:ident) => { $crate::$l!(" 认领进度 ", " Claim progress ") };
+    (dashboard_info $l:ident) => { $crate::$l!(" 任务池: {}    尝试次数: {}    今日认领: {}    成功率: {:.1}%", " Pool: {}    Attempts: {}    Claimed today: {}    Success rate: {:.1}%") };
+    (dashboard_latency_title $l:ident) => { $crate::$l!(" 列表请求耗时 最近 {}ms 平均 {}ms ", " List latency last {}ms avg {}ms ") };
+    (dashboard_latency_title_empty $l:ident) => { $crate::$l!(" 列表请求耗时 ", " List latency ") };
+    (dashboard_events_title $l:ident) => { $crate::$l!(" 最近事件 ", " Recent events ") };
+    (dashboard_hint $l:ident) => { $crate::$l!(" [p] 暂停/恢复   [+/-] 调整认领限制   [q] 退出", " [p] pause/resume   [+/-] adjust claim limit   [q] quit") };
+    (event_pending_limit $l:ident) => { $crate::$l!("待完成任务 {}/{}，跳过本次认领", "Pending tasks {}/{}, skipping this attempt") };
+    (event_claimed $l:ident) => { $crate::$l!("认领成功 {} 个: {}，累计 {}", "Claimed {}: {}, total {}") };
+    (event_claim_failed $l:ident) => { $crate::$l!("认领失败 [{}] {}", "Claim failed [{}] {}") };
+    (event_limit_reached $l:ident) => { $crate::$l!("已达到认领限制 {}/{}", "Claim limit reached {}/{}") };
+    (event_cookie_expired $l:ident) => { $crate::$l!("Cookie 已过期: {}", "Cookie expired: {}") };
+    (event_limit_changed $l:ident) => { $crate::$l!("认领限制修改为 {}", "Claim limit changed to {}") };
+    (event_paused $l:ident) => { $crate::$l!("已暂停认领", "Claiming paused") };
+    (event_dormant $l:ident) => { $crate::$l!("待完成任务过多（错误码 {}），进入休眠", "Too many pending tasks (errno {}), going dormant") };
+    (event_resumed $l:ident) => { $crate::$l!("待完成任务剩余 {} 个，恢复认领", "{} pending tasks left, resuming claiming") };
+    (event_daily_limit $l:ident) => { $crate::$l!("今日已认领 {}/{}，{} 恢复", "Claimed {}/{} today, resuming {}") };
+    (event_hourly_limit $l:ident) => { $crate::$l!("最近一小时已认领 {}/{}，{} 恢复", "Claimed {}/{} in the last hour, resuming {}") };
+    (event_non_working_day $l:ident) => { $crate::$l!("非工作日，{} 恢复", "Not a workday, resuming {}") };
+    (event_outside_active_hours $l:ident) => { $crate::$l!("不在工作时段内，{} 恢复", "Outside active hours, resuming {}") };
+    (event_awaiting_cron $l:ident) => { $crate::$l!("等待定时触发，{} 开始", "Waiting for schedule, starting {}") };
+    (event_error $l:ident) => { $crate::$l!("出错: {}", "Error: {}") };
+    (event_finished $l:ident) => { $crate::$l!("认领结束，共认领 {} 个任务", "Claiming finished, {} tasks claimed") };
+
+    // 配置文件与初始化向导
+    (read_config_failed $l:ident) => { $crate::$l!("读取配置文件失败 {}: {}", "Failed to read config file {}: {}") };
+    (parse_config_failed $l:ident) => { $crate::$l!("解析配置文件失败 {}: {}", "Failed to parse config file {}: {}") };
+    (write_config_failed $l:ident) => { $crate::$l!("写入配置文件失败 {}: {}", "Failed to write config file {}: {}") };
+    (init_overwrite_prompt $l:ident) => { $crate::$l!("配置文件 {} 已存在，是否覆盖？", "Config file {} already exists, overwrite it?") };
+    (init_cookie_prompt $l:ident) => { $crate::$l!("Cookie（从浏览器开发者工具中复制，输入不回显）", "Cookie (copy it from the browser developer tools, input is hidden)") };
+    (init_cookie_valid $l:ident) => { $crate::$l!("Cookie 有效，当前账号: {}", "Cookie is valid, signed in as {}") };
+    (init_cookie_invalid $l:ident) => { $crate::$l!("Cookie 无效: {}，请重新输入", "Invalid cookie: {}, please try again") };
+    (init_task_type_prompt $l:ident) => { $crate::$l!("任务类型", "Task type") };
+    (audit_task_item $l:ident) => { $crate::$l!("审核任务 (audittask)", "Audit tasks (audittask)") };
+    (produce_task_item $l:ident) => { $crate::$l!("生产任务 (producetask)", "Produce tasks (producetask)") };
+    (init_filter_prompt $l:ident) => { $crate::$l!("选择{}", "Select {}") };
+    (init_saved $l:ident) => { $crate::$l!("配置已写入 {}", "Config written to {}") };
+    (init_next_step $l:ident) => { $crate::$l!("运行 bedu-claim run 即可按该配置认领，命令行参数优先于配置文件", "Run bedu-claim run to claim with this config; command-line options take precedence over the config file") };
+
+    // 交互式选择任务
+    (pick_prompt $l:ident) => { $crate::$l!("选择要认领的任务（共 {} 个，↑↓ 移动，空格选中，回车确认）", "Select tasks to claim ({} in total, ↑↓ to move, space to select, enter to confirm)") };
+    (pick_none_selected $l:ident) => { $crate::$l!("未选择任务", "No tasks selected") };
+    (pick_confirm $l:ident) => { $crate::$l!("认领选中的 {} 个任务？", "Claim the {} selected tasks?") };
+
+    // 单实例锁
+    (open_lock_failed $l:ident) => { $crate::$l!("打开锁文件失败 {}: {}", "Failed to open lock file {}: {}") };
+    (instance_running $l:ident) => { $crate::$l!("该账号已有认领器在运行（PID {}，锁文件 {}），确认要同时运行请使用 --force", "A claimer for this account is already running (PID {}, lock file {}); use --force to run anyway") };
+    (lock_failed $l:ident) => { $crate::$l!("锁定 {} 失败: {}", "Failed to lock {}: {}") };
+    (instance_locked $l:ident) => { $crate::$l!("已获取账号锁: {}", "Account lock acquired: {}") };
+
+    // 后台运行
+    (daemon_requires_log_file $l:ident) => { $crate::$l!("后台运行需要同时指定 --log-file", "--daemon requires --log-file") };
+    (daemon_failed $l:ident) => { $crate::$l!("转入后台运行失败: {}", "Failed to daemonize: {}") };
+    (daemon_started $l:ident) => { $crate::$l!("已在后台运行，PID {}，日志写入 {}", "Running in background with PID {}, logging to {}") };
+    (write_pid_file_failed $l:ident) => { $crate::$l!("写入 PID 文件失败 {}: {}", "Failed to write PID file {}: {}") };
+
+    // systemd
+    (systemd_notify_failed $l:ident) => { $crate::$l!("发送 systemd 通知失败: {}", "Failed to send systemd notification: {}") };
+    (systemd_status $l:ident) => { $crate::$l!("已认领 {}/{}，尝试 {} 次", "claimed {}/{}, {} attempts") };
+
+    // Windows 服务
+    (service_display_name $l:ident) => { $crate::$l!("百度教育自动认领", "Baidu Education Auto Claimer") };
+    (service_description $l:ident) => { $crate::$l!("按配置持续认领百度教育任务，配置文件位于 %ProgramData%\\bedu-claim\\config.toml", "Claims Baidu Education tasks continuously, configured by %ProgramData%\\bedu-claim\\config.toml") };
+    (service_install_failed $l:ident) => { $crate::$l!("安装服务失败: {}", "Failed to install service: {}") };
+    (service_uninstall_failed $l:ident) => { $crate::$l!("卸载服务失败: {}", "Failed to uninstall service: {}") };
+    (service_open_failed $l:ident) => { $crate::$l!("打开服务 {} 失败: {}", "Failed to open service {}: {}") };
+    (service_start_failed $l:ident) => { $crate::$l!("启动服务失败: {}", "Failed to start service: {}") };
+    (service_stop_failed $l:ident) => { $crate::$l!("停止服务失败: {}", "Failed to stop service: {}") };
+    (service_control_failed $l:ident) => { $crate::$l!("与服务控制管理器通信失败: {}", "Failed to communicate with the service control manager: {}") };
+    (service_failed $l:ident) => { $crate::$l!("服务运行出错: {}", "Service failed: {}") };
+    (service_installed $l:ident) => { $crate::$l!("已安装服务 {}", "Service {} installed") };
+    (service_config_missing $l:ident) => { $crate::$l!("配置文件 {} 不存在，请先运行 bedu-claim --config \"{0}\" init 生成", "Config file {} not found, run bedu-claim --config \"{0}\" init to create it") };
+    (service_uninstalled $l:ident) => { $crate::$l!("已卸载服务 {}", "Service {} uninstalled") };
+    (service_started $l:ident) => { $crate::$l!("已启动服务 {}", "Service {} started") };
+    (service_stopped $l:ident) => { $crate::$l!("已停止服务 {}", "Service {} stopped") };
+
+    // 健康检查
+    (healthcheck_ok $l:ident) => { $crate::$l!("正常：Cookie 有效（用户 {}），接口可访问", "OK: cookie is valid (user {}), API is reachable") };
+    (healthcheck_unreachable $l:ident) => { $crate::$l!("接口不可访问: {}", "API unreachable: {}") };
+    (healthcheck_cookie_invalid $l:ident) => { $crate::$l!("Cookie 无效: {}", "Cookie is invalid: {}") };
+    (healthcheck_timeout $l:ident) => { $crate::$l!("接口 {} 秒内未响应", "API did not respond within {} seconds") };
+
+    // 配置重新加载
+    (settings_changed $l:ident) => { $crate::$l!("认领参数已更新: 轮询间隔={}秒, 学科ID={}, 学段ID={}, 线索类型ID={}", "Claim settings updated: interval={}s, subject ID={}, step ID={}, clue type ID={}") };
+    (reload_requested $l:ident) => { $crate::$l!("收到重新加载配置的请求", "Reload requested") };
+    (event_settings_changed $l:ident) => { $crate::$l!("认领参数已更新，轮询间隔 {} 秒", "Claim settings updated, interval {}s") };
+    (reload_config_failed $l:ident) => { $crate::$l!("重新加载配置失败，继续使用原配置: {}", "Failed to reload config, keeping the current one: {}") };
+    (reload_args_invalid $l:ident) => { $crate::$l!("重新加载配置时参数无效: {}", "Invalid arguments when reloading config: {}") };
+    (config_reloaded $l:ident) => { $crate::$l!("已重新加载配置文件: {}", "Config reloaded from {}") };
+    (notifiers_reloaded $l:ident) => { $crate::$l!("通知目标已更新", "Notification targets updated") };
+
+    // 系统凭据存储
+    (keyring_failed $l:ident) => { $crate::$l!("访问系统凭据存储失败: {}", "Failed to access the system credential store: {}") };
+    (auth_stored $l:ident) => { $crate::$l!("凭据存储中已保存 Cookie（{} 个字符）", "A cookie is stored in the credential store ({} characters)") };
+    (auth_not_stored $l:ident) => { $crate::$l!("凭据存储中没有保存 Cookie", "No cookie is stored in the credential store") };
+    (auth_deleted $l:ident) => { $crate::$l!("已从凭据存储中删除 Cookie", "Cookie removed from the credential store") };
+    (auth_saved $l:ident) => { $crate::$l!("Cookie 已保存到系统凭据存储", "Cookie saved to the system credential store") };
+    (auth_config_overrides $l:ident) => { $crate::$l!("配置文件 {} 中的 Cookie 优先于凭据存储，请将其删除", "The cookie in config file {} takes precedence over the credential store; remove it") };
+
+    // 加密保存的 Cookie
+    (encrypt_cookie_failed $l:ident) => { $crate::$l!("加密 Cookie 失败: {}", "Failed to encrypt the cookie: {}") };
+    (decrypt_cookie_failed $l:ident) => { $crate::$l!("解密 Cookie 失败: {}", "Failed to decrypt the cookie: {}") };
+    (wrong_passphrase $l:ident) => { $crate::$l!("口令错误，无法解密 Cookie", "Wrong passphrase, cannot decrypt the cookie") };
+    (passphrase_required $l:ident) => { $crate::$l!("Cookie 已加密保存，请通过环境变量 {} 提供口令", "The cookie is encrypted; provide the passphrase via the {} environment variable") };
+    (passphrase_prompt $l:ident) => { $crate::$l!("请输入解密 Cookie 的口令", "Enter the passphrase for the cookie") };
+    (passphrase_confirm_prompt $l:ident) => { $crate::$l!("请再次输入口令", "Enter the passphrase again") };
+    (passphrase_mismatch $l:ident) => { $crate::$l!("两次输入的口令不一致", "Passphrases do not match") };
+    (encrypted_cookie_unsupported $l:ident) => { $crate::$l!("Cookie 已加密保存，需要启用 encrypted-cookie 特性才能解密", "The cookie is encrypted; rebuild with the encrypted-cookie feature to decrypt it") };
+
+    // Cookie 解析
+    (cookie_not_in_curl $l:ident) => { $crate::$l!("curl 命令中没有 Cookie（-H 'Cookie: ...' 或 -b）", "No cookie found in the curl command (-H 'Cookie: ...' or -b)") };
+    (cookie_no_pairs $l:ident) => { $crate::$l!("Cookie 中没有 name=value 形式的字段", "The cookie contains no name=value pairs") };
+    (cookie_missing_keys $l:ident) => { $crate::$l!("Cookie 缺少必需的字段: {}，请确认已登录并复制了完整的 Cookie", "The cookie is missing required fields: {}; make sure you are logged in and copied the whole cookie") };
+
+    // cookies.txt
+    (read_cookie_file_failed $l:ident) => { $crate::$l!("读取 Cookie 文件 {} 失败: {}", "Failed to read cookie file {}: {}") };
+    (invalid_server_url $l:ident) => { $crate::$l!("无效的服务器地址: {}", "Invalid server URL: {}") };
+    (cookie_file_no_match $l:ident) => { $crate::$l!("Cookie 文件中没有 {} 的 Cookie", "The cookie file has no cookies for {}") };
+
+    // 扫码登录
+    (qr_code_response $l:ident) => { $crate::$l!("获取登录二维码响应: {}", "Login QR code response: {}") };
+    (qr_code_failed $l:ident) => { $crate::$l!("获取登录二维码失败", "Failed to get the login QR code") };
+    (qr_poll_response $l:ident) => { $crate::$l!("扫码状态响应: {}", "QR code status response: {}") };
+    (qr_login_response $l:ident) => { $crate::$l!("扫码登录响应: {}", "QR code login response: {}") };
+    (qr_login_expired $l:ident) => { $crate::$l!("二维码已失效，请重新运行登录", "The QR code has expired; run login again") };
+    (qr_login_cancelled $l:ident) => { $crate::$l!("已在手机上取消登录", "Login was cancelled on the phone") };
+    (qr_scan_prompt $l:ident) => { $crate::$l!("请用手机百度 App 扫描上方二维码登录；二维码显示不全时可在浏览器中打开 {}", "Scan the QR code above with the Baidu app to log in; if it does not display correctly, open {} in a browser") };
+    (qr_scanned $l:ident) => { $crate::$l!("已扫码，请在手机上确认登录", "Scanned, confirm the login on your phone") };
+    (qr_confirmed $l:ident) => { $crate::$l!("已确认登录，正在获取 Cookie", "Login confirmed, fetching the cookie") };
+    (qr_render_failed $l:ident) => { $crate::$l!("生成二维码失败: {}", "Failed to render the QR code: {}") };
+    (login_saved $l:ident) => { $crate::$l!("Cookie 已写入配置文件 {}", "Cookie saved to config file {}") };
+
+    // 动态令牌
+    (invalid_token_spec $l:ident) => { $crate::$l!("令牌格式无效: {}，应为 <请求头>=cookie:<字段名>、<请求头>=json:<地址>#<JSON Pointer> 或 <请求头>=page:<地址>#<前缀>{{}}<后缀>", "Invalid token spec: {}, expected <header>=cookie:<name>, <header>=json:<url>#<JSON pointer> or <header>=page:<url>#<prefix>{{}}<suffix>") };
+    (token_cookie_missing $l:ident) => { $crate::$l!("Cookie 中没有令牌字段 {}", "The cookie has no token field {}") };
+    (fetch_token_failed $l:ident) => { $crate::$l!("获取令牌 {} 失败: {}", "Failed to fetch token {}: {}") };
+    (token_not_found $l:ident) => { $crate::$l!("未在 {1} 的响应中找到令牌 {0}", "Token {0} not found in the response of {1}") };
+    (token_fetched $l:ident) => { $crate::$l!("已获取令牌 {}（{}）", "Fetched token {} ({})") };
+    (token_refreshing $l:ident) => { $crate::$l!("服务器返回 {}，刷新令牌后重试", "Server returned {}, refreshing tokens and retrying") };
+
+    // 故障切换
+    (endpoint_unreachable $l:ident) => { $crate::$l!("无法连接服务器地址 {}，暂停使用: {}", "Cannot connect to server {}, suspending it: {}") };
+    (endpoint_recovered $l:ident) => { $crate::$l!("服务器地址 {} 已恢复", "Server {} is reachable again") };
+
+    // 主机名解析
+    (invalid_resolve $l:ident) => { $crate::$l!("主机名解析规则格式无效: {}，应为 HOST=IP，如 easylearn.baidu.com=1.2.3.4", "Invalid resolve rule: {}, expected HOST=IP such as easylearn.baidu.com=1.2.3.4") };
+
+    // 条件请求
+    (task_list_not_modified $l:ident) => { $crate::$l!("任务列表未变化（304），使用上次的结果", "Task list not modified (304), reusing the previous result") };
+    (pool_unchanged $l:ident) => { $crate::$l!("任务池没有新任务，上次没有可认领的任务，跳过筛选", "No new tasks in the pool and nothing to claim last time, skipping filtering") };
+
+    // 任务池变化
+    (new_tasks_appeared $l:ident) => { $crate::$l!("任务池中出现 {} 个新任务: {}", "{} new tasks appeared in the pool: {}") };
+
+    // 任务池竞争
+    (tasks_drained $l:ident) => { $crate::$l!("任务池中有 {} 个任务被他人认领", "{} tasks in the pool were claimed by others") };
+    (store_pool_failed $l:ident) => { $crate::$l!("写入任务池记录失败: {}", "Failed to write task pool record: {}") };
+    (hour_header $l:ident) => { $crate::$l!("时段", "Hour") };
+    (seen_header $l:ident) => { $crate::$l!("出现任务数", "Seen") };
+    (drained_header $l:ident) => { $crate::$l!("被他人认领", "Drained") };
+    (avg_lifetime_header $l:ident) => { $crate::$l!("平均停留", "Avg lifetime") };
+    (contention_header $l:ident) => { $crate::$l!("竞争指数", "Contention") };
+    (contention_table $l:ident) => { $crate::$l!("\n按学科和时段的竞争情况（竞争指数 = 被他人认领的比例 ÷ 平均停留分钟数）\n{}", "\nContention by subject and hour (index = drained share / avg lifetime in minutes)\n{}") };
+
+    // 任务池热力图
+    (weekday_names $l:ident) => { $crate::$l!("周一,周二,周三,周四,周五,周六,周日", "Mon,Tue,Wed,Thu,Fri,Sat,Sun") };
+    (no_pool_records $l:ident) => { $crate::$l!("\n还没有任务池记录，使用 --db 运行自动认领后再查看", "\nNo task pool records yet, run auto-claim with --db first") };
+    (heatmap_table $l:ident) => { $crate::$l!("\n按星期几和小时的平均任务池大小（. 为空，# 为最多，平均 {:.1} 个任务；空白为没有记录）\n{}", "\nAverage pool size by weekday and hour (. empty, # busiest at {:.1} tasks on average; blank means no records)\n{}") };
+    (no_recommended_windows $l:ident) => { $crate::$l!("\n没有观测次数不少于 {} 次且有任务的时段，暂无推荐", "\nNo hour with at least {} observations and tasks yet, no recommendation") };
+    (recommended_windows $l:ident) => { $crate::$l!("\n推荐的轮询时段:", "\nRecommended polling windows:") };
+    (recommended_window $l:ident) => { $crate::$l!("  {} {:02}:00-{:02}:59  平均 {:.1} 个任务，{:.0}% 的时间非空（{} 次观测）", "  {} {:02}:00-{:02}:59  {:.1} tasks on average, non-empty {:.0}% of the time ({} observations)") };
+
+    // 接口延迟
+    (list_latency_percentiles_label $l:ident) => { $crate::$l!("列表延迟 p50/p95/p99", "List latency p50/p95/p99") };
+    (claim_latency_percentiles_label $l:ident) => { $crate::$l!("认领延迟 p50/p95/p99", "Claim latency p50/p95/p99") };
+
+    // 列表到认领的延迟
+    (latency_success_row $l:ident) => { $crate::$l!("列表到认领 {} 成功率", "Success rate, list to claim {}") };
+}
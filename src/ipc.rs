@@ -0,0 +1,267 @@
+//! 本地控制套接字（需启用 `control-socket` 特性，仅支持 Unix）
+//!
+//! 认领器在 Unix 套接字上提供 JSON-RPC 2.0 接口，每行一个请求、每行一个响应，
+//! 供 `bedu-claim ctl` 等本机工具操作已在运行的实例：
+//!
+//! | 方法 | 说明 |
+//! |------|------|
+//! | `status` | 运行状态和统计 |
+//! | `pause` | 暂停认领 |
+//! | `resume` | 恢复认领 |
+//! | `reload` | 重新读取状态文件（认领计数和已认领任务） |
+//!
+//! 所有方法都返回调用后的运行状态，例如：
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","method":"pause","id":1}
+//! ← {"jsonrpc":"2.0","result":{"claimed":3,"paused":true,...},"id":1}
+//! ```
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+use crate::client::bedu_api::BeduApi;
+use crate::client::{AutoClaimer, ClaimerStatus};
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+/// 方法执行失败（如状态文件读取失败）
+const SERVER_ERROR: i32 = -32000;
+
+/// 默认的套接字路径：`$XDG_RUNTIME_DIR/bedu-claim.sock`，未设置时放在系统临时目录
+pub fn default_socket_path() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("bedu-claim.sock")
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    id: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcResponse {
+    jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+    id: Value,
+}
+
+/// JSON-RPC 错误
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[error("{}", tr!(rpc_error, .message, .code))]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// 绑定控制套接字
+///
+/// 路径上已有套接字文件时先尝试连接：仍有实例在监听则报错，否则视为上次异常退出的残留文件并删除。
+pub async fn bind(path: &Path) -> Result<UnixListener> {
+    match UnixListener::bind(path) {
+        Err(e) if e.kind() == ErrorKind::AddrInUse => {
+            if UnixStream::connect(path).await.is_ok() {
+                return Err(anyhow!(tr!(control_socket_in_use, path.display())));
+            }
+            std::fs::remove_file(path)?;
+            Ok(UnixListener::bind(path)?)
+        }
+        result => result.map_err(|e| anyhow!(tr!(control_socket_bind_failed, path.display(), e))),
+    }
+}
+
+/// 在 `listener` 上提供控制接口，直到 `shutdown` 完成，返回时断开所有连接
+///
+/// ```rust,no_run
+/// use bedu_claim::client::{AutoClaimConfig, AutoClaimer};
+/// use std::sync::Arc;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let claimer = Arc::new(AutoClaimer::new(AutoClaimConfig::default())?);
+/// let path = bedu_claim::ipc::default_socket_path();
+/// let listener = bedu_claim::ipc::bind(&path).await?;
+/// let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+/// let server = tokio::spawn(bedu_claim::ipc::serve(listener, claimer.clone(), async {
+///     let _ = stopped.await;
+/// }));
+///
+/// let summary = claimer.start().await;
+/// let _ = stop.send(());
+/// server.await?;
+/// std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve<A: BeduApi + 'static>(
+    listener: UnixListener,
+    claimer: Arc<AutoClaimer<A>>,
+    shutdown: impl Future<Output = ()>,
+) {
+    // 连接任务持有认领器，随 JoinSet 一起结束，避免关闭后认领器无法释放
+    let mut connections = JoinSet::new();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let claimer = claimer.clone();
+                    connections.spawn(async move {
+                        if let Err(e) = handle_connection(stream, &claimer).await {
+                            debug!(error = %e, "{}", tr!(control_connection_dropped, e));
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "{}", tr!(control_accept_failed, e)),
+            },
+            Some(_) = connections.join_next() => {}
+        }
+    }
+}
+
+async fn handle_connection<A: BeduApi>(stream: UnixStream, claimer: &AutoClaimer<A>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = handle_line(&line, claimer).await;
+        let mut output = serde_json::to_vec(&response)?;
+        output.push(b'\n');
+        writer.write_all(&output).await?;
+    }
+    Ok(())
+}
+
+async fn handle_line<A: BeduApi>(line: &str, claimer: &AutoClaimer<A>) -> RpcResponse {
+    let (id, result) = match serde_json::from_str::<RpcRequest>(line) {
+        Ok(request) if request.jsonrpc != "2.0" => (
+            request.id,
+            Err(RpcError::new(
+                INVALID_REQUEST,
+                tr!(jsonrpc_version_unsupported),
+            )),
+        ),
+        Ok(request) => {
+            let result = call(&request.method, claimer).await;
+            (request.id, result)
+        }
+        Err(e) => (
+            Value::Null,
+            Err(RpcError::new(PARSE_ERROR, tr!(invalid_rpc_request, e))),
+        ),
+    };
+
+    let (result, error) = match result {
+        Ok(status) => (serde_json::to_value(status).ok(), None),
+        Err(error) => (None, Some(error)),
+    };
+    RpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result,
+        error,
+        id,
+    }
+}
+
+async fn call<A: BeduApi>(
+    method: &str,
+    claimer: &AutoClaimer<A>,
+) -> Result<ClaimerStatus, RpcError> {
+    match method {
+        "status" => {}
+        "pause" => claimer.pause(),
+        "resume" => claimer.resume(),
+        "reload" => {
+            claimer
+                .load_state()
+                .await
+                .map_err(|e| RpcError::new(SERVER_ERROR, tr!(reload_state_failed, e)))?;
+            info!("{}", tr!(state_reloaded));
+        }
+        _ => {
+            return Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                tr!(unknown_rpc_method, method),
+            ));
+        }
+    }
+    Ok(claimer.status().await)
+}
+
+/// 控制套接字客户端
+pub struct ControlClient {
+    lines: tokio::io::Lines<BufReader<tokio::net::unix::OwnedReadHalf>>,
+    writer: tokio::net::unix::OwnedWriteHalf,
+    next_id: u64,
+}
+
+impl ControlClient {
+    /// 连接正在运行的认领器
+    pub async fn connect(path: &Path) -> Result<Self> {
+        let stream = UnixStream::connect(path)
+            .await
+            .map_err(|e| anyhow!(tr!(control_socket_connect_failed, path.display(), e)))?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            next_id: 1,
+        })
+    }
+
+    /// 调用一个方法，返回调用后的运行状态（JSON）
+    pub async fn call(&mut self, method: &str) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut request = serde_json::to_vec(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "id": id,
+        }))?;
+        request.push(b'\n');
+        self.writer.write_all(&request).await?;
+
+        let line = self
+            .lines
+            .next_line()
+            .await?
+            .ok_or_else(|| anyhow!(tr!(control_socket_closed)))?;
+        let response: RpcResponse =
+            serde_json::from_str(&line).map_err(|e| anyhow!(tr!(invalid_rpc_response, e)))?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(error.into()),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(anyhow!(tr!(rpc_response_empty))),
+        }
+    }
+}
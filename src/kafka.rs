@@ -0,0 +1,81 @@
+//! 将认领成功的任务发布到 Kafka（需启用 `kafka` 特性）
+//!
+//! 每个任务一条消息，键为任务ID，值为 [`ClaimedTask`] 的 JSON（含完整的任务详情），
+//! 下游可实时消费并分配给作者。
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use rdkafka::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::client::{ClaimEvent, ClaimedTask};
+
+/// 认领任务的 Kafka 发布者
+pub struct ClaimPublisher {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl ClaimPublisher {
+    /// 连接 `brokers`（如 `127.0.0.1:9092`，多个以逗号分隔），任务发布到 `topic`
+    pub fn new(brokers: &str, topic: impl Into<String>) -> Result<Self> {
+        let mut config = ClientConfig::new();
+        config.set("bootstrap.servers", brokers);
+        Self::from_config(&config, topic)
+    }
+
+    /// 使用自定义的生产者配置，如 SASL 认证、压缩等
+    ///
+    /// 未设置 `message.timeout.ms` 时使用 30 秒，避免 broker 不可用时认领结束后长时间等待。
+    pub fn from_config(config: &ClientConfig, topic: impl Into<String>) -> Result<Self> {
+        let mut config = config.clone();
+        if config.get("message.timeout.ms").is_none() {
+            config.set("message.timeout.ms", "30000");
+        }
+        let producer = config
+            .create()
+            .map_err(|e| anyhow!(tr!(kafka_producer_failed, e)))?;
+        Ok(Self {
+            producer,
+            topic: topic.into(),
+        })
+    }
+
+    /// 发布一个认领成功的任务，等待 broker 确认
+    pub async fn publish(&self, task: &ClaimedTask) -> Result<()> {
+        let key = task.task.task_id.to_string();
+        let payload = serde_json::to_string(task)?;
+        self.producer
+            .send(
+                FutureRecord::to(&self.topic).key(&key).payload(&payload),
+                Duration::from_secs(10),
+            )
+            .await
+            .map_err(|(e, _)| anyhow!(tr!(kafka_delivery_failed, key, e)))?;
+        Ok(())
+    }
+}
+
+/// 将事件流中认领成功的任务发布到 Kafka，直到事件通道关闭
+pub async fn publish_events(
+    mut receiver: broadcast::Receiver<ClaimEvent>,
+    publisher: &ClaimPublisher,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(ClaimEvent::TaskClaimed { task }) => {
+                if let Err(e) = publisher.publish(&task).await {
+                    warn!(error = %e, "{}", tr!(kafka_publish_failed, e));
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(skipped, "{}", tr!(kafka_lagged, skipped));
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
@@ -5,7 +5,26 @@
 //! ## 功能模块
 //!
 //! - `api`: 包含所有API响应的数据结构定义
-//! - `client`: 包含HTTP客户端和自动认领器
+//! - `error`: 库的错误类型 `BeduError`
+//! - `client`: 包含HTTP客户端（`client` 特性）、自动认领器和自动审核器（`claimer` 特性）
+//! - `export`: 认领记录导出为 CSV / JSON（`claimer` 特性）
+//! - `i18n`: 日志、错误信息和命令输出的中英文消息目录
+//! - `logging`: 日志文件输出与轮转（`cli` 特性）
+//! - `store`: SQLite 认领历史存储（需启用 `sqlite` 特性）
+//! - `control`: 内嵌的 HTTP 控制接口（需启用 `control-server` 特性）
+//! - `ipc`: 本地控制套接字，JSON-RPC over Unix socket（`control-socket` 特性，`cli` 默认包含）
+//! - `grpc`: gRPC 控制与事件服务（需启用 `grpc` 特性）
+//! - `kafka`: 将认领成功的任务发布到 Kafka（需启用 `kafka` 特性）
+//! - `redis`: 认领任务推送到 Redis 队列，并从 Redis 读取控制命令（需启用 `redis` 特性）
+//! - `telemetry`: OpenTelemetry 链路追踪导出（需启用 `otel` 特性）
+//! - `client::blocking`: 同步版本的客户端和认领器（需启用 `blocking` 特性）
+//! - `testing`: 基于 wiremock 的假服务器，用于离线端到端测试（需启用 `testing` 特性）
+//!
+//! 默认启用 `cli` 特性（包含 `claimer` 和 `client`）；只需要接口类型和 HTTP 客户端时可使用
+//! `default-features = false, features = ["client"]`，不会引入 clap、tracing-subscriber 和完整的 tokio。
+//!
+//! 编译到 `wasm32-unknown-unknown` 时只包含 `api`、`error` 和 `client::HttpClient`（不含中间件、代理和超时），
+//! 请求通过浏览器 fetch 发出并携带浏览器中的 Cookie，可用于浏览器扩展前端。
 //!
 //! ## 基本用法
 //!
@@ -23,10 +42,12 @@
 //!         step_id: 1,
 //!         subject_id: 2,
 //!         clue_type_id: 1,
+//!         ..Default::default()
 //!     };
 //!
-//!     let claimer = AutoClaimer::new(config);
-//!     claimer.start().await?;
+//!     let claimer = AutoClaimer::new(config)?;
+//!     let summary = claimer.start().await?;
+//!     println!("认领 {} 个任务，尝试 {} 次", summary.total_claims, summary.attempts);
 //!
 //!     Ok(())
 //! }
@@ -41,30 +62,67 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
-//!     let client = HttpClient::new(
+//!     let client = HttpClient::try_new(
 //!         "https://easylearn.baidu.com".to_string(),
 //!         "your_cookie_here".to_string()
-//!     );
+//!     )?;
 //!
-//!     // 获取用户信息
-//!     let user_info = client.get_user_info().await?;
-//!     println!("用户名: {}", user_info.data.user_name);
+//!     // 获取用户信息，errno 非 0 时 into_result 返回 BeduError
+//!     let user_info = client.get_user_info().await?.into_result()?;
+//!     println!("用户名: {}", user_info.user_name);
 //!
 //!     // 获取任务列表
 //!     let mut options = HashMap::new();
 //!     options.insert("taskType".to_string(), json!("audittask"));
 //!     options.insert("subject".to_string(), json!(2));
 //!
-//!     let tasks = client.get_audit_task_list(&options).await?;
-//!     println!("任务数量: {}", tasks.data.list.len());
+//!     let tasks = client.get_audit_task_list(&options).await?.into_result()?;
+//!     println!("任务数量: {}", tasks.list.len());
 //!
 //!     Ok(())
 //! }
 //! ```
 
+#[cfg(all(target_arch = "wasm32", feature = "claimer"))]
+compile_error!(
+    "wasm32 目标只支持 `client` 特性，请使用 `--no-default-features --features client` 构建"
+);
+
 pub mod api;
+#[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "control-server")]
+pub mod control;
+pub mod error;
+#[cfg(feature = "claimer")]
+pub mod export;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod i18n;
+#[cfg(all(unix, feature = "control-socket"))]
+pub mod ipc;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "cli")]
+pub mod logging;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "sqlite")]
+pub mod store;
+#[cfg(feature = "otel")]
+pub mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // 重新导出常用的类型和结构体，方便使用
 pub use api::*;
-pub use client::{AutoClaimConfig, AutoClaimer, HttpClient};
+#[cfg(feature = "client")]
+pub use client::HttpClient;
+#[cfg(feature = "claimer")]
+pub use client::{
+    AutoClaimConfig, AutoClaimer, ClaimEvent, ClaimFailure, ClaimState, ClaimStats, ClaimSummary,
+    ClaimedTask,
+};
+pub use error::BeduError;
+#[cfg(feature = "claimer")]
+pub use export::export_claims;
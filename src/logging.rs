@@ -0,0 +1,193 @@
+//! 日志文件输出与轮转
+//!
+//! 支持按时间（每小时/每天）或按文件大小轮转，长时间运行时避免单个日志文件无限增长。
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::{DefaultFields, Writer};
+
+/// 日志轮转策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRotation {
+    /// 不轮转
+    Never,
+    /// 每小时轮转
+    Hourly,
+    /// 每天轮转
+    Daily,
+    /// 文件超过指定字节数时轮转
+    Size(u64),
+}
+
+impl FromStr for LogRotation {
+    type Err = anyhow::Error;
+
+    /// 解析 `never`、`hourly`、`daily` 或 `10MB`/`512KB` 形式的大小
+    fn from_str(s: &str) -> Result<Self> {
+        let value = s.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "never" => return Ok(Self::Never),
+            "hourly" => return Ok(Self::Hourly),
+            "daily" => return Ok(Self::Daily),
+            _ => {}
+        }
+
+        let (number, unit) = value.split_at(
+            value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len()),
+        );
+        let number: u64 = number
+            .parse()
+            .map_err(|_| anyhow!(tr!(invalid_log_rotation, s)))?;
+        let multiplier = match unit.trim() {
+            "" | "b" => 1,
+            "k" | "kb" => 1024,
+            "m" | "mb" => 1024 * 1024,
+            "g" | "gb" => 1024 * 1024 * 1024,
+            _ => return Err(anyhow!(tr!(invalid_log_rotation, s))),
+        };
+
+        if number == 0 {
+            return Err(anyhow!(tr!(log_rotation_size_not_positive)));
+        }
+
+        Ok(Self::Size(number * multiplier))
+    }
+}
+
+/// 创建日志文件写入器，返回的守卫需在程序退出前保持存活以刷新缓冲
+pub fn file_writer(
+    path: &Path,
+    rotation: LogRotation,
+    max_files: usize,
+) -> Result<(NonBlocking, WorkerGuard)> {
+    let directory = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!(tr!(invalid_log_path, path.display())))?;
+
+    fs::create_dir_all(directory)?;
+
+    let time_rotation = match rotation {
+        LogRotation::Never => Rotation::NEVER,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Size(max_bytes) => {
+            let writer = SizeRotatingFile::open(path.to_path_buf(), max_bytes, max_files)?;
+            return Ok(tracing_appender::non_blocking(writer));
+        }
+    };
+
+    let appender = RollingFileAppender::builder()
+        .rotation(time_rotation)
+        .filename_prefix(file_name)
+        .max_log_files(max_files.max(1))
+        .build(directory)?;
+
+    Ok(tracing_appender::non_blocking(appender))
+}
+
+/// 日志文件使用的字段格式化器
+///
+/// 与终端输出层使用不同的类型，避免两层共享已格式化的 span 字段（带颜色控制符且重复记录）。
+#[derive(Debug, Default)]
+pub struct PlainFields(DefaultFields);
+
+impl PlainFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'writer> FormatFields<'writer> for PlainFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        writer: Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        self.0.format_fields(writer, fields)
+    }
+}
+
+/// 按大小轮转的日志文件，轮转后旧文件依次重命名为 `.1`、`.2` ...
+struct SizeRotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    written: u64,
+}
+
+impl SizeRotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file,
+            written,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.max_files > 1 {
+            let _ = fs::remove_file(self.rotated_path(self.max_files - 1));
+            for index in (1..self.max_files - 1).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.written = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for SizeRotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
@@ -1,80 +1,159 @@
-mod api;
-mod client;
-
-use anyhow::{Result, anyhow};
-use clap::Parser;
-use client::{AutoClaimConfig, AutoClaimer};
-
-#[derive(Parser, Debug)]
-#[command(author, version, about = "百度教育自动认领工具", long_about = None)]
-struct Args {
-    #[arg(short, long, help = "Cookie字符串")]
-    cookie: String,
-
-    #[arg(short, long, default_value = "2", help = "学科ID")]
-    subject_id: i32,
-
-    #[arg(short = 'e', long, default_value = "1", help = "学段ID")]
-    step_id: i32,
-
-    #[arg(short = 'u', long, default_value = "1", help = "线索类型ID")]
-    clue_type_id: i32,
+mod cli;
+
+use anyhow::Result;
+use bedu_claim::logging::{PlainFields, file_writer};
+#[cfg(unix)]
+use bedu_claim::tr;
+use clap::{CommandFactory, FromArgMatches};
+use cli::config::Config;
+use cli::{Cli, Command};
+use std::path::PathBuf;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+fn main() -> Result<()> {
+    // 配置文件中的值作为参数默认值，需要在解析命令行前读取；配置有误时只允许运行 init 重新生成
+    let config_path =
+        cli::config::path_from_args(std::env::args_os()).unwrap_or_else(cli::config::default_path);
+    let config = Config::load_defaults(&config_path);
+    let command = match &config {
+        Ok(config) => config.apply(Cli::command()),
+        Err(_) => Cli::command(),
+    };
+    let matches = match command.try_get_matches() {
+        Ok(matches) => matches,
+        // 缺少参数可能是因为配置文件读取失败，先报告配置文件的错误
+        Err(e) => {
+            if e.use_stderr() {
+                config?;
+            }
+            e.exit()
+        }
+    };
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    bedu_claim::i18n::set_lang(cli.lang);
+    if !matches!(cli.command, Command::Init(_)) {
+        config?;
+    }
 
-    #[arg(
-        short,
-        long,
-        default_value = "audittask",
-        help = "任务类型 (audittask/producetask)"
-    )]
-    task_type: String,
+    // 加密保存的 Cookie 需要在后台运行前解密，之后无法在终端输入口令
+    if let Some(connection) = cli.command.connection_mut() {
+        connection.resolve_cookie()?;
+    }
 
-    #[arg(short = 'l', long, default_value = "10", help = "认领限制数量")]
-    limit: i32,
+    // 后台运行需要在启动异步运行时之前 fork，子进程中只会保留调用 fork 的线程
+    #[cfg(unix)]
+    let _pid_file = match &cli.command {
+        Command::Run(args) if args.daemon => {
+            let log_file = cli
+                .log_file
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!(tr!(daemon_requires_log_file)))?;
+            cli::daemon::daemonize(log_file, args.pid_file.as_deref())?
+        }
+        _ => None,
+    };
 
-    #[arg(short, long, default_value = "3.0", help = "轮询间隔 (秒)")]
-    interval: f64,
+    // 由服务控制管理器启动时，在服务线程中执行
+    #[cfg(all(windows, feature = "windows-service"))]
+    if matches!(&cli.command, Command::Run(args) if args.windows_service) {
+        use futures::FutureExt;
+        return cli::service::run(move || start(cli, config_path).boxed_local());
+    }
 
-    #[arg(
-        long,
-        default_value = "https://easylearn.baidu.com",
-        help = "服务器基础URL"
-    )]
-    server: String,
+    tokio::runtime::Runtime::new()?.block_on(start(cli, config_path))
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // 使用 env_logger::Builder 来设置默认日志级别
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
-
-    let args = Args::parse();
+/// 初始化日志并执行子命令
+async fn start(cli: Cli, config_path: PathBuf) -> Result<()> {
+    cli::style::init();
+
+    // 除文本模式的 run 外，日志写到 stderr，stdout 只保留命令输出；仪表盘模式下日志只写入日志文件
+    let log_writer = if cli.command.shows_dashboard() {
+        BoxMakeWriter::new(std::io::sink)
+    } else if cli.command.shows_progress() {
+        progress_writer(cli.command.logs_to_stdout())
+    } else if cli.command.logs_to_stdout() {
+        BoxMakeWriter::new(std::io::stdout)
+    } else {
+        BoxMakeWriter::new(std::io::stderr)
+    };
 
-    // 验证参数
-    if args.cookie.is_empty() {
-        return Err(anyhow!("Cookie不能为空"));
-    }
+    // 日志输出到终端时带颜色，重定向到文件或管道时不带
+    let log_color = if cli.command.logs_to_stdout() {
+        cli::style::should_color(&std::io::stdout())
+    } else {
+        cli::style::should_color(&std::io::stderr())
+    };
+    let filter = cli.log_filter();
+    // 日志文件不输出颜色控制符
+    let (file_layer, _log_guard) = match &cli.log_file {
+        Some(path) => {
+            let (writer, guard) = file_writer(path, cli.log_rotate, cli.log_max_files)?;
+            let layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .fmt_fields(PlainFields::new())
+                .with_writer(writer);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
 
-    if args.interval < 0.001 {
-        return Err(anyhow!("轮询间隔不能小于0.001秒（1毫秒）"));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(log_color)
+                .fmt_fields(cli::style::StatusFields::default())
+                .with_writer(log_writer),
+        )
+        .with(file_layer);
+
+    // 配置了 OTLP 导出地址时启用链路追踪
+    #[cfg(feature = "otel")]
+    let _telemetry = {
+        let (layer, guard) = bedu_claim::telemetry::layer_from_env()?.unzip();
+        registry.with(layer).init();
+        guard
+    };
+    #[cfg(not(feature = "otel"))]
+    registry.init();
+
+    match cli.command {
+        Command::Init(args) => cli::init::execute(args, &config_path).await,
+        #[cfg(feature = "qr-login")]
+        Command::Login(args) => cli::login::execute(args, &config_path).await,
+        Command::Run(args) => cli::run::execute(*args).await,
+        Command::List(args) => cli::tasks::list(args).await,
+        Command::Claim(args) => cli::tasks::claim(args).await,
+        Command::Pick(args) => cli::tasks::pick(args).await,
+        Command::Release(args) => cli::tasks::release(args).await,
+        Command::Whoami(args) => cli::whoami::execute(args).await,
+        Command::Labels(args) => cli::labels::execute(args).await,
+        Command::Healthcheck(args) => cli::healthcheck::execute(args).await,
+        #[cfg(feature = "keyring")]
+        Command::Auth(args) => cli::auth::execute(args, &config_path).await,
+        #[cfg(feature = "sqlite")]
+        Command::Stats(args) => cli::stats::execute(&args),
+        #[cfg(unix)]
+        Command::Ctl(args) => cli::ctl::execute(args).await,
+        #[cfg(all(windows, feature = "windows-service"))]
+        Command::Service(args) => cli::service::execute(args),
     }
+}
 
-    if !["audittask", "producetask"].contains(&args.task_type.as_str()) {
-        return Err(anyhow!("任务类型必须是 audittask 或 producetask"));
+/// 显示进度条时的日志输出，写日志前先擦除进度条
+#[cfg(feature = "progress")]
+fn progress_writer(to_stdout: bool) -> BoxMakeWriter {
+    if to_stdout {
+        BoxMakeWriter::new(cli::progress::stdout)
+    } else {
+        BoxMakeWriter::new(cli::progress::stderr)
     }
+}
 
-    let config = AutoClaimConfig {
-        server_base_url: args.server,
-        cookie: args.cookie,
-        task_type: args.task_type,
-        claim_limit: args.limit,
-        interval: args.interval,
-        step_id: args.step_id,
-        subject_id: args.subject_id,
-        clue_type_id: args.clue_type_id,
-    };
-
-    let auto_claimer = AutoClaimer::new(config);
-    auto_claimer.start().await?;
-
-    Ok(())
+#[cfg(not(feature = "progress"))]
+fn progress_writer(_to_stdout: bool) -> BoxMakeWriter {
+    unreachable!("未启用 progress 特性")
 }
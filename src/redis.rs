@@ -0,0 +1,216 @@
+//! Redis 队列与远程控制（需启用 `redis` 特性）
+//!
+//! 认领成功的任务推送到 Redis 列表或 Stream，接入基于 Redis 的任务系统；
+//! 运行中还可以从 Redis 列表读取控制命令，暂停、恢复认领或修改认领限制：
+//!
+//! ```text
+//! RPUSH bedu-claim:control pause
+//! RPUSH bedu-claim:control resume
+//! RPUSH bedu-claim:control "limit 20"
+//! ```
+
+use crate::tr;
+use anyhow::{Result, anyhow};
+use redis::AsyncCommands;
+use redis::aio::MultiplexedConnection;
+use std::convert::Infallible;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::client::bedu_api::BeduApi;
+use crate::client::{AutoClaimer, ClaimEvent, ClaimSummary, ClaimedTask};
+
+/// 认领任务推送到的 Redis 数据结构
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueKind {
+    /// 列表，`RPUSH` 任务ID
+    #[default]
+    List,
+    /// Stream，`XADD` 任务ID、任务类型、尝试序号和认领时间
+    Stream,
+}
+
+impl FromStr for QueueKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "list" => Ok(Self::List),
+            "stream" => Ok(Self::Stream),
+            _ => Err(anyhow!(tr!(invalid_queue_kind, s))),
+        }
+    }
+}
+
+/// 认领任务的 Redis 队列
+pub struct ClaimQueue {
+    connection: MultiplexedConnection,
+    key: String,
+    kind: QueueKind,
+}
+
+impl ClaimQueue {
+    /// 连接 `url`（如 `redis://127.0.0.1:6379/0`），任务推送到 `key`
+    pub async fn connect(url: &str, key: impl Into<String>, kind: QueueKind) -> Result<Self> {
+        let connection = redis::Client::open(url)
+            .map_err(|e| anyhow!(tr!(invalid_redis_url, url, e)))?
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| anyhow!(tr!(redis_connect_failed, e)))?;
+        Ok(Self {
+            connection,
+            key: key.into(),
+            kind,
+        })
+    }
+
+    /// 推送一个认领成功的任务
+    pub async fn push(&self, task: &ClaimedTask) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let task_id = task.task.task_id;
+        match self.kind {
+            QueueKind::List => {
+                let _: i64 = connection.rpush(&self.key, task_id).await?;
+            }
+            QueueKind::Stream => {
+                let _: String = connection
+                    .xadd(
+                        &self.key,
+                        "*",
+                        &[
+                            ("task_id", task_id.to_string()),
+                            ("task_type", task.task_type.clone()),
+                            ("attempt", task.attempt.to_string()),
+                            ("claimed_at", task.claimed_at.to_rfc3339()),
+                        ],
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 将事件流中认领成功的任务推送到 Redis，直到事件通道关闭
+pub async fn push_events(mut receiver: broadcast::Receiver<ClaimEvent>, queue: &ClaimQueue) {
+    loop {
+        match receiver.recv().await {
+            Ok(ClaimEvent::TaskClaimed { task }) => {
+                if let Err(e) = queue.push(&task).await {
+                    warn!(
+                        task_id = task.task.task_id,
+                        error = %e,
+                        "{}",
+                        tr!(redis_push_failed, task.task.task_id, e)
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(skipped, "{}", tr!(redis_lagged, skipped));
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// 控制命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// 暂停认领
+    Pause,
+    /// 恢复认领
+    Resume,
+    /// 修改认领限制
+    Limit(i32),
+}
+
+impl FromStr for ControlCommand {
+    type Err = anyhow::Error;
+
+    /// 解析 `pause`、`resume` 或 `limit <数量>`
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split_whitespace();
+        let command = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("pause") => Self::Pause,
+            Some("resume") => Self::Resume,
+            Some("limit") => {
+                let limit = parts
+                    .next()
+                    .and_then(|limit| limit.parse().ok())
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(|| anyhow!(tr!(limit_command_usage)))?;
+                Self::Limit(limit)
+            }
+            _ => return Err(anyhow!(tr!(unknown_control_command, s))),
+        };
+        if parts.next().is_some() {
+            return Err(anyhow!(tr!(unknown_control_command, s)));
+        }
+        Ok(command)
+    }
+}
+
+impl ControlCommand {
+    /// 对认领器执行命令
+    pub async fn apply<A: BeduApi>(self, claimer: &AutoClaimer<A>) {
+        match self {
+            Self::Pause => claimer.pause(),
+            Self::Resume => claimer.resume(),
+            Self::Limit(limit) => claimer.set_claim_limit(limit).await,
+        }
+    }
+}
+
+/// 运行认领循环，同时从 Redis 列表 `key` 读取控制命令，认领结束后停止读取
+///
+/// Redis 不可用时只记录日志并每 5 秒重连，不影响认领。
+pub async fn start_with_control<A: BeduApi>(
+    claimer: &AutoClaimer<A>,
+    url: &str,
+    key: &str,
+) -> Result<ClaimSummary> {
+    let client = redis::Client::open(url).map_err(|e| anyhow!(tr!(invalid_redis_url, url, e)))?;
+    tokio::select! {
+        result = claimer.start() => result,
+        never = read_commands(&client, key, claimer) => match never {},
+    }
+}
+
+async fn read_commands<A: BeduApi>(
+    client: &redis::Client,
+    key: &str,
+    claimer: &AutoClaimer<A>,
+) -> Infallible {
+    loop {
+        if let Err(e) = read_commands_once(client, key, claimer).await {
+            warn!(error = %e, "{}", tr!(redis_control_read_failed, e));
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+}
+
+async fn read_commands_once<A: BeduApi>(
+    client: &redis::Client,
+    key: &str,
+    claimer: &AutoClaimer<A>,
+) -> Result<()> {
+    let mut connection = client.get_multiplexed_async_connection().await?;
+    info!(key, "{}", tr!(redis_control_started, key));
+    loop {
+        let popped: Option<(String, String)> = connection.blpop(key, 5.0).await?;
+        let Some((_, command)) = popped else {
+            continue;
+        };
+        match command.parse::<ControlCommand>() {
+            Ok(command) => {
+                info!(command = ?command, "{}", tr!(control_command_received, command));
+                command.apply(claimer).await;
+            }
+            Err(e) => warn!(command = %command, "{}", tr!(invalid_control_command_ignored, e)),
+        }
+    }
+}
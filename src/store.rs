@@ -0,0 +1,462 @@
+//! SQLite 认领历史存储（需启用 `sqlite` 特性）
+//!
+//! 记录每个认领成功的任务，进程重启后历史仍可查询。同时记录任务池中新出现的任务以及其中
+//! 被他人认领的任务，用于按学科和时段统计竞争程度；每次获取的任务池大小按小时汇总，
+//! 用于找出任务最多的时段。
+
+use crate::tr;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike, Weekday};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::api::TaskItem;
+use crate::client::{ClaimEvent, ClaimedTask, DrainedTask};
+
+/// 按天汇总的认领情况
+#[derive(Debug, Clone, Serialize)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    pub claims: i64,
+    /// 发起认领请求的任务数
+    pub requested: i64,
+    pub success_rate: f64,
+}
+
+/// 某学科在某个时段（任务出现时的本地小时）的竞争情况
+#[derive(Debug, Clone, Serialize)]
+pub struct Contention {
+    pub subject: String,
+    /// 本地时间 0-23
+    pub hour: u32,
+    /// 出现在任务池中且未被自己认领的任务数
+    pub seen: i64,
+    /// 其中从任务池中消失（被他人认领）的任务数
+    pub drained: i64,
+    /// 被他人认领的任务平均在任务池中停留的时长
+    pub avg_lifetime: Duration,
+    /// 竞争指数：被他人认领的比例除以平均停留分钟数，越高说明任务被抢得越多、越快
+    pub index: f64,
+}
+
+/// 某个星期几的某个小时任务池的平均情况
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolAvailability {
+    pub weekday: Weekday,
+    /// 本地时间 0-23
+    pub hour: u32,
+    /// 获取任务列表的次数
+    pub observations: i64,
+    /// 平均任务池大小（第一页的任务数）
+    pub avg_size: f64,
+    /// 任务池非空的比例
+    pub nonempty_rate: f64,
+}
+
+/// 认领历史存储
+pub struct ClaimStore {
+    conn: Mutex<Connection>,
+}
+
+impl ClaimStore {
+    /// 打开（不存在时创建）数据库文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        Self::init(conn)
+    }
+
+    /// 打开内存数据库，主要用于临时统计
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS claims (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                clue_id INTEGER NOT NULL,
+                task_type TEXT NOT NULL,
+                brief TEXT NOT NULL,
+                subject INTEGER NOT NULL,
+                subject_name TEXT NOT NULL,
+                step INTEGER NOT NULL,
+                attempt INTEGER NOT NULL,
+                claimed_at INTEGER NOT NULL,
+                task_json TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_claims_claimed_at ON claims (claimed_at);
+            CREATE TABLE IF NOT EXISTS claim_requests (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                requested_at INTEGER NOT NULL,
+                requested INTEGER NOT NULL,
+                succeeded INTEGER NOT NULL,
+                errno INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_claim_requests_requested_at ON claim_requests (requested_at);
+            CREATE TABLE IF NOT EXISTS pool_tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                subject_name TEXT NOT NULL,
+                appeared_at INTEGER NOT NULL,
+                lifetime_ms INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_pool_tasks_appeared_at ON pool_tasks (appeared_at);
+            CREATE INDEX IF NOT EXISTS idx_pool_tasks_task_id ON pool_tasks (task_id);
+            CREATE TABLE IF NOT EXISTS pool_samples (
+                day TEXT NOT NULL,
+                hour INTEGER NOT NULL,
+                weekday INTEGER NOT NULL,
+                observations INTEGER NOT NULL,
+                total_size INTEGER NOT NULL,
+                nonempty INTEGER NOT NULL,
+                PRIMARY KEY (day, hour)
+            );",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// 写入一条认领记录
+    pub fn insert_claim(&self, claim: &ClaimedTask) -> Result<()> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        conn.execute(
+            "INSERT INTO claims (task_id, clue_id, task_type, brief, subject, subject_name, step, attempt, claimed_at, task_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                claim.task.task_id,
+                claim.task.clue_id,
+                claim.task_type,
+                claim.task.brief,
+                claim.task.subject,
+                claim.task.subject_name,
+                claim.task.step,
+                claim.attempt,
+                claim.claimed_at.timestamp(),
+                serde_json::to_string(&claim.task)?,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 写入一次认领请求结果，用于计算成功率
+    pub fn insert_request(&self, requested: usize, succeeded: i32, errno: i32) -> Result<()> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        conn.execute(
+            "INSERT INTO claim_requests (requested_at, requested, succeeded, errno) VALUES (?1, ?2, ?3, ?4)",
+            params![Local::now().timestamp(), requested as i64, succeeded, errno],
+        )?;
+
+        Ok(())
+    }
+
+    /// 写入任务池中新出现的任务
+    pub fn insert_appeared(&self, tasks: &[TaskItem]) -> Result<()> {
+        let mut conn = self.conn.lock().expect("claim store mutex poisoned");
+        let tx = conn.transaction()?;
+        let now = Local::now().timestamp();
+        for task in tasks {
+            tx.execute(
+                "INSERT INTO pool_tasks (task_id, subject_name, appeared_at) VALUES (?1, ?2, ?3)",
+                params![task.task_id, task.subject_name, now],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// 记录被他人认领的任务在任务池中停留的时长
+    pub fn insert_drained(&self, tasks: &[DrainedTask]) -> Result<()> {
+        let mut conn = self.conn.lock().expect("claim store mutex poisoned");
+        let tx = conn.transaction()?;
+        for drained in tasks {
+            tx.execute(
+                "UPDATE pool_tasks SET lifetime_ms = ?1
+                 WHERE id = (SELECT MAX(id) FROM pool_tasks WHERE task_id = ?2 AND lifetime_ms IS NULL)",
+                params![drained.lifetime_ms as i64, drained.task.task_id],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// 记录一次获取到的任务池大小，累加到当前小时
+    pub fn insert_pool_size(&self, pool_size: usize) -> Result<()> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let now = Local::now();
+        conn.execute(
+            "INSERT INTO pool_samples (day, hour, weekday, observations, total_size, nonempty)
+             VALUES (?1, ?2, ?3, 1, ?4, ?5)
+             ON CONFLICT (day, hour) DO UPDATE SET
+                observations = observations + 1,
+                total_size = total_size + excluded.total_size,
+                nonempty = nonempty + excluded.nonempty",
+            params![
+                now.date_naive().to_string(),
+                now.hour(),
+                now.weekday().num_days_from_monday(),
+                pool_size as i64,
+                (pool_size > 0) as i64,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// 按星期几和小时汇总任务池大小，按星期一 0 点起排列，没有记录的时段不返回
+    pub fn pool_availability(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<PoolAvailability>> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT weekday, hour, SUM(observations), SUM(total_size), SUM(nonempty)
+             FROM pool_samples
+             WHERE day >= ?1 AND day < ?2
+             GROUP BY weekday, hour ORDER BY weekday, hour",
+        )?;
+
+        // 按本地日期过滤，截止时间所在的日期不含
+        let day = |time: Option<DateTime<Local>>, default: &str| {
+            time.map_or(default.to_string(), |t| t.date_naive().to_string())
+        };
+        let rows = stmt.query_map(params![day(since, ""), day(until, "~")], |row| {
+            Ok((
+                row.get::<_, u8>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut availability = Vec::new();
+        for row in rows {
+            let (weekday, hour, observations, total_size, nonempty) = row?;
+            availability.push(PoolAvailability {
+                weekday: Weekday::try_from(weekday)?,
+                hour,
+                observations,
+                avg_size: total_size as f64 / observations as f64,
+                nonempty_rate: nonempty as f64 / observations as f64,
+            });
+        }
+
+        Ok(availability)
+    }
+
+    /// 按学科和任务出现的时段统计竞争情况，自己认领的任务不计入，按竞争指数降序
+    pub fn contention(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<Contention>> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT subject_name, CAST(strftime('%H', appeared_at, 'unixepoch', 'localtime') AS INTEGER) AS hour,
+                    COUNT(*), COUNT(lifetime_ms), COALESCE(AVG(lifetime_ms), 0)
+             FROM pool_tasks
+             WHERE appeared_at >= ?1 AND appeared_at < ?2
+               AND task_id NOT IN (SELECT task_id FROM claims)
+             GROUP BY subject_name, hour",
+        )?;
+
+        let (since, until) = time_range(since, until);
+        let rows = stmt.query_map(params![since, until], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, f64>(4)?,
+            ))
+        })?;
+
+        let mut contention = Vec::new();
+        for row in rows {
+            let (subject, hour, seen, drained, avg_lifetime_ms) = row?;
+            let avg_lifetime = Duration::from_millis(avg_lifetime_ms as u64);
+            // 停留不到一分钟按一分钟计，避免轮询间隔很短时指数失真
+            let minutes = (avg_lifetime.as_secs_f64() / 60.0).max(1.0);
+            contention.push(Contention {
+                subject,
+                hour,
+                seen,
+                drained,
+                avg_lifetime,
+                index: drained as f64 / seen as f64 / minutes,
+            });
+        }
+        contention.sort_by(|a, b| b.index.total_cmp(&a.index));
+
+        Ok(contention)
+    }
+
+    /// 按天汇总认领数和成功率（本地日期）
+    pub fn daily_summary(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<DailySummary>> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT date(requested_at, 'unixepoch', 'localtime') AS day,
+                    SUM(succeeded), SUM(requested)
+             FROM claim_requests
+             WHERE requested_at >= ?1 AND requested_at < ?2
+             GROUP BY day ORDER BY day",
+        )?;
+
+        let (since, until) = time_range(since, until);
+        let rows = stmt.query_map(params![since, until], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+
+        let mut summaries = Vec::new();
+        for row in rows {
+            let (day, claims, requested) = row?;
+            summaries.push(DailySummary {
+                date: day.parse()?,
+                claims,
+                requested,
+                success_rate: if requested > 0 {
+                    claims as f64 / requested as f64
+                } else {
+                    0.0
+                },
+            });
+        }
+
+        Ok(summaries)
+    }
+
+    /// 按学科统计认领数，按数量降序
+    pub fn claims_by_subject(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<(String, i64)>> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT subject_name, COUNT(*) AS total FROM claims
+             WHERE claimed_at >= ?1 AND claimed_at < ?2
+             GROUP BY subject_name ORDER BY total DESC, subject_name",
+        )?;
+
+        let (since, until) = time_range(since, until);
+        let rows = stmt.query_map(params![since, until], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// 查询认领记录，按认领时间升序
+    pub fn query_claims(
+        &self,
+        since: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Result<Vec<ClaimedTask>> {
+        let conn = self.conn.lock().expect("claim store mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT task_type, attempt, claimed_at, task_json FROM claims
+             WHERE claimed_at >= ?1 AND claimed_at < ?2
+             ORDER BY claimed_at, id",
+        )?;
+
+        let (since, until) = time_range(since, until);
+        let rows = stmt.query_map(params![since, until], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut claims = Vec::new();
+        for row in rows {
+            let (task_type, attempt, claimed_at, task_json) = row?;
+            claims.push(ClaimedTask {
+                task: serde_json::from_str(&task_json)?,
+                task_type,
+                attempt,
+                claimed_at: DateTime::from_timestamp(claimed_at, 0)
+                    .unwrap_or_default()
+                    .with_timezone(&Local),
+            });
+        }
+
+        Ok(claims)
+    }
+}
+
+/// 将可选的时间范围转换为时间戳区间 [since, until)
+fn time_range(since: Option<DateTime<Local>>, until: Option<DateTime<Local>>) -> (i64, i64) {
+    (
+        since.map(|t| t.timestamp()).unwrap_or(i64::MIN),
+        until.map(|t| t.timestamp()).unwrap_or(i64::MAX),
+    )
+}
+
+/// 将事件流中的认领记录写入存储，直到事件通道关闭
+pub async fn record_events(mut receiver: broadcast::Receiver<ClaimEvent>, store: &ClaimStore) {
+    loop {
+        match receiver.recv().await {
+            Ok(ClaimEvent::TaskClaimed { task }) => {
+                if let Err(e) = store.insert_claim(&task) {
+                    warn!(error = %e, "{}", tr!(store_claim_failed, e));
+                }
+            }
+            Ok(ClaimEvent::Claimed {
+                requested, count, ..
+            }) => {
+                if let Err(e) = store.insert_request(requested, count, 0) {
+                    warn!(error = %e, "{}", tr!(store_request_failed, e));
+                }
+            }
+            Ok(ClaimEvent::PoolFetched { pool_size, .. }) => {
+                if let Err(e) = store.insert_pool_size(pool_size) {
+                    warn!(error = %e, "{}", tr!(store_pool_failed, e));
+                }
+            }
+            Ok(ClaimEvent::NewTasksAppeared { tasks, .. }) => {
+                if let Err(e) = store.insert_appeared(&tasks) {
+                    warn!(error = %e, "{}", tr!(store_pool_failed, e));
+                }
+            }
+            Ok(ClaimEvent::TasksDrained { tasks, .. }) => {
+                if let Err(e) = store.insert_drained(&tasks) {
+                    warn!(error = %e, "{}", tr!(store_pool_failed, e));
+                }
+            }
+            Ok(ClaimEvent::ClaimFailed {
+                task_ids, errno, ..
+            }) => {
+                if let Err(e) = store.insert_request(task_ids.len(), 0, errno) {
+                    warn!(error = %e, "{}", tr!(store_request_failed, e));
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(skipped, "{}", tr!(store_lagged, skipped));
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! OpenTelemetry 链路追踪导出
+//!
+//! 通过标准的 OTLP 环境变量配置，例如：
+//!
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: 导出地址，未设置时不启用导出
+//! - `OTEL_SERVICE_NAME`: 服务名，默认 `bedu-claim`
+
+use crate::tr;
+use anyhow::Result;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// 追踪导出守卫，drop 时刷新并关闭导出器
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("{}", tr!(otel_shutdown_failed, e));
+        }
+    }
+}
+
+/// 根据环境变量构建 OTLP 导出层，未配置导出地址时返回 None
+pub fn layer_from_env<S>() -> Result<Option<(OpenTelemetryLayer<S, SdkTracer>, TelemetryGuard)>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_err()
+        && std::env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").is_err()
+    {
+        return Ok(None);
+    }
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()?;
+
+    let mut resource = Resource::builder();
+    if std::env::var("OTEL_SERVICE_NAME").is_err() {
+        resource = resource.with_service_name("bedu-claim");
+    }
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("bedu-claim"));
+
+    Ok(Some((layer, TelemetryGuard { provider })))
+}
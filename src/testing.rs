@@ -0,0 +1,205 @@
+//! 离线测试工具（需启用 `testing` 特性）
+//!
+//! 基于 wiremock 启动本地的假百度教育服务器，内置任务列表、认领和用户信息的默认响应，
+//! 并可以切换到 10003、Cookie 过期、5xx 等失败场景，用于端到端测试认领逻辑。
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::api::TaskItem;
+use crate::client::{AutoClaimConfig, HttpClient};
+
+pub use crate::error::NOT_LOGGED_IN_ERRNO;
+
+/// 失败场景的优先级，高于默认响应
+const SCENARIO_PRIORITY: u8 = 1;
+
+/// 本地的假百度教育服务器
+///
+/// 启动后默认：用户信息返回 `mock_user`，任务池中有 3 个任务，认领全部成功，
+/// 我的任务为空。失败场景通过 `fail_claims`、`expire_cookie`、`server_error` 切换，
+/// 优先于默认响应生效；`reset` 恢复到初始状态。
+pub struct MockBeduServer {
+    server: MockServer,
+}
+
+impl MockBeduServer {
+    /// 启动服务器并挂载默认响应
+    pub async fn start() -> Self {
+        let mock = Self {
+            server: MockServer::start().await,
+        };
+        mock.mount_defaults().await;
+        mock
+    }
+
+    /// 服务器地址，用作 `server_base_url`
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// 连接到该服务器的HTTP客户端
+    pub fn client(&self) -> HttpClient {
+        HttpClient::new(self.uri(), "mock_cookie".to_string())
+    }
+
+    /// 连接到该服务器的认领配置，轮询间隔缩短为 10 毫秒且不休眠
+    pub fn claim_config(&self) -> AutoClaimConfig {
+        AutoClaimConfig {
+            server_base_url: self.uri(),
+            cookie: "mock_cookie".to_string(),
+            interval: 0.01,
+            pending_recheck_interval: None,
+            ..Default::default()
+        }
+    }
+
+    /// 替换任务池中的任务
+    pub async fn set_tasks(&self, tasks: Vec<TaskItem>) {
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/edushop/question/\w+/list$"))
+            .respond_with(ok(json!({ "total": tasks.len(), "list": tasks })))
+            .with_priority(SCENARIO_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// 认领返回指定错误，`times` 为 None 时一直失败，否则失败指定次数后恢复成功
+    pub async fn fail_claims(&self, errno: i32, errmsg: &str, times: Option<u64>) {
+        let mut mock = Mock::given(method("POST"))
+            .and(path_regex(r"^/edushop/question/\w+commit/claim$"))
+            .respond_with(error(errno, errmsg))
+            .with_priority(SCENARIO_PRIORITY);
+        if let Some(times) = times {
+            mock = mock.up_to_n_times(times);
+        }
+        mock.mount(&self.server).await;
+    }
+
+    /// 认领返回 10003（待完成任务过多）
+    pub async fn pending_limit(&self, times: Option<u64>) {
+        self.fail_claims(10003, "请先完成待审核任务", times).await;
+    }
+
+    /// 所有接口返回未登录，模拟 Cookie 过期
+    pub async fn expire_cookie(&self) {
+        Mock::given(path_regex(r"^/edushop/"))
+            .respond_with(error(NOT_LOGGED_IN_ERRNO, "用户未登录"))
+            .with_priority(SCENARIO_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// 所有接口返回指定的 HTTP 状态码和网关错误页
+    pub async fn server_error(&self, status: u16) {
+        Mock::given(path_regex(r"^/edushop/"))
+            .respond_with(ResponseTemplate::new(status).set_body_string("<html>Bad Gateway</html>"))
+            .with_priority(SCENARIO_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// 已收到的认领请求体，按接收顺序排列
+    pub async fn received_claims(&self) -> Vec<Value> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path().ends_with("commit/claim"))
+            .filter_map(|request| serde_json::from_slice(&request.body).ok())
+            .collect()
+    }
+
+    /// 清除所有场景和请求记录，恢复默认响应
+    pub async fn reset(&self) {
+        self.server.reset().await;
+        self.mount_defaults().await;
+    }
+
+    async fn mount_defaults(&self) {
+        Mock::given(method("GET"))
+            .and(path("/edushop/user/common/info"))
+            .respond_with(ok(json!({
+                "userName": "mock_user",
+                "roleNames": ["审核员"],
+                "roleLinks": [],
+                "avatar": "",
+            })))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/edushop/question/\w+/label$"))
+            .respond_with(ok(json!({
+                "filter": [
+                    { "id": "subject", "name": "学科", "type": "radio",
+                      "list": [{ "id": 2, "name": "数学" }, { "id": 3, "name": "语文" }] },
+                    { "id": "step", "name": "学段", "type": "radio",
+                      "list": [{ "id": 1, "name": "初中" }, { "id": 2, "name": "高中" }] },
+                    { "id": "clueType", "name": "线索类型", "type": "radio",
+                      "list": [{ "id": 1, "name": "拍搜" }] },
+                ]
+            })))
+            .mount(&self.server)
+            .await;
+
+        let tasks: Vec<TaskItem> = (1..=3).map(sample_task).collect();
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/edushop/question/\w+/list$"))
+            .respond_with(ok(json!({ "total": tasks.len(), "list": tasks })))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/edushop/question/\w+/mylist$"))
+            .respond_with(ok(json!({ "total": 0, "list": [] })))
+            .mount(&self.server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path_regex(r"^/edushop/question/\w+commit/(claim|release)$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "errno": 0,
+                "errmsg": "",
+                "data": null,
+            })))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+/// 构造一个数学、初中、拍搜的示例任务，线索ID为任务ID加 50000
+pub fn sample_task(task_id: i64) -> TaskItem {
+    TaskItem {
+        task_id,
+        clue_id: task_id + 50000,
+        brief: format!("示例题目{}", task_id),
+        step: 1,
+        subject: 2,
+        step_name: "初中".to_string(),
+        subject_name: "数学".to_string(),
+        clue_type: 1,
+        clue_type_name: "拍搜".to_string(),
+        state_name: "待认领".to_string(),
+        create_time: "2024-01-01 10:00:00".to_string(),
+        ..Default::default()
+    }
+}
+
+fn ok(data: Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "errno": 0,
+        "errmsg": "",
+        "data": data,
+    }))
+}
+
+fn error(errno: i32, errmsg: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "errno": errno,
+        "errmsg": errmsg,
+        "data": null,
+    }))
+}